@@ -188,6 +188,7 @@ where
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
     C::Api: xpallet_assets_rpc_runtime_api::XAssetsApi<Block, AccountId, Balance>,
+    C::Api: xpallet_assets_bridge_rpc_runtime_api::XAssetsBridgeApi<Block, Balance>,
     C::Api:
         xpallet_dex_spot_rpc_runtime_api::XSpotApi<Block, AccountId, Balance, BlockNumber, Balance>,
     C::Api: xpallet_gateway_bitcoin_rpc_runtime_api::XGatewayBitcoinApi<Block, AccountId>,
@@ -229,6 +230,7 @@ where
 {
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
     use substrate_frame_rpc_system::{FullSystem, SystemApi};
+    use xpallet_assets_bridge_rpc::{XAssetsBridge, XAssetsBridgeApi};
     use xpallet_assets_rpc::{Assets, XAssetsApi};
     use xpallet_btc_ledger_rpc::{BtcLedger, BtcLedgerApi};
     use xpallet_dex_spot_rpc::{XSpot, XSpotApi};
@@ -319,6 +321,9 @@ where
         client.clone(),
     )));
     io.extend_with(BtcLedgerApi::to_delegate(BtcLedger::new(client.clone())));
+    io.extend_with(XAssetsBridgeApi::to_delegate(XAssetsBridge::new(
+        client.clone(),
+    )));
 
     // EVM
     {
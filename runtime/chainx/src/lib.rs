@@ -45,9 +45,14 @@ use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
 use sp_consensus_babe::AllowedSlots::PrimaryAndSecondaryPlainSlots;
 
 use chainx_runtime_common::{BlockLength, BlockWeights, BASE_FEE};
-use xpallet_dex_spot::{Depth, FullPairInfo, RpcOrder, TradingPairId};
-use xpallet_mining_asset::{MinerLedger, MiningAssetInfo, MiningDividendInfo};
-use xpallet_mining_staking::{NominatorInfo, NominatorLedger, ValidatorInfo};
+use xpallet_dex_spot::{Candle, Depth, FullPairInfo, RpcOrder, TradeRecord, TradingPairId};
+use xpallet_mining_asset::{
+    FixedAssetPower, MinerLedger, MiningAssetInfo, MiningDividendInfo, PendingDividends,
+    ReferralRewardInfo,
+};
+use xpallet_mining_staking::{
+    EraIndex, EraRewardInfo, NominatorInfo, NominatorLedger, ValidatorEraRecord, ValidatorInfo,
+};
 use xpallet_support::traits::MultisigAddressFor;
 
 // A few exports that help ease life for downstream crates.
@@ -115,7 +120,9 @@ use pallet_evm::{
 };
 use sp_core::{H160, U256};
 use sp_runtime::traits::{Dispatchable, PostDispatchInfoOf};
+mod bind;
 mod precompiles;
+mod spv;
 mod withdraw;
 
 pub use precompiles::ChainXPrecompiles;
@@ -150,10 +157,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("chainx"),
     impl_name: create_runtime_str!("chainx-net"),
     authoring_version: 1,
-    spec_version: 32,
+    spec_version: 33,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 7,
+    transaction_version: 8,
     state_version: 0,
 };
 
@@ -1659,7 +1666,7 @@ impl xpallet_assets::Config for Runtime {
     type Currency = Balances;
     type TreasuryAccount = SimpleTreasuryAccount;
     type OnCreatedAccount = frame_system::Provider<Runtime>;
-    type OnAssetChanged = XMiningAsset;
+    type OnAssetChanged = (XMiningAsset, XSpot);
     type WeightInfo = xpallet_assets::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1753,9 +1760,32 @@ impl xpallet_gateway_bitcoin::Config for Runtime {
     type WeightInfo = xpallet_gateway_bitcoin::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+    pub const SpotPalletId: PalletId = PalletId(*b"pcx/spot");
+    // 1 minute, 5 minutes, 15 minutes, 1 hour, 4 hours and 1 day.
+    pub CandleIntervals: Vec<BlockNumber> = vec![
+        MINUTES,
+        5 * MINUTES,
+        15 * MINUTES,
+        HOURS,
+        4 * HOURS,
+        DAYS,
+    ];
+    pub const MaxCandles: u32 = 500;
+    pub const MaxRecentTrades: u32 = 100;
+    pub const TwapWindow: BlockNumber = HOURS;
+    pub const ConditionalOrderDeposit: Balance = DOLLARS;
+}
+
 impl xpallet_dex_spot::Config for Runtime {
     type Event = Event;
     type Price = Balance;
+    type PalletId = SpotPalletId;
+    type CandleIntervals = CandleIntervals;
+    type MaxCandles = MaxCandles;
+    type MaxRecentTrades = MaxRecentTrades;
+    type TwapWindow = TwapWindow;
+    type ConditionalOrderDeposit = ConditionalOrderDeposit;
     type WeightInfo = xpallet_dex_spot::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1786,6 +1816,9 @@ impl xpallet_mining_staking::Config for Runtime {
     type DetermineRewardPotAccount =
         xpallet_mining_staking::SimpleValidatorRewardPotAccountDeterminer<Runtime>;
     type ValidatorRegistration = Session;
+    type ValidatorElection = ();
+    type SlashCancelOrigin = EnsureRootOrHalfCouncil;
+    type UnsignedPriority = StakingUnsignedPriority;
     type WeightInfo = xpallet_mining_staking::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1822,6 +1855,12 @@ impl xpallet_mining_staking::Config for Runtime {
 
 - `Event`: 事件类型.
 - `Price`: 价格类型,使用余额(`Balance`)表示.
+- `PalletId`: 用于派生流动性池账户的 Pallet ID.
+- `CandleIntervals`: K 线的周期(以区块数计).
+- `MaxCandles`: 每个交易对每个周期保留的最大 K 线数量.
+- `MaxRecentTrades`: 每个交易对保留的最近成交记录的最大数量.
+- `TwapWindow`: 计算时间加权平均价格(TWAP)的窗口(以区块数计).
+- `ConditionalOrderDeposit`: 每个条件单需要预留的 PCX 押金.
 - `WeightInfo`: 权重信息.
 
 ### 财政库(`xpallet_support`)配置
@@ -1858,6 +1897,10 @@ impl xpallet_mining_asset::GatewayInterface<AccountId> for ReferralGetter {
     }
 }
 
+parameter_types! {
+    pub const AssetPowerUpdateInterval: BlockNumber = HOURS;
+}
+
 impl xpallet_mining_asset::Config for Runtime {
     type Event = Event;
     type StakingInterface = Self;
@@ -1865,6 +1908,8 @@ impl xpallet_mining_asset::Config for Runtime {
     type TreasuryAccount = SimpleTreasuryAccount;
     type DetermineRewardPotAccount =
         xpallet_mining_asset::SimpleAssetRewardPotAccountDeterminer<Runtime>;
+    type PriceOracle = XSpot;
+    type AssetPowerUpdateInterval = AssetPowerUpdateInterval;
     type WeightInfo = xpallet_mining_asset::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1892,6 +1937,8 @@ impl xpallet_btc_ledger::Config for Runtime {
 - `GatewayInterface`: 网关接口,使用 `ReferralGetter` 来获取推荐信息.
 - `TreasuryAccount`: 财政库账户,使用 `SimpleTreasuryAccount` 结构体.
 - `DetermineRewardPotAccount`: 确定奖励池账户的逻辑.
+- `PriceOracle`: 价格预言机,使用现货交易(`XSpot`)的时间加权平均价格.
+- `AssetPowerUpdateInterval`: 根据价格预言机更新资产挖矿算力的间隔(以区块数计).
 - `WeightInfo`: 权重信息,用于衡量操作的计算成本.
 
 ### 比特币账本(`xpallet_btc_ledger`)配置
@@ -2039,7 +2086,7 @@ construct_runtime!(
         XAssets: xpallet_assets::{Pallet, Call, Storage, Event<T>, Config<T>} = 26,
 
         // Mining, must be after XAssets.
-        XStaking: xpallet_mining_staking::{Pallet, Call, Storage, Event<T>, Config<T>} = 27,
+        XStaking: xpallet_mining_staking::{Pallet, Call, Storage, Event<T>, Config<T>, ValidateUnsigned} = 27,
         XMiningAsset: xpallet_mining_asset::{Pallet, Call, Storage, Event<T>, Config<T>} = 28,
 
         // Crypto gateway stuff.
@@ -2476,6 +2523,12 @@ impl_runtime_apis! {
         }
     }
 
+    impl xpallet_assets_bridge_rpc_runtime_api::XAssetsBridgeApi<Block, Balance> for Runtime {
+        fn xbtc_bridge_audit() -> Option<xpallet_assets_bridge::BridgeAuditReport<Balance>> {
+            XAssetsBridge::audit_xbtc()
+        }
+    }
+
     impl xpallet_mining_staking_rpc_runtime_api::XStakingApi<Block, AccountId, Balance, VoteWeight, BlockNumber> for Runtime {
         fn validators() -> Vec<ValidatorInfo<AccountId, Balance, VoteWeight, BlockNumber>> {
             XStaking::validators_info()
@@ -2492,6 +2545,12 @@ impl_runtime_apis! {
         fn nominator_info_of(who: AccountId) -> NominatorInfo<BlockNumber> {
             XStaking::nominator_info_of(who)
         }
+        fn era_reward_history(era: EraIndex) -> EraRewardInfo<AccountId, Balance> {
+            XStaking::era_reward_history(era)
+        }
+        fn validator_era_history(who: AccountId) -> Vec<ValidatorEraRecord<Balance>> {
+            XStaking::validator_era_history(who)
+        }
     }
 
     impl xpallet_dex_spot_rpc_runtime_api::XSpotApi<Block, AccountId, Balance, BlockNumber, Balance> for Runtime {
@@ -2506,6 +2565,18 @@ impl_runtime_apis! {
         fn depth(pair_id: TradingPairId, depth_size: u32) -> Option<Depth<Balance, Balance>> {
             XSpot::depth(pair_id, depth_size)
         }
+
+        fn aggregated_depth(pair_id: TradingPairId, bucket_ticks: u32, max_levels: u32) -> Option<Depth<Balance, Balance>> {
+            XSpot::aggregated_depth(pair_id, bucket_ticks, max_levels)
+        }
+
+        fn recent_trades(pair_id: TradingPairId, count: u32) -> Vec<TradeRecord<Balance, Balance, BlockNumber>> {
+            XSpot::recent_trades(pair_id, count)
+        }
+
+        fn candles(pair_id: TradingPairId, interval: BlockNumber, count: u32) -> Vec<Candle<Balance, Balance, BlockNumber>> {
+            XSpot::candles(pair_id, interval, count)
+        }
     }
 
     impl xpallet_mining_asset_rpc_runtime_api::XMiningAssetApi<Block, AccountId, Balance, MiningWeight, BlockNumber> for Runtime {
@@ -2520,6 +2591,25 @@ impl_runtime_apis! {
         fn miner_ledger(who: AccountId) -> BTreeMap<AssetId, MinerLedger<MiningWeight, BlockNumber>> {
             XMiningAsset::miner_ledger(who)
         }
+
+        fn pending_dividends(who: AccountId) -> PendingDividends<AccountId, Balance> {
+            PendingDividends {
+                staking: XStaking::staking_dividend_of(who.clone()),
+                mining: XMiningAsset::mining_dividend(who),
+            }
+        }
+
+        fn asset_power_at(asset_id: AssetId, at: BlockNumber) -> FixedAssetPower {
+            XMiningAsset::asset_power_at(asset_id, at)
+        }
+
+        fn referral_rewards(who: AccountId) -> BTreeMap<AssetId, ReferralRewardInfo<Balance>> {
+            XMiningAsset::referral_rewards(who)
+        }
+
+        fn miner_referrals(who: AccountId) -> BTreeMap<AssetId, AccountId> {
+            XMiningAsset::miner_referrals(who)
+        }
     }
 
     impl xpallet_gateway_records_rpc_runtime_api::XGatewayRecordsApi<Block, AccountId, Balance, BlockNumber> for Runtime {
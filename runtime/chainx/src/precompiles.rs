@@ -25,7 +25,7 @@ where
     /// Return all addresses that contain precompiles. This can be used to populate dummy code
     /// under the precompile.
     pub fn used_addresses() -> sp_std::vec::Vec<H160> {
-        sp_std::vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 1024, 1025, 1026, 1027]
+        sp_std::vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 1024, 1025, 1026, 1027, 1028, 1029]
             .into_iter()
             .map(hash)
             .collect()
@@ -40,7 +40,8 @@ impl<R> PrecompileSet for ChainXPrecompiles<R>
 where
     R: xpallet_assets_bridge::Config
         + xpallet_gateway_common::Config
-        + xpallet_gateway_records::Config,
+        + xpallet_gateway_records::Config
+        + xpallet_gateway_bitcoin::Config,
     Dispatch<R>: Precompile,
 {
     fn execute(
@@ -75,6 +76,12 @@ where
             a if a == hash(1027) => Some(crate::withdraw::Withdraw::<R>::execute(
                 input, target_gas, context, is_static,
             )),
+            a if a == hash(1028) => Some(crate::spv::BtcSpv::<R>::execute(
+                input, target_gas, context, is_static,
+            )),
+            a if a == hash(1029) => Some(crate::bind::BindAccount::<R>::execute(
+                input, target_gas, context, is_static,
+            )),
             _ => None,
         }
     }
@@ -0,0 +1,94 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use core::marker::PhantomData;
+use fp_evm::{
+    Context, ExitError, ExitRevert, ExitSucceed, PrecompileFailure, PrecompileOutput,
+    PrecompileResult,
+};
+use frame_support::log;
+use pallet_evm::Precompile;
+use sp_core::hexdisplay::HexDisplay;
+use sp_runtime::AccountId32;
+
+const BASE_GAS_COST: u64 = 100_000;
+
+/// Bind the caller(evm address) to a substrate account.
+///
+/// input = substrate pubkey(32 bytes) + sr25519 signature(64 bytes)
+///
+/// The signature is signed by the substrate account over
+/// `xpallet_assets_bridge::Pallet::evm_binding_payload(caller)`.
+pub struct BindAccount<T: xpallet_assets_bridge::Config> {
+    _marker: PhantomData<T>,
+}
+
+impl<T: xpallet_assets_bridge::Config> BindAccount<T> {
+    fn process(context: &Context, input: &[u8]) -> Result<(), PrecompileFailure> {
+        let revert = |output: &str| PrecompileFailure::Revert {
+            exit_status: ExitRevert::Reverted,
+            output: output.into(),
+            cost: BASE_GAS_COST,
+        };
+
+        if input.len() != 96 {
+            return Err(revert("invalid bind(0x405) input"));
+        }
+
+        let mut pubkey = [0u8; 32];
+        pubkey.copy_from_slice(&input[0..32]);
+        let who = T::AccountId::decode(&mut &AccountId32::new(pubkey).encode()[..])
+            .map_err(|_| revert("decode AccountId32 failed"))?;
+        let mut signature = [0u8; 64];
+        signature.copy_from_slice(&input[32..96]);
+
+        log::debug!(target: "evm-bind", "from(evm): {:?}", context.caller);
+        log::debug!(target: "evm-bind", "to(sub): {:?}", HexDisplay::from(&who.encode()));
+
+        xpallet_assets_bridge::Pallet::<T>::claim_account_from_evm(context.caller, who, &signature)
+            .map_err(|err| {
+                log::debug!(target: "evm-bind", "claim_account_from_evm: {:?}", err);
+                revert("bind account failed")
+            })
+    }
+}
+
+impl<T> Precompile for BindAccount<T>
+where
+    T: xpallet_assets_bridge::Config,
+{
+    fn execute(
+        input: &[u8],
+        target_gas: Option<u64>,
+        context: &Context,
+        is_static: bool,
+    ) -> PrecompileResult {
+        if let Some(gas) = target_gas {
+            if gas < BASE_GAS_COST {
+                return Err(PrecompileFailure::Error {
+                    exit_status: ExitError::OutOfGas,
+                });
+            }
+        }
+
+        if is_static {
+            return Err(PrecompileFailure::Revert {
+                exit_status: ExitRevert::Reverted,
+                output: "bind(0x405) can not be called statically".into(),
+                cost: BASE_GAS_COST,
+            });
+        }
+
+        Self::process(context, input).map_err(|err| {
+            log::warn!(target: "evm-bind", "err = {:?}", err);
+            err
+        })?;
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost: BASE_GAS_COST,
+            output: Default::default(),
+            logs: Default::default(),
+        })
+    }
+}
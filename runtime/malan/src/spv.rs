@@ -0,0 +1,114 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use core::marker::PhantomData;
+use fp_evm::{
+    Context, ExitError, ExitRevert, ExitSucceed, PrecompileFailure, PrecompileOutput,
+    PrecompileResult,
+};
+use frame_support::log;
+use pallet_evm::Precompile;
+use sp_core::{H256, U256};
+use sp_std::{vec, vec::Vec};
+
+use xpallet_gateway_bitcoin::types::BtcSpvProof;
+
+const BASE_GAS_COST: u64 = 50_000;
+const GAS_COST_PER_BYTE: u64 = 16;
+
+/// Verify a BTC transaction against the relayed header chain of `xpallet_gateway_bitcoin`.
+///
+/// input = block_hash(32 bytes) + raw_tx length(32 bytes) + raw_tx + merkle proof
+///
+/// `block_hash` is in the internal byte order, the merkle proof is a serialized
+/// `PartialMerkleTree`, the same as the `merkle_proof` of `push_transaction`.
+///
+/// output = txid(32 bytes) + confirmations(32 bytes) + outputs count(32 bytes) +
+/// for each output: value(32 bytes) + script length(32 bytes) + script padded to 32 bytes
+pub struct BtcSpv<T: xpallet_gateway_bitcoin::Config> {
+    _marker: PhantomData<T>,
+}
+
+impl<T: xpallet_gateway_bitcoin::Config> BtcSpv<T> {
+    fn process(input: &[u8], cost: u64) -> Result<BtcSpvProof, PrecompileFailure> {
+        let revert = |output: &str| PrecompileFailure::Revert {
+            exit_status: ExitRevert::Reverted,
+            output: output.into(),
+            cost,
+        };
+
+        if input.len() < 64 {
+            return Err(revert("invalid btc spv(0x404) input"));
+        }
+
+        let block_hash = H256::from_slice(&input[0..32]);
+        let tx_len = U256::from_big_endian(&input[32..64]);
+        if tx_len > U256::from(input.len() - 64) {
+            return Err(revert("invalid btc tx length"));
+        }
+        let tx_len = tx_len.low_u64() as usize;
+        let raw_tx = &input[64..64 + tx_len];
+        let merkle_proof = &input[64 + tx_len..];
+
+        log::debug!(target: "evm-btc-spv", "block_hash: {:?}", block_hash);
+
+        xpallet_gateway_bitcoin::Pallet::<T>::verify_spv_proof(raw_tx, merkle_proof, block_hash)
+            .map_err(|err| {
+                log::debug!(target: "evm-btc-spv", "verify_spv_proof: {:?}", err);
+                revert("verify btc spv proof failed")
+            })
+    }
+
+    fn encode_output(proof: BtcSpvProof) -> Vec<u8> {
+        let word = |value: U256| {
+            let mut out = [0u8; 32];
+            value.to_big_endian(&mut out);
+            out
+        };
+
+        let mut out = Vec::new();
+        out.extend_from_slice(proof.txid.as_bytes());
+        out.extend_from_slice(&word(proof.confirmations.into()));
+        out.extend_from_slice(&word(proof.outputs.len().into()));
+        for output in proof.outputs {
+            out.extend_from_slice(&word(output.value.into()));
+            out.extend_from_slice(&word(output.script_pubkey.len().into()));
+            let padded = (output.script_pubkey.len() + 31) / 32 * 32;
+            out.extend_from_slice(&output.script_pubkey);
+            out.extend(vec![0u8; padded - output.script_pubkey.len()]);
+        }
+        out
+    }
+}
+
+impl<T> Precompile for BtcSpv<T>
+where
+    T: xpallet_gateway_bitcoin::Config,
+{
+    fn execute(
+        input: &[u8],
+        target_gas: Option<u64>,
+        _context: &Context,
+        _: bool,
+    ) -> PrecompileResult {
+        let cost = BASE_GAS_COST.saturating_add(GAS_COST_PER_BYTE * input.len() as u64);
+        if let Some(gas) = target_gas {
+            if gas < cost {
+                return Err(PrecompileFailure::Error {
+                    exit_status: ExitError::OutOfGas,
+                });
+            }
+        }
+
+        let proof = Self::process(input, cost).map_err(|err| {
+            log::warn!(target: "evm-btc-spv", "err = {:?}", err);
+            err
+        })?;
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost,
+            output: Self::encode_output(proof),
+            logs: Default::default(),
+        })
+    }
+}
@@ -0,0 +1,17 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+
+pub use xpallet_assets_bridge::{AssetId, BridgeAuditReport};
+
+sp_api::decl_runtime_apis! {
+    pub trait XAssetsBridgeApi<Balance>
+    where
+        Balance: Codec,
+    {
+        fn xbtc_bridge_audit() -> Option<BridgeAuditReport<Balance>>;
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+use xp_rpc::{runtime_error_into_rpc_err, Result};
+
+use xpallet_assets_bridge_rpc_runtime_api::{
+    AssetId, BridgeAuditReport, XAssetsBridgeApi as AssetsBridgeRuntimeApi,
+};
+
+pub struct XAssetsBridge<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> XAssetsBridge<C, B> {
+    /// Create new `XAssetsBridge` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+#[rpc]
+pub trait XAssetsBridgeApi<BlockHash, Balance>
+where
+    Balance: Display + FromStr,
+{
+    /// Return the reconciliation of X-BTC bridging, None if the audit is disabled
+    #[rpc(name = "xassetsbridge_xbtcBridgeAudit")]
+    fn xbtc_bridge_audit(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<Option<RpcBridgeAuditReport<Balance>>>;
+}
+
+impl<C, Block, Balance> XAssetsBridgeApi<<Block as BlockT>::Hash, Balance>
+    for XAssetsBridge<C, Block>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: AssetsBridgeRuntimeApi<Block, Balance>,
+    Balance: Display + FromStr + Codec + PartialEq,
+{
+    fn xbtc_bridge_audit(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<RpcBridgeAuditReport<Balance>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.xbtc_bridge_audit(&at)
+            .map(|report| report.map(Into::into))
+            .map_err(runtime_error_into_rpc_err)
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBridgeAuditReport<Balance: Display + FromStr> {
+    pub asset_id: AssetId,
+    #[serde(with = "xp_rpc::serde_num_str")]
+    pub expected: Balance,
+    #[serde(with = "xp_rpc::serde_num_str")]
+    pub actual: Balance,
+    pub balanced: bool,
+}
+
+impl<Balance: Display + FromStr + PartialEq> From<BridgeAuditReport<Balance>>
+    for RpcBridgeAuditReport<Balance>
+{
+    fn from(report: BridgeAuditReport<Balance>) -> Self {
+        Self {
+            asset_id: report.asset_id,
+            balanced: report.is_balanced(),
+            expected: report.expected,
+            actual: report.actual,
+        }
+    }
+}
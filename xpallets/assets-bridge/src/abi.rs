@@ -48,6 +48,66 @@ pub fn burn_from_encode(account: H160, amount: u128) -> Vec<u8> {
     v
 }
 
+pub fn transfer_from_encode(from: H160, to: H160, amount: u128) -> Vec<u8> {
+    // signature ++ from ++ to ++ amount
+    let length = 4 + 32 + 32 + 32;
+    let mut v = Vec::with_capacity(length);
+
+    // bytes4(keccak256(bytes("transferFrom(address,address,uint256)"))
+    // 0x23b872dd
+    let sig_transfer_from = [35u8, 184, 114, 221];
+
+    v.extend_from_slice(&sig_transfer_from[..]);
+    v.extend_from_slice(&[0u8; 12][..]);
+    v.extend_from_slice(&from[..]);
+    v.extend_from_slice(&[0u8; 12][..]);
+    v.extend_from_slice(&to[..]);
+    v.extend_from_slice(&[0u8; 16][..]);
+    v.extend_from_slice(&amount.to_be_bytes()[..]);
+
+    v
+}
+
+pub fn transfer_encode(to: H160, amount: u128) -> Vec<u8> {
+    // signature ++ to ++ amount
+    let length = 4 + 32 + 32;
+    let mut v = Vec::with_capacity(length);
+
+    // bytes4(keccak256(bytes("transfer(address,uint256)"))
+    // 0xa9059cbb
+    let sig_transfer = [169u8, 5, 156, 187];
+
+    v.extend_from_slice(&sig_transfer[..]);
+    v.extend_from_slice(&[0u8; 12][..]);
+    v.extend_from_slice(&to[..]);
+    v.extend_from_slice(&[0u8; 16][..]);
+    v.extend_from_slice(&amount.to_be_bytes()[..]);
+
+    v
+}
+
+pub fn total_supply_encode() -> Vec<u8> {
+    // bytes4(keccak256(bytes("totalSupply()"))
+    // 0x18160ddd
+    [24u8, 22, 13, 221].to_vec()
+}
+
+pub fn balance_of_encode(account: H160) -> Vec<u8> {
+    // signature ++ account
+    let length = 4 + 32;
+    let mut v = Vec::with_capacity(length);
+
+    // bytes4(keccak256(bytes("balanceOf(address)"))
+    // 0x70a08231
+    let sig_balance_of = [112u8, 160, 130, 49];
+
+    v.extend_from_slice(&sig_balance_of[..]);
+    v.extend_from_slice(&[0u8; 12][..]);
+    v.extend_from_slice(&account[..]);
+
+    v
+}
+
 /*
 这段代码定义了两个函数,`mint_into_encode` 和 `burn_from_encode`,它们用于生成以太坊智能合约调用所需的编码数据.
 这些函数主要用于构建调用合约函数的交易数据,特别是在涉及代币铸造(mint)和销毁(burn)的场景中.
@@ -76,4 +136,4 @@ pub fn burn_from_encode(account: H160, amount: u128) -> Vec<u8> {
 
 这两个函数生成的数据可以作为以太坊交易的数据字段(`data`),用于调用相应的智能合约函数.
 这些编码的数据遵循了以太坊合约调用的标准格式,其中包含了函数选择器和必要的参数.
-*/
\ No newline at end of file
+*/
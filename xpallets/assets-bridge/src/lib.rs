@@ -12,6 +12,7 @@
 #![allow(dead_code)]
 
 pub mod abi;
+mod migrations;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -27,19 +28,44 @@ use frame_support::{
     traits::{Currency, ExistenceRequirement, IsType, ReservableCurrency, WithdrawReasons},
     transactional,
 };
-use sp_core::{ecdsa, H160, U256};
+use sp_core::{ecdsa, sr25519, H160, U256};
 use sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256};
-use sp_runtime::traits::{StaticLookup, UniqueSaturatedInto, Zero};
+use sp_runtime::traits::{Saturating, StaticLookup, UniqueSaturatedInto, Zero};
 use sp_std::vec::Vec;
 
 pub use chainx_primitives::AssetId;
-use pallet_evm::{AddressMapping, CallInfo, ExitReason, Runner};
+use pallet_evm::{AddressMapping, CallInfo, ExitReason, GasWeightMapping, Runner};
+use xpallet_assets::AssetType;
+use xpallet_assets_registrar::{AssetInfo, Chain};
 
 pub type EcdsaSignature = ecdsa::Signature;
 pub type AddressMappingOf<T> = <T as pallet_evm::Config>::AddressMapping;
 pub type BalanceOf<T> = <<T as xpallet_assets::Config>::Currency as Currency<
     <T as frame_system::Config>::AccountId,
 >>::Balance;
+pub type BridgeAuditReportOf<T> = BridgeAuditReport<BalanceOf<T>>;
+
+/// The gas limit of the erc20 calls made by the bridge.
+pub const EVM_CALL_GAS_LIMIT: u64 = 3_000_000;
+
+/// The reconciliation of a bridged asset between its on-chain balances and the
+/// deposits/withdrawals recorded by the gateway.
+#[derive(
+    Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, scale_info::TypeInfo,
+)]
+pub struct BridgeAuditReport<Balance> {
+    pub asset_id: AssetId,
+    /// The balance expected from the deposits and finished withdrawals.
+    pub expected: Balance,
+    /// The balance actually held across the bridged ledgers.
+    pub actual: Balance,
+}
+
+impl<Balance: PartialEq> BridgeAuditReport<Balance> {
+    pub fn is_balanced(&self) -> bool {
+        self.expected == self.actual
+    }
+}
 
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, scale_info::TypeInfo)]
 pub enum ActionType {
@@ -56,13 +82,22 @@ pub mod pallet {
     use super::*;
     use frame_system::pallet_prelude::*;
 
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
     #[pallet::config]
-    pub trait Config: frame_system::Config + xpallet_assets::Config + pallet_evm::Config {
+    pub trait Config:
+        frame_system::Config
+        + xpallet_assets::Config
+        + xpallet_gateway_records::Config
+        + xpallet_btc_ledger::Config
+        + pallet_evm::Config
+    {
         /// The overarching event type.
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
         /// The assets-bridge's inner evm caller.
@@ -87,6 +122,14 @@ pub mod pallet {
     #[pallet::getter(fn evm_accounts)]
     pub type EvmAccounts<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, H160, OptionQuery>;
 
+    /// The claim nonces of Evm Addresses, increased on every successful claim
+    /// to prevent the claim signatures from being replayed.
+    ///
+    /// ClaimNonces: map H160 => u64
+    #[pallet::storage]
+    #[pallet::getter(fn claim_nonces)]
+    pub type ClaimNonces<T: Config> = StorageMap<_, Twox64Concat, H160, u64, ValueQuery>;
+
     /// The Erc20 Contract Addresses for Asset Ids
     ///
     /// Erc20s: map AssetId => Option<H160>
@@ -101,6 +144,20 @@ pub mod pallet {
     #[pallet::getter(fn asset_ids)]
     pub type AssetIds<T: Config> = StorageMap<_, Twox64Concat, H160, AssetId, OptionQuery>;
 
+    /// The evm native Erc20 Contract Addresses for Asset Ids
+    ///
+    /// NativeErc20s: map AssetId => Option<H160>
+    #[pallet::storage]
+    #[pallet::getter(fn native_erc20s)]
+    pub type NativeErc20s<T: Config> = StorageMap<_, Twox64Concat, AssetId, H160, OptionQuery>;
+
+    /// The Asset Ids for evm native Erc20 Contract Addresses
+    ///
+    /// NativeAssetIds: map H160 => Option<AssetId>
+    #[pallet::storage]
+    #[pallet::getter(fn native_asset_ids)]
+    pub type NativeAssetIds<T: Config> = StorageMap<_, Twox64Concat, H160, AssetId, OptionQuery>;
+
     /// The Assets can back foreign chain
     ///
     /// AssetIds: Vec<AssetId>
@@ -118,6 +175,33 @@ pub mod pallet {
     #[pallet::getter(fn emergencies)]
     pub(super) type Emergencies<T: Config> = StorageValue<_, Vec<AssetId>, ValueQuery>;
 
+    /// Whether to pause X-BTC bridging when the X-BTC audit fails,
+    /// None means the audit is disabled.
+    #[pallet::storage]
+    #[pallet::getter(fn xbtc_audit)]
+    pub type XBtcAudit<T: Config> = StorageValue<_, bool, OptionQuery>;
+
+    /// The last failed X-BTC audit, to avoid emitting the same alarm in every block.
+    #[pallet::storage]
+    #[pallet::getter(fn last_xbtc_audit_failure)]
+    pub type LastXBtcAuditFailure<T: Config> = StorageValue<_, BridgeAuditReportOf<T>, OptionQuery>;
+
+    /// The bridging rate limit of the Assets, (period in blocks, max amount per period)
+    ///
+    /// RateLimits: map AssetId => Option<(BlockNumber, Balance)>
+    #[pallet::storage]
+    #[pallet::getter(fn rate_limits)]
+    pub type RateLimits<T: Config> =
+        StorageMap<_, Twox64Concat, AssetId, (T::BlockNumber, BalanceOf<T>), OptionQuery>;
+
+    /// The bridged amount of the Assets in current period, (period start, amount)
+    ///
+    /// RateUsages: map AssetId => (BlockNumber, Balance)
+    #[pallet::storage]
+    #[pallet::getter(fn rate_usages)]
+    pub type RateUsages<T: Config> =
+        StorageMap<_, Twox64Concat, AssetId, (T::BlockNumber, BalanceOf<T>), ValueQuery>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         /// The `AccountId` of the admin key.
@@ -169,6 +253,22 @@ pub mod pallet {
         UnPausedAll,
         // (asset_id, remove)
         BackForeign(AssetId, bool),
+        /// (asset_id, erc20_contract)
+        RegisterNativeErc20(AssetId, H160),
+        /// (asset_id, account_id, evm_address, amount, erc20_contract)
+        Erc20Locked(AssetId, T::AccountId, H160, BalanceOf<T>, H160),
+        /// (asset_id, account_id, evm_address, amount, erc20_contract)
+        Erc20Unlocked(AssetId, T::AccountId, H160, BalanceOf<T>, H160),
+        /// (asset_id, period, max_amount)
+        SetRateLimit(AssetId, Option<(T::BlockNumber, BalanceOf<T>)>),
+        /// (pause_on_mismatch), None means the audit is disabled
+        SetXBtcAudit(Option<bool>),
+        /// X-BTC + btc ledger balance is not equal to all the deposits minus finished withdrawals.
+        /// (report)
+        XBtcAuditFailed(BridgeAuditReportOf<T>),
+        /// The escrow balance is not equal to the erc20 total supply, the asset is paused.
+        /// (asset_id, escrow_balance, erc20_total_supply)
+        EscrowMismatch(AssetId, BalanceOf<T>, Option<u128>),
     }
 
     /// Error for evm accounts module.
@@ -206,6 +306,46 @@ pub mod pallet {
         ZeroBalance,
         /// Deprecated
         Deprecated,
+        /// Exceed the bridging rate limit of current period
+        ExceedRateLimit,
+        /// The evm native Erc20 asset must be on the Ethereum chain
+        InvalidAssetChain,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let version = StorageVersion::get::<Pallet<T>>();
+            if version >= STORAGE_VERSION {
+                return 0;
+            }
+            let mut weight = 0;
+            if version < 1 {
+                weight += migrations::migrate_to_v1::<T>();
+            }
+            if version < 2 {
+                weight += migrations::migrate_to_v2::<T>();
+            }
+            STORAGE_VERSION.put::<Pallet<T>>();
+            weight
+        }
+
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            let weight = T::DbWeight::get().reads_writes(7, 3);
+            if remaining_weight < weight || Self::xbtc_audit().is_none() {
+                return T::DbWeight::get().reads(1);
+            }
+            Self::check_xbtc_audit();
+            weight
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade() -> Result<(), &'static str> {
+            match Self::audit_xbtc() {
+                Some(report) if !report.is_balanced() => Err("X-BTC bridge audit failed"),
+                _ => Ok(()),
+            }
+        }
     }
 
     #[pallet::call]
@@ -218,41 +358,24 @@ pub mod pallet {
         /// Note: for general users
         ///
         /// - `eth_address`: The address to bind to the caller's account
-        /// - `eth_signature`: A signature generated by the address to prove ownership
+        /// - `eth_signature`: An EIP-712 signature of
+        ///   `Claim(bytes32 substrate,uint256 nonce)` generated by the address to prove
+        ///   ownership, see [`eip712_claim_digest`].
         #[pallet::weight(100_000_000u64)]
-        // #[transactional]
+        #[transactional]
         pub fn claim_account(
             origin: OriginFor<T>,
             eth_address: H160,
             eth_signature: EcdsaSignature,
         ) -> DispatchResult {
-            Err(Error::<T>::Deprecated.into())
-            // let who = ensure_signed(origin)?;
-            //
-            // // ensure account_id and eth_address has not been mapped
-            // ensure!(
-            //     !EvmAccounts::<T>::contains_key(&who),
-            //     Error::<T>::AccountIdHasMapped
-            // );
-            // ensure!(
-            //     !SubAccounts::<T>::contains_key(eth_address),
-            //     Error::<T>::EthAddressHasMapped
-            // );
-            //
-            // // recover evm address from signature
-            // let address = eth_recover(&eth_signature, &who.using_encoded(to_ascii_hex), &[][..])
-            //     .ok_or(Error::<T>::BadSignature)?;
-            //
-            // ensure!(eth_address == address, Error::<T>::InvalidSignature);
-            //
-            // <T as xpallet_assets::Config>::Currency::reserve(&who, T::ClaimBond::get())?;
-            //
-            // SubAccounts::<T>::insert(eth_address, &who);
-            // EvmAccounts::<T>::insert(&who, eth_address);
-            //
-            // Self::deposit_event(Event::ClaimAccount(who, eth_address));
-            //
-            // Ok(())
+            let who = ensure_signed(origin)?;
+
+            let digest = Self::claim_digest(&who, &eth_address);
+            let address =
+                eth_recover_digest(&eth_signature, &digest).ok_or(Error::<T>::BadSignature)?;
+            ensure!(eth_address == address, Error::<T>::InvalidSignature);
+
+            Self::apply_claim_account(who, eth_address)
         }
 
         /// Dissolve substrate accounts and EVM accounts.
@@ -284,40 +407,50 @@ pub mod pallet {
         ///
         /// - `asset_id`: The asset id
         /// - `amount`: Deposit amount
-        #[pallet::weight(1_000_000u64)]
+        #[pallet::weight(Pallet::<T>::bridge_call_weight(2))]
         #[transactional]
         pub fn deposit(
             origin: OriginFor<T>,
             asset_id: AssetId,
             amount: BalanceOf<T>,
         ) -> DispatchResultWithPostInfo {
-            Err(Error::<T>::Deprecated.into())
-            // let who = ensure_signed(origin)?;
-            // ensure!(!Self::is_in_emergency(asset_id), Error::<T>::InEmergency);
-            // ensure!(!amount.is_zero(), Error::<T>::ZeroBalance);
-            //
-            // // 1. check evm account
-            // let evm_account = Self::evm_accounts(&who).ok_or(Error::<T>::EthAddressHasNotMapped)?;
-            //
-            // // 2. burn asset
-            // let _ = xpallet_assets::Pallet::<T>::destroy_usable(&asset_id, &who, amount)?;
-            //
-            // // 3. mint erc20
-            // let erc20 = Self::erc20s(asset_id).ok_or(Error::<T>::ContractAddressHasNotMapped)?;
-            //
-            // let inputs = mint_into_encode(evm_account, amount.unique_saturated_into());
-            //
-            // Self::call_evm(erc20, inputs)?;
-            //
-            // Self::deposit_event(Event::DepositExecuted(
-            //     asset_id,
-            //     who,
-            //     evm_account,
-            //     amount,
-            //     erc20,
-            // ));
-            //
-            // Ok(Pays::No.into())
+            let who = ensure_signed(origin)?;
+            ensure!(!Self::is_in_emergency(asset_id), Error::<T>::InEmergency);
+            ensure!(!amount.is_zero(), Error::<T>::ZeroBalance);
+            xpallet_assets::Pallet::<T>::ensure_not_native_asset(&asset_id)?;
+
+            // 1. check evm account and erc20
+            let evm_account = Self::evm_accounts(&who).ok_or(Error::<T>::EthAddressHasNotMapped)?;
+            let erc20 = Self::erc20s(asset_id).ok_or(Error::<T>::ContractAddressHasNotMapped)?;
+            Self::check_rate_limit(asset_id, amount)?;
+
+            // 2. lock asset into escrow
+            xpallet_assets::Pallet::<T>::move_balance(
+                &asset_id,
+                &who,
+                AssetType::Usable,
+                &Self::escrow_account(),
+                AssetType::Locked,
+                amount,
+            )
+            .map_err(xpallet_assets::Error::<T>::from)?;
+
+            // 3. mint erc20
+            let inputs = mint_into_encode(evm_account, amount.unique_saturated_into());
+
+            Self::call_evm(erc20, inputs)?;
+
+            Self::deposit_event(Event::DepositExecuted(
+                asset_id,
+                who,
+                evm_account,
+                amount,
+                erc20,
+            ));
+
+            Self::check_escrow_invariant(asset_id);
+
+            Ok(Pays::No.into())
         }
 
         /// Withdraw from evm erc20 contracts into substrate assets
@@ -325,40 +458,138 @@ pub mod pallet {
         ///
         /// - `asset_id`: The asset id
         /// - `amount`: Withdraw amount
-        #[pallet::weight(1_000_000u64)]
+        #[pallet::weight(Pallet::<T>::bridge_call_weight(2))]
         #[transactional]
         pub fn withdraw(
             origin: OriginFor<T>,
             asset_id: AssetId,
             amount: BalanceOf<T>,
         ) -> DispatchResultWithPostInfo {
-            Err(Error::<T>::Deprecated.into())
-            // let who = ensure_signed(origin)?;
-            // ensure!(!Self::is_in_emergency(asset_id), Error::<T>::InEmergency);
-            // ensure!(!amount.is_zero(), Error::<T>::ZeroBalance);
-            //
-            // // 1. check evm account
-            // let evm_account = Self::evm_accounts(&who).ok_or(Error::<T>::EthAddressHasNotMapped)?;
-            //
-            // // 2. burn erc20
-            // let erc20 = Self::erc20s(asset_id).ok_or(Error::<T>::ContractAddressHasNotMapped)?;
-            //
-            // let inputs = burn_from_encode(evm_account, amount.unique_saturated_into());
-            //
-            // Self::call_evm(erc20, inputs)?;
-            //
-            // // 3. mint asset
-            // let _ = xpallet_assets::Pallet::<T>::issue(&asset_id, &who, amount)?;
-            //
-            // Self::deposit_event(Event::WithdrawExecuted(
-            //     asset_id,
-            //     who,
-            //     evm_account,
-            //     amount,
-            //     erc20,
-            // ));
-            //
-            // Ok(Pays::No.into())
+            let who = ensure_signed(origin)?;
+            ensure!(!Self::is_in_emergency(asset_id), Error::<T>::InEmergency);
+            ensure!(!amount.is_zero(), Error::<T>::ZeroBalance);
+            xpallet_assets::Pallet::<T>::ensure_not_native_asset(&asset_id)?;
+
+            // 1. check evm account and erc20
+            let evm_account = Self::evm_accounts(&who).ok_or(Error::<T>::EthAddressHasNotMapped)?;
+            let erc20 = Self::erc20s(asset_id).ok_or(Error::<T>::ContractAddressHasNotMapped)?;
+            Self::check_rate_limit(asset_id, amount)?;
+
+            // 2. burn erc20
+            let inputs = burn_from_encode(evm_account, amount.unique_saturated_into());
+
+            Self::call_evm(erc20, inputs)?;
+
+            // 3. unlock asset from escrow
+            xpallet_assets::Pallet::<T>::move_balance(
+                &asset_id,
+                &Self::escrow_account(),
+                AssetType::Locked,
+                &who,
+                AssetType::Usable,
+                amount,
+            )
+            .map_err(xpallet_assets::Error::<T>::from)?;
+
+            Self::deposit_event(Event::WithdrawExecuted(
+                asset_id,
+                who,
+                evm_account,
+                amount,
+                erc20,
+            ));
+
+            Self::check_escrow_invariant(asset_id);
+
+            Ok(Pays::No.into())
+        }
+
+        /// Lock evm native erc20 tokens into the bridge and mint the substrate assets.
+        /// The mapped evm address must approve the `EvmCaller` in advance.
+        /// Note: for general users
+        ///
+        /// - `asset_id`: The asset id
+        /// - `amount`: Lock amount, the minted amount is the erc20 balance actually received
+        ///    by the bridge for the fee-on-transfer tokens
+        #[pallet::weight(Pallet::<T>::bridge_call_weight(3))]
+        #[transactional]
+        pub fn lock_erc20(
+            origin: OriginFor<T>,
+            asset_id: AssetId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(!Self::is_in_emergency(asset_id), Error::<T>::InEmergency);
+            ensure!(!amount.is_zero(), Error::<T>::ZeroBalance);
+
+            // 1. check evm account and erc20
+            let evm_account = Self::evm_accounts(&who).ok_or(Error::<T>::EthAddressHasNotMapped)?;
+            let erc20 =
+                Self::native_erc20s(asset_id).ok_or(Error::<T>::ContractAddressHasNotMapped)?;
+            Self::check_rate_limit(asset_id, amount)?;
+
+            // 2. lock erc20 into the bridge, measure the received amount for the
+            // fee-on-transfer tokens
+            let bridge = T::EvmCaller::get();
+            let before = Self::erc20_balance_of(erc20, bridge).ok_or(Error::<T>::ExecutedFailed)?;
+            let inputs = transfer_from_encode(evm_account, bridge, amount.unique_saturated_into());
+            Self::call_erc20_transfer(erc20, inputs)?;
+            let after = Self::erc20_balance_of(erc20, bridge).ok_or(Error::<T>::ExecutedFailed)?;
+            let received: BalanceOf<T> = after.saturating_sub(before).unique_saturated_into();
+            ensure!(!received.is_zero(), Error::<T>::ZeroBalance);
+
+            // 3. mint asset
+            xpallet_assets::Pallet::<T>::issue(&asset_id, &who, received, false)?;
+
+            Self::deposit_event(Event::Erc20Locked(
+                asset_id,
+                who,
+                evm_account,
+                received,
+                erc20,
+            ));
+
+            Ok(Pays::No.into())
+        }
+
+        /// Burn the substrate assets and unlock evm native erc20 tokens from the bridge.
+        /// Note: for general users
+        ///
+        /// - `asset_id`: The asset id
+        /// - `amount`: Unlock amount
+        #[pallet::weight(Pallet::<T>::bridge_call_weight(1))]
+        #[transactional]
+        pub fn unlock_erc20(
+            origin: OriginFor<T>,
+            asset_id: AssetId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(!Self::is_in_emergency(asset_id), Error::<T>::InEmergency);
+            ensure!(!amount.is_zero(), Error::<T>::ZeroBalance);
+
+            // 1. check evm account and erc20
+            let evm_account = Self::evm_accounts(&who).ok_or(Error::<T>::EthAddressHasNotMapped)?;
+            let erc20 =
+                Self::native_erc20s(asset_id).ok_or(Error::<T>::ContractAddressHasNotMapped)?;
+            Self::check_rate_limit(asset_id, amount)?;
+
+            // 2. burn asset
+            xpallet_assets::Pallet::<T>::destroy_usable(&asset_id, &who, amount)?;
+
+            // 3. unlock erc20 from the bridge
+            let inputs = transfer_encode(evm_account, amount.unique_saturated_into());
+            Self::call_erc20_transfer(erc20, inputs)?;
+
+            Self::deposit_event(Event::Erc20Unlocked(
+                asset_id,
+                who,
+                evm_account,
+                amount,
+                erc20,
+            ));
+
+            Ok(Pays::No.into())
         }
 
         /// Teleport native currency between substrate account and evm address
@@ -453,11 +684,12 @@ pub mod pallet {
 
             // ensure asset_id and erc20 address has not been mapped
             ensure!(
-                !Erc20s::<T>::contains_key(&asset_id),
+                !Erc20s::<T>::contains_key(&asset_id)
+                    && !NativeErc20s::<T>::contains_key(&asset_id),
                 Error::<T>::AssetIdHasMapped
             );
             ensure!(
-                !AssetIds::<T>::contains_key(&erc20),
+                !AssetIds::<T>::contains_key(&erc20) && !NativeAssetIds::<T>::contains_key(&erc20),
                 Error::<T>::ContractAddressHasMapped
             );
 
@@ -469,6 +701,54 @@ pub mod pallet {
             Ok(Pays::No.into())
         }
 
+        /// Register evm native erc20 contracts as new substrate assets
+        /// Note: for root
+        ///
+        /// - `asset_id`: The new asset id
+        /// - `erc20`: The evm native erc20 contract address
+        /// - `asset`: The asset info, must be on the `Ethereum` chain
+        /// - `has_mining_rights`: Whether the asset has mining rights
+        #[pallet::weight(100_000_000u64)]
+        pub fn register_native_erc20(
+            origin: OriginFor<T>,
+            asset_id: AssetId,
+            erc20: H160,
+            asset: AssetInfo,
+            has_mining_rights: bool,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ensure!(
+                asset.chain() == Chain::Ethereum,
+                Error::<T>::InvalidAssetChain
+            );
+
+            // ensure asset_id and erc20 address has not been mapped
+            ensure!(
+                !Erc20s::<T>::contains_key(&asset_id)
+                    && !NativeErc20s::<T>::contains_key(&asset_id),
+                Error::<T>::AssetIdHasMapped
+            );
+            ensure!(
+                !AssetIds::<T>::contains_key(&erc20) && !NativeAssetIds::<T>::contains_key(&erc20),
+                Error::<T>::ContractAddressHasMapped
+            );
+
+            xpallet_assets_registrar::Pallet::<T>::register(
+                frame_system::RawOrigin::Root.into(),
+                asset_id,
+                asset,
+                true,
+                has_mining_rights,
+            )?;
+
+            NativeErc20s::<T>::insert(asset_id, erc20);
+            NativeAssetIds::<T>::insert(erc20, asset_id);
+
+            Self::deposit_event(Event::RegisterNativeErc20(asset_id, erc20));
+
+            Ok(Pays::No.into())
+        }
+
         /// Pause assets bridge deposit and withdraw
         /// Note: for admin
         ///
@@ -485,7 +765,7 @@ pub mod pallet {
                 if let Some(id) = asset_id {
                     // ensure asset_id and erc20 address has not been mapped
                     ensure!(
-                        Erc20s::<T>::contains_key(&id),
+                        Erc20s::<T>::contains_key(&id) || NativeErc20s::<T>::contains_key(&id),
                         Error::<T>::AssetIdHasNotMapped
                     );
                     if !Self::is_in_emergency(id) {
@@ -498,6 +778,9 @@ pub mod pallet {
                     for id in AssetIds::<T>::iter_values() {
                         emergencies.push(id);
                     }
+                    for id in NativeAssetIds::<T>::iter_values() {
+                        emergencies.push(id);
+                    }
 
                     Self::deposit_event(Event::PausedAll);
                 }
@@ -522,7 +805,7 @@ pub mod pallet {
                 if let Some(id) = asset_id {
                     // ensure asset_id and erc20 address has been mapped
                     ensure!(
-                        Erc20s::<T>::contains_key(&id),
+                        Erc20s::<T>::contains_key(&id) || NativeErc20s::<T>::contains_key(&id),
                         Error::<T>::AssetIdHasNotMapped
                     );
 
@@ -541,6 +824,57 @@ pub mod pallet {
             })
         }
 
+        /// Set the bridging rate limit of assets
+        /// Note: for admin
+        ///
+        /// - `asset_id`: The asset id
+        /// - `limit`: None will remove the limit, Some((period, max_amount)) allows bridging
+        ///    at most `max_amount` of the asset (deposit and withdraw) every `period` blocks
+        #[pallet::weight(100_000_000u64)]
+        pub fn set_rate_limit(
+            origin: OriginFor<T>,
+            asset_id: AssetId,
+            limit: Option<(T::BlockNumber, BalanceOf<T>)>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(Some(who) == Self::admin_key(), Error::<T>::RequireAdmin);
+            ensure!(
+                Erc20s::<T>::contains_key(&asset_id) || NativeErc20s::<T>::contains_key(&asset_id),
+                Error::<T>::AssetIdHasNotMapped
+            );
+
+            match limit {
+                Some(limit) => RateLimits::<T>::insert(asset_id, limit),
+                None => RateLimits::<T>::remove(asset_id),
+            }
+            RateUsages::<T>::remove(asset_id);
+
+            Self::deposit_event(Event::SetRateLimit(asset_id, limit));
+
+            Ok(Pays::No.into())
+        }
+
+        /// Enable or disable the audit of X-BTC bridging
+        /// Note: for admin
+        ///
+        /// - `pause_on_mismatch`: None will disable the audit, Some(pause) will enable the audit
+        ///    and pause X-BTC bridging on mismatch if `pause` is true
+        #[pallet::weight(100_000_000u64)]
+        pub fn set_xbtc_audit(
+            origin: OriginFor<T>,
+            pause_on_mismatch: Option<bool>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(Some(who) == Self::admin_key(), Error::<T>::RequireAdmin);
+
+            XBtcAudit::<T>::set(pause_on_mismatch);
+            LastXBtcAuditFailure::<T>::kill();
+
+            Self::deposit_event(Event::SetXBtcAudit(pause_on_mismatch));
+
+            Ok(Pays::No.into())
+        }
+
         /// Add assets which can back add_back_foreign chain
         /// Note: for admin
         ///
@@ -597,15 +931,21 @@ pub mod pallet {
             let who = ensure_signed(origin)?;
             ensure!(Some(who) == Self::admin_key(), Error::<T>::RequireAdmin);
 
-            let erc20 = Self::erc20s(&asset_id).ok_or(Error::<T>::AssetIdHasNotMapped)?;
-
-            ensure!(
-                AssetIds::<T>::contains_key(&erc20),
-                Error::<T>::ContractAddressHasMapped
-            );
-
-            Erc20s::<T>::remove(&asset_id);
-            AssetIds::<T>::remove(&erc20);
+            let erc20 = if let Some(erc20) = Self::erc20s(&asset_id) {
+                ensure!(
+                    AssetIds::<T>::contains_key(&erc20),
+                    Error::<T>::ContractAddressHasMapped
+                );
+                Erc20s::<T>::remove(&asset_id);
+                AssetIds::<T>::remove(&erc20);
+                erc20
+            } else {
+                let erc20 =
+                    Self::native_erc20s(&asset_id).ok_or(Error::<T>::AssetIdHasNotMapped)?;
+                NativeErc20s::<T>::remove(&asset_id);
+                NativeAssetIds::<T>::remove(&erc20);
+                erc20
+            };
 
             // clear emergency
             if Self::is_in_emergency(asset_id) {
@@ -749,6 +1089,10 @@ impl<T: Config> Pallet<T> {
             &mapping_account,
             amount.unique_saturated_into(),
         );
+        xpallet_gateway_records::Pallet::<T>::note_deposit(
+            asset_id,
+            amount.unique_saturated_into(),
+        );
 
         Ok(())
     }
@@ -824,13 +1168,258 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// The EIP-712 digest that `eth_address` signs to claim `who`.
+    pub fn claim_digest(who: &T::AccountId, eth_address: &H160) -> [u8; 32] {
+        eip712_claim_digest(
+            &Self::to_bytes32(who),
+            Self::claim_nonces(eth_address),
+            <T as pallet_evm::Config>::ChainId::get(),
+            &Self::genesis_hash(),
+        )
+    }
+
+    /// The message that `who` signs(sr25519) to be claimed by `eth_address` from the evm side.
+    pub fn evm_binding_payload(eth_address: &H160) -> Vec<u8> {
+        evm_binding_message(
+            eth_address,
+            Self::claim_nonces(eth_address),
+            <T as pallet_evm::Config>::ChainId::get(),
+            &Self::genesis_hash(),
+        )
+    }
+
+    /// Claim account mapping initiated by the evm address `caller`,
+    /// `signature` is the sr25519 signature of [`Self::evm_binding_payload`] signed by `who`.
+    pub fn claim_account_from_evm(
+        caller: H160,
+        who: T::AccountId,
+        signature: &[u8; 64],
+    ) -> DispatchResult {
+        let payload = Self::evm_binding_payload(&caller);
+        ensure!(
+            sp_io::crypto::sr25519_verify(
+                &sr25519::Signature::from_raw(*signature),
+                &payload,
+                &sr25519::Public::from_raw(Self::to_bytes32(&who)),
+            ),
+            Error::<T>::InvalidSignature
+        );
+
+        Self::apply_claim_account(who, caller)
+    }
+
+    fn apply_claim_account(who: T::AccountId, eth_address: H160) -> DispatchResult {
+        // ensure account_id and eth_address has not been mapped
+        ensure!(
+            !EvmAccounts::<T>::contains_key(&who),
+            Error::<T>::AccountIdHasMapped
+        );
+        ensure!(
+            !SubAccounts::<T>::contains_key(eth_address),
+            Error::<T>::EthAddressHasMapped
+        );
+
+        <T as xpallet_assets::Config>::Currency::reserve(&who, T::ClaimBond::get())?;
+
+        ClaimNonces::<T>::mutate(eth_address, |nonce| *nonce = nonce.saturating_add(1));
+        SubAccounts::<T>::insert(eth_address, &who);
+        EvmAccounts::<T>::insert(&who, eth_address);
+
+        Self::deposit_event(Event::ClaimAccount(who, eth_address));
+
+        Ok(())
+    }
+
+    fn genesis_hash() -> [u8; 32] {
+        Self::to_bytes32(&frame_system::Pallet::<T>::block_hash(
+            T::BlockNumber::zero(),
+        ))
+    }
+
+    /// 32 bytes accounts and hashes are used as is, others are hashed.
+    fn to_bytes32<E: Encode>(value: &E) -> [u8; 32] {
+        value.using_encoded(|encoded| {
+            let mut out = [0u8; 32];
+            if encoded.len() == 32 {
+                out.copy_from_slice(encoded);
+            } else {
+                out = keccak_256(encoded);
+            }
+            out
+        })
+    }
+
+    /// The X-BTC held across xpallet_assets and the btc ledger.
+    pub(crate) fn xbtc_bridged_balance() -> BalanceOf<T> {
+        let xbtc_asset_id = 1;
+        let ledger_total: u128 =
+            xpallet_btc_ledger::Pallet::<T>::get_total().unique_saturated_into();
+        xpallet_assets::Pallet::<T>::total_issuance(&xbtc_asset_id)
+            .saturating_add(ledger_total.unique_saturated_into())
+    }
+
+    /// Reconcile X-BTC + btc ledger balance against all the deposits minus finished
+    /// withdrawals recorded by gateway records. None if the audit is disabled.
+    pub fn audit_xbtc() -> Option<BridgeAuditReportOf<T>> {
+        Self::xbtc_audit()?;
+        Some(Self::xbtc_audit_report())
+    }
+
+    pub(crate) fn xbtc_audit_report() -> BridgeAuditReportOf<T> {
+        let xbtc_asset_id = 1;
+        let deposited = xpallet_gateway_records::Pallet::<T>::total_deposited(xbtc_asset_id);
+        let withdrawn = xpallet_gateway_records::Pallet::<T>::total_withdrawn(xbtc_asset_id);
+
+        BridgeAuditReportOf::<T> {
+            asset_id: xbtc_asset_id,
+            expected: deposited.saturating_sub(withdrawn),
+            actual: Self::xbtc_bridged_balance(),
+        }
+    }
+
+    /// Run the X-BTC audit, emit an alarm and optionally pause X-BTC bridging on mismatch.
+    pub fn check_xbtc_audit() {
+        let (pause_on_mismatch, report) = match Self::xbtc_audit() {
+            Some(pause_on_mismatch) => (pause_on_mismatch, Self::xbtc_audit_report()),
+            None => return,
+        };
+
+        if report.is_balanced() {
+            LastXBtcAuditFailure::<T>::kill();
+            return;
+        }
+        if Self::last_xbtc_audit_failure() == Some(report) {
+            return;
+        }
+
+        frame_support::log::error!(
+            target: "runtime::assets-bridge",
+            "[check_xbtc_audit] expected:{:?}, actual:{:?}",
+            report.expected,
+            report.actual
+        );
+
+        LastXBtcAuditFailure::<T>::put(report);
+        if pause_on_mismatch && !Self::is_in_emergency(report.asset_id) {
+            Emergencies::<T>::mutate(|emergencies| emergencies.push(report.asset_id));
+            Self::deposit_event(Event::Paused(report.asset_id));
+        }
+        Self::deposit_event(Event::XBtcAuditFailed(report));
+    }
+
+    /// The escrow account which locks the substrate assets bridged into evm.
+    pub fn escrow_account() -> T::AccountId {
+        T::AddressMapping::into_account_id(T::EvmCaller::get())
+    }
+
+    /// The weight of `calls` erc20 calls made with the full gas limit.
+    pub fn evm_calls_weight(calls: u64) -> Weight {
+        <T as pallet_evm::Config>::GasWeightMapping::gas_to_weight(EVM_CALL_GAS_LIMIT)
+            .saturating_mul(calls)
+    }
+
+    /// The weight of the bridging calls which make `evm_calls` erc20 calls.
+    pub fn bridge_call_weight(evm_calls: u64) -> Weight {
+        Self::evm_calls_weight(evm_calls).saturating_add(T::DbWeight::get().reads_writes(12, 6))
+    }
+
+    /// Ensure the bridged amount of current period does not exceed the rate limit.
+    fn check_rate_limit(asset_id: AssetId, amount: BalanceOf<T>) -> DispatchResult {
+        let (period, max_amount) = match Self::rate_limits(asset_id) {
+            Some(limit) => limit,
+            None => return Ok(()),
+        };
+
+        let now = frame_system::Pallet::<T>::block_number();
+        RateUsages::<T>::try_mutate(asset_id, |(start, used)| {
+            if now >= start.saturating_add(period) {
+                *start = now;
+                *used = Zero::zero();
+            }
+            let new_used = used.saturating_add(amount);
+            ensure!(new_used <= max_amount, Error::<T>::ExceedRateLimit);
+            *used = new_used;
+            Ok(())
+        })
+    }
+
+    /// Check that the escrow balance equals the erc20 total supply of the asset,
+    /// otherwise pause the asset.
+    pub fn check_escrow_invariant(asset_id: AssetId) -> bool {
+        let erc20 = match Self::erc20s(asset_id) {
+            Some(erc20) => erc20,
+            None => return true,
+        };
+
+        let escrow = xpallet_assets::Pallet::<T>::asset_balance_of(
+            &Self::escrow_account(),
+            &asset_id,
+            AssetType::Locked,
+        );
+        let total_supply = Self::erc20_total_supply(erc20);
+        if total_supply == Some(escrow.unique_saturated_into()) {
+            return true;
+        }
+
+        frame_support::log::error!(
+            target: "runtime::assets-bridge",
+            "[check_escrow_invariant] asset:{}, escrow:{:?}, total_supply:{:?}",
+            asset_id,
+            escrow,
+            total_supply
+        );
+
+        if !Self::is_in_emergency(asset_id) {
+            Emergencies::<T>::mutate(|emergencies| emergencies.push(asset_id));
+            Self::deposit_event(Event::Paused(asset_id));
+        }
+        Self::deposit_event(Event::EscrowMismatch(asset_id, escrow, total_supply));
+
+        false
+    }
+
+    /// Get the total supply of the erc20 contract.
+    pub fn erc20_total_supply(erc20: H160) -> Option<u128> {
+        Self::call_erc20_u128(erc20, total_supply_encode())
+    }
+
+    /// Get the erc20 balance of the evm account.
+    pub fn erc20_balance_of(erc20: H160, account: H160) -> Option<u128> {
+        Self::call_erc20_u128(erc20, balance_of_encode(account))
+    }
+
+    fn call_erc20_u128(erc20: H160, inputs: Vec<u8>) -> Option<u128> {
+        let output = Self::call_evm_with_output(erc20, inputs).ok()?;
+        if output.len() != 32 {
+            return None;
+        }
+        let value = U256::from_big_endian(&output);
+        if value > U256::from(u128::MAX) {
+            return None;
+        }
+        Some(value.low_u128())
+    }
+
     fn call_evm(erc20: H160, inputs: Vec<u8>) -> DispatchResult {
+        Self::call_evm_with_output(erc20, inputs).map(|_| ())
+    }
+
+    /// Call erc20 `transfer` or `transferFrom`, tokens returning nothing are treated as success.
+    fn call_erc20_transfer(erc20: H160, inputs: Vec<u8>) -> DispatchResult {
+        let output = Self::call_evm_with_output(erc20, inputs)?;
+        match output.last() {
+            None | Some(&1) => Ok(()),
+            _ => Err(Error::<T>::ExecutedFailed.into()),
+        }
+    }
+
+    fn call_evm_with_output(erc20: H160, inputs: Vec<u8>) -> Result<Vec<u8>, DispatchError> {
         match T::Runner::call(
             T::EvmCaller::get(),
             erc20,
             inputs,
             U256::default(),
-            3_000_000,
+            EVM_CALL_GAS_LIMIT,
             None,
             None,
             None,
@@ -840,8 +1429,9 @@ impl<T: Config> Pallet<T> {
         ) {
             Ok(CallInfo {
                 exit_reason: ExitReason::Succeed(_),
+                value,
                 ..
-            }) => Ok(()),
+            }) => Ok(value),
             _ => Err(Error::<T>::ExecutedFailed.into()),
         }
     }
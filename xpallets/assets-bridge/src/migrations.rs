@@ -0,0 +1,98 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{
+    log::{info, warn},
+    storage::{with_transaction, TransactionOutcome},
+    traits::Get,
+    weights::Weight,
+};
+use sp_runtime::{
+    traits::{Saturating, UniqueSaturatedInto, Zero},
+    DispatchError,
+};
+
+use xpallet_assets::AssetType;
+
+use crate::{Config, Erc20s, Pallet};
+
+/// Seeds the escrow with the erc20 supply minted under the former burn/mint model,
+/// so that the existing erc20 tokens are backed by the locked assets.
+pub fn migrate_to_v1<T: Config>() -> Weight {
+    let escrow = Pallet::<T>::escrow_account();
+    let mut visited = 0u64;
+    let mut seeded = 0u64;
+    for (asset_id, erc20) in Erc20s::<T>::iter() {
+        visited += 1;
+        let total_supply = match Pallet::<T>::erc20_total_supply(erc20) {
+            Some(total_supply) => total_supply,
+            None => {
+                warn!(
+                    target: "runtime::assets-bridge",
+                    "[migrate_to_v1] failed to get the total supply of asset:{}, erc20:{:?}",
+                    asset_id,
+                    erc20
+                );
+                continue;
+            }
+        };
+        let locked: u128 =
+            xpallet_assets::Pallet::<T>::asset_balance_of(&escrow, &asset_id, AssetType::Locked)
+                .unique_saturated_into();
+        let missing = total_supply.saturating_sub(locked);
+        if missing == 0 {
+            continue;
+        }
+
+        let missing = missing.unique_saturated_into();
+        let result = with_transaction(|| {
+            let result = xpallet_assets::Pallet::<T>::issue(&asset_id, &escrow, missing, false)
+                .and_then(|_| {
+                    xpallet_assets::Pallet::<T>::move_balance(
+                        &asset_id,
+                        &escrow,
+                        AssetType::Usable,
+                        &escrow,
+                        AssetType::Locked,
+                        missing,
+                    )
+                    .map_err(|err| DispatchError::from(xpallet_assets::Error::<T>::from(err)))
+                });
+            if result.is_ok() {
+                TransactionOutcome::Commit(result)
+            } else {
+                TransactionOutcome::Rollback(result)
+            }
+        });
+        match result {
+            Ok(_) => seeded += 1,
+            Err(err) => warn!(
+                target: "runtime::assets-bridge",
+                "[migrate_to_v1] failed to seed the escrow of asset:{}, err:{:?}",
+                asset_id,
+                err
+            ),
+        }
+    }
+    info!(
+        target: "runtime::assets-bridge",
+        "[migrate_to_v1] {} of {} escrows seeded", seeded, visited
+    );
+    T::DbWeight::get()
+        .reads_writes(visited * 2 + seeded * 4, seeded * 6)
+        .saturating_add(Pallet::<T>::evm_calls_weight(visited))
+}
+
+/// Seeds the gateway records with the X-BTC held before the deposits and withdrawals
+/// were recorded, so that the X-BTC audit checks the absolute balance from now on.
+pub fn migrate_to_v2<T: Config>() -> Weight {
+    let report = Pallet::<T>::xbtc_audit_report();
+    let unrecorded = report.actual.saturating_sub(report.expected);
+    if !unrecorded.is_zero() {
+        xpallet_gateway_records::Pallet::<T>::note_deposit(report.asset_id, unrecorded);
+    }
+    info!(
+        target: "runtime::assets-bridge",
+        "[migrate_to_v2] {:?} unrecorded X-BTC seeded", unrecorded
+    );
+    T::DbWeight::get().reads_writes(4, 1)
+}
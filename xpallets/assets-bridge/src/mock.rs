@@ -3,10 +3,16 @@
 pub use crate as xassets_bridge;
 pub use xassets_bridge::{AssetId, Config, Error, Event as XAssetsBridgeEvent};
 
+use std::{cell::RefCell, collections::BTreeMap};
+
+use fp_evm::{
+    Context, ExitRevert, ExitSucceed, PrecompileFailure, PrecompileOutput, PrecompileResult,
+};
 use frame_support::traits::ConstU32;
-use frame_support::{parameter_types, traits::GenesisBuild};
+use frame_support::{parameter_types, traits::GenesisBuild, PalletId};
 use frame_system as system;
-use sp_core::{H160, H256};
+use pallet_evm::PrecompileSet;
+use sp_core::{H160, H256, U256};
 pub use sp_runtime::{
     testing::Header,
     traits::{BlakeTwo256, IdentityLookup},
@@ -29,6 +35,8 @@ frame_support::construct_runtime!(
         Evm: pallet_evm::{Pallet, Call, Storage, Config, Event<T>},
         XAssetsRegistrar: xpallet_assets_registrar::{Pallet, Call, Config, Storage, Event<T>},
         XAssets: xpallet_assets::{Pallet, Call, Config<T>, Storage, Event<T>},
+        XGatewayRecords: xpallet_gateway_records::{Pallet, Call, Storage, Event<T>},
+        XBtcLedger: xpallet_btc_ledger::{Pallet, Call, Storage, Config<T>, Event<T>},
         XAssetsBridge: xassets_bridge::{Pallet, Call, Storage, Config<T>, Event<T>},
     }
 );
@@ -121,17 +129,33 @@ impl xpallet_assets::Config for Test {
     type WeightInfo = ();
 }
 
+impl xpallet_gateway_records::Config for Test {
+    type Event = Event;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const BtcLedgerPalletId: PalletId = PalletId(*b"pcx/trsy");
+}
+
+impl xpallet_btc_ledger::Config for Test {
+    type Balance = u128;
+    type Event = Event;
+    type CouncilOrigin = frame_system::EnsureRoot<AccountId32>;
+    type PalletId = BtcLedgerPalletId;
+}
+
 impl pallet_evm::Config for Test {
     type FeeCalculator = ();
     type GasWeightMapping = ();
     type CallOrigin = pallet_evm::EnsureAddressRoot<Self::AccountId>;
     type WithdrawOrigin = pallet_evm::EnsureAddressNever<Self::AccountId>;
     type AddressMapping = pallet_evm::HashedAddressMapping<BlakeTwo256>;
-    type Currency = Balances;
+    type Currency = XBtcLedger;
     type Runner = pallet_evm::runner::stack::Runner<Self>;
     type Event = Event;
-    type PrecompilesType = ();
-    type PrecompilesValue = ();
+    type PrecompilesType = MockErc20Precompiles;
+    type PrecompilesValue = MockErc20PrecompilesValue;
     type ChainId = ();
     type BlockGasLimit = ();
     type OnChargeTransaction = ();
@@ -140,6 +164,159 @@ impl pallet_evm::Config for Test {
     type WeightInfo = ();
 }
 
+/// A minimal erc20 token emulated by a precompile.
+#[derive(Default)]
+pub struct MockErc20 {
+    balances: BTreeMap<H160, u128>,
+    total_supply: u128,
+    /// Burnt from every transfer, to emulate the fee-on-transfer tokens.
+    transfer_fee: u128,
+}
+
+thread_local! {
+    static MOCK_ERC20S: RefCell<BTreeMap<H160, MockErc20>> = RefCell::new(BTreeMap::new());
+}
+
+/// Deploy a mock erc20 token at `token`.
+pub fn deploy_mock_erc20(token: H160, transfer_fee: u128) {
+    MOCK_ERC20S.with(|tokens| {
+        tokens.borrow_mut().insert(
+            token,
+            MockErc20 {
+                transfer_fee,
+                ..Default::default()
+            },
+        )
+    });
+}
+
+/// Mint `amount` of `token` to `to` outside of the bridge.
+pub fn mock_erc20_mint(token: H160, to: H160, amount: u128) {
+    MOCK_ERC20S.with(|tokens| {
+        let mut tokens = tokens.borrow_mut();
+        let erc20 = tokens.get_mut(&token).expect("mock erc20 is deployed");
+        *erc20.balances.entry(to).or_default() += amount;
+        erc20.total_supply += amount;
+    });
+}
+
+pub fn mock_erc20_balance_of(token: H160, who: H160) -> u128 {
+    MOCK_ERC20S.with(|tokens| {
+        tokens.borrow()[&token]
+            .balances
+            .get(&who)
+            .copied()
+            .unwrap_or_default()
+    })
+}
+
+pub fn mock_erc20_total_supply(token: H160) -> u128 {
+    MOCK_ERC20S.with(|tokens| tokens.borrow()[&token].total_supply)
+}
+
+impl MockErc20 {
+    fn transfer(&mut self, from: H160, to: H160, amount: u128) -> Result<(), &'static str> {
+        let from_balance = self.balances.entry(from).or_default();
+        *from_balance = from_balance
+            .checked_sub(amount)
+            .ok_or("insufficient balance")?;
+        let fee = self.transfer_fee.min(amount);
+        *self.balances.entry(to).or_default() += amount - fee;
+        self.total_supply -= fee;
+        Ok(())
+    }
+
+    fn execute(&mut self, caller: H160, input: &[u8]) -> Result<Vec<u8>, &'static str> {
+        let arg = |i: usize| {
+            input
+                .get(4 + 32 * i..4 + 32 * (i + 1))
+                .ok_or("invalid input")
+        };
+        let address = |i: usize| arg(i).map(|word| H160::from_slice(&word[12..]));
+        let amount = |i: usize| arg(i).map(|word| U256::from_big_endian(word).low_u128());
+        let word = |value: u128| {
+            let mut out = [0u8; 32];
+            U256::from(value).to_big_endian(&mut out);
+            out.to_vec()
+        };
+
+        match input.get(..4).ok_or("invalid input")? {
+            // mint_into(address,uint256)
+            [0xef, 0xe5, 0x16, 0x95] => {
+                let (to, amount) = (address(0)?, amount(1)?);
+                *self.balances.entry(to).or_default() += amount;
+                self.total_supply += amount;
+                Ok(Vec::new())
+            }
+            // burn_from(address,uint256)
+            [0x0f, 0x53, 0x6f, 0x84] => {
+                let (from, amount) = (address(0)?, amount(1)?);
+                let balance = self.balances.entry(from).or_default();
+                *balance = balance.checked_sub(amount).ok_or("insufficient balance")?;
+                self.total_supply -= amount;
+                Ok(Vec::new())
+            }
+            // transferFrom(address,address,uint256)
+            [0x23, 0xb8, 0x72, 0xdd] => {
+                self.transfer(address(0)?, address(1)?, amount(2)?)?;
+                Ok(word(1))
+            }
+            // transfer(address,uint256)
+            [0xa9, 0x05, 0x9c, 0xbb] => {
+                self.transfer(caller, address(0)?, amount(1)?)?;
+                Ok(word(1))
+            }
+            // totalSupply()
+            [0x18, 0x16, 0x0d, 0xdd] => Ok(word(self.total_supply)),
+            // balanceOf(address)
+            [0x70, 0xa0, 0x82, 0x31] => {
+                let who = address(0)?;
+                Ok(word(self.balances.get(&who).copied().unwrap_or_default()))
+            }
+            _ => Err("unknown selector"),
+        }
+    }
+}
+
+pub struct MockErc20Precompiles;
+
+impl PrecompileSet for MockErc20Precompiles {
+    fn execute(
+        &self,
+        address: H160,
+        input: &[u8],
+        _target_gas: Option<u64>,
+        context: &Context,
+        _is_static: bool,
+    ) -> Option<PrecompileResult> {
+        MOCK_ERC20S.with(|tokens| {
+            let mut tokens = tokens.borrow_mut();
+            let erc20 = tokens.get_mut(&address)?;
+            Some(match erc20.execute(context.caller, input) {
+                Ok(output) => Ok(PrecompileOutput {
+                    exit_status: ExitSucceed::Returned,
+                    cost: 1_000,
+                    output,
+                    logs: Default::default(),
+                }),
+                Err(err) => Err(PrecompileFailure::Revert {
+                    exit_status: ExitRevert::Reverted,
+                    output: err.as_bytes().to_vec(),
+                    cost: 1_000,
+                }),
+            })
+        })
+    }
+
+    fn is_precompile(&self, address: H160) -> bool {
+        MOCK_ERC20S.with(|tokens| tokens.borrow().contains_key(&address))
+    }
+}
+
+parameter_types! {
+    pub MockErc20PrecompilesValue: MockErc20Precompiles = MockErc20Precompiles;
+}
+
 impl xassets_bridge::Config for Test {
     type Event = Event;
     type EvmCaller = EvmCaller;
@@ -42,6 +42,74 @@ pub fn ethereum_signable_message(what: &[u8], extra: &[u8]) -> Vec<u8> {
     v
 }
 
+/// The EIP-712 domain of the account claim.
+pub const EIP712_DOMAIN_NAME: &[u8] = b"ChainX Assets Bridge";
+pub const EIP712_DOMAIN_VERSION: &[u8] = b"1";
+
+/// Constructs the EIP-712 digest of `Claim(bytes32 substrate,uint256 nonce)` signed by
+/// `eth_signTypedData_v4`, the domain is
+/// `EIP712Domain(string name,string version,uint256 chainId,bytes32 salt)`
+/// with the genesis hash as salt.
+pub fn eip712_claim_digest(
+    who: &[u8; 32],
+    nonce: u64,
+    chain_id: u64,
+    genesis_hash: &[u8; 32],
+) -> [u8; 32] {
+    let mut domain = Vec::with_capacity(32 * 5);
+    domain.extend_from_slice(&keccak_256(
+        b"EIP712Domain(string name,string version,uint256 chainId,bytes32 salt)",
+    ));
+    domain.extend_from_slice(&keccak_256(EIP712_DOMAIN_NAME));
+    domain.extend_from_slice(&keccak_256(EIP712_DOMAIN_VERSION));
+    domain.extend_from_slice(&U256::from(chain_id).encode_be());
+    domain.extend_from_slice(&genesis_hash[..]);
+    let domain_separator = keccak_256(&domain);
+
+    let mut claim = Vec::with_capacity(32 * 3);
+    claim.extend_from_slice(&keccak_256(b"Claim(bytes32 substrate,uint256 nonce)"));
+    claim.extend_from_slice(&who[..]);
+    claim.extend_from_slice(&U256::from(nonce).encode_be());
+    let struct_hash = keccak_256(&claim);
+
+    let mut v = Vec::with_capacity(2 + 32 * 2);
+    v.extend_from_slice(b"\x19\x01");
+    v.extend_from_slice(&domain_separator[..]);
+    v.extend_from_slice(&struct_hash[..]);
+    keccak_256(&v)
+}
+
+/// Attempts to recover the Ethereum address from a signature of the 32 bytes digest.
+pub fn eth_recover_digest(s: &EcdsaSignature, digest: &[u8; 32]) -> Option<H160> {
+    let mut res = H160::default();
+    res.0
+        .copy_from_slice(&keccak_256(&secp256k1_ecdsa_recover(&s.0, digest).ok()?[..])[12..]);
+    Some(res)
+}
+
+/// Constructs the message that the substrate account signs to bind an evm address
+/// from the evm side.
+pub fn evm_binding_message(
+    eth_address: &H160,
+    nonce: u64,
+    chain_id: u64,
+    genesis_hash: &[u8; 32],
+) -> Vec<u8> {
+    (b"evm-bind:", eth_address, nonce, chain_id, genesis_hash).encode()
+}
+
+trait EncodeBe {
+    fn encode_be(&self) -> [u8; 32];
+}
+
+impl EncodeBe for U256 {
+    fn encode_be(&self) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        self.to_big_endian(&mut out);
+        out
+    }
+}
+
 /*
 这段代码提供了几个与以太坊签名和地址恢复相关的函数,这些函数在处理以太坊兼容的签名和验证时非常有用.下面是对这些函数的详细解释:
 
@@ -2,7 +2,7 @@
 
 use crate::mock::*;
 use crate::{to_ascii_hex, EcdsaSignature};
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::Get};
 use sp_core::{H160, U256};
 
 use ethabi::{Function, Param, ParamType, Token};
@@ -502,6 +502,324 @@ fn force_unregister_should_work() {
     })
 }
 
+#[test]
+fn set_rate_limit_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            XAssetsBridge::set_rate_limit(Origin::signed(ALICE.into()), 1, Some((10, 100))),
+            Error::<Test>::AssetIdHasNotMapped
+        );
+
+        assert_ok!(XAssetsBridge::register(
+            Origin::signed(ALICE.into()),
+            1,
+            H160::from_slice(&ERC20_1)
+        ));
+
+        assert_noop!(
+            XAssetsBridge::set_rate_limit(Origin::signed(BOB.into()), 1, Some((10, 100))),
+            Error::<Test>::RequireAdmin
+        );
+
+        assert_ok!(XAssetsBridge::set_rate_limit(
+            Origin::signed(ALICE.into()),
+            1,
+            Some((10, 100))
+        ));
+        expect_event(XAssetsBridgeEvent::SetRateLimit(1, Some((10, 100))));
+        assert_eq!(XAssetsBridge::rate_limits(1), Some((10, 100)));
+
+        assert_ok!(XAssetsBridge::set_rate_limit(
+            Origin::signed(ALICE.into()),
+            1,
+            None
+        ));
+        expect_event(XAssetsBridgeEvent::SetRateLimit(1, None));
+        assert_eq!(XAssetsBridge::rate_limits(1), None);
+    })
+}
+
+#[test]
+fn deposit_should_respect_rate_limit() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(XAssetsBridge::register(
+            Origin::signed(ALICE.into()),
+            1,
+            H160::from_slice(&ERC20_1)
+        ));
+        crate::EvmAccounts::<Test>::insert(AccountId32::from(BOB), H160::from_slice(&EVM_ADDR));
+
+        assert_noop!(
+            XAssetsBridge::deposit(Origin::signed(BOB.into()), 1, 0),
+            Error::<Test>::ZeroBalance
+        );
+        assert_noop!(
+            XAssetsBridge::deposit(Origin::signed(BOB.into()), 0, 1),
+            xpallet_assets::Error::<Test>::DenyNativeAsset
+        );
+
+        assert_ok!(XAssetsBridge::set_rate_limit(
+            Origin::signed(ALICE.into()),
+            1,
+            Some((10, 100))
+        ));
+        assert_noop!(
+            XAssetsBridge::deposit(Origin::signed(BOB.into()), 1, 101),
+            Error::<Test>::ExceedRateLimit
+        );
+        assert_noop!(
+            XAssetsBridge::withdraw(Origin::signed(BOB.into()), 1, 101),
+            Error::<Test>::ExceedRateLimit
+        );
+
+        assert_ok!(XAssetsBridge::pause(Origin::signed(ALICE.into()), Some(1)));
+        assert_noop!(
+            XAssetsBridge::deposit(Origin::signed(BOB.into()), 1, 1),
+            Error::<Test>::InEmergency
+        );
+    })
+}
+
+fn setup_bridged_asset(erc20: H160) {
+    use xpallet_assets_registrar::{AssetInfo, Chain};
+
+    assert_ok!(XAssetsRegistrar::register(
+        Origin::root(),
+        1,
+        AssetInfo::new::<Test>(
+            b"XBTC".to_vec(),
+            b"ChainX Bitcoin".to_vec(),
+            Chain::Bitcoin,
+            8,
+            b"ChainX's cross-chain Bitcoin".to_vec(),
+        )
+        .unwrap(),
+        true,
+        false
+    ));
+    assert_ok!(XAssets::issue(&1, &BOB.into(), 1000, false));
+    assert_ok!(XAssetsBridge::register(
+        Origin::signed(ALICE.into()),
+        1,
+        erc20
+    ));
+    crate::EvmAccounts::<Test>::insert(AccountId32::from(BOB), H160::from_slice(&EVM_ADDR));
+    deploy_mock_erc20(erc20, 0);
+}
+
+fn escrow_locked(asset_id: AssetId) -> u128 {
+    XAssets::asset_balance_of(
+        &XAssetsBridge::escrow_account(),
+        &asset_id,
+        xpallet_assets::AssetType::Locked,
+    )
+}
+
+#[test]
+fn deposit_and_withdraw_should_work() {
+    new_test_ext().execute_with(|| {
+        let erc20 = H160::from_slice(&ERC20_1);
+        let evm_account = H160::from_slice(&EVM_ADDR);
+        setup_bridged_asset(erc20);
+
+        assert_ok!(XAssetsBridge::deposit(Origin::signed(BOB.into()), 1, 40));
+        expect_event(XAssetsBridgeEvent::DepositExecuted(
+            1,
+            BOB.into(),
+            evm_account,
+            40,
+            erc20,
+        ));
+        assert_eq!(XAssets::usable_balance(&BOB.into(), &1), 960);
+        assert_eq!(escrow_locked(1), 40);
+        assert_eq!(mock_erc20_balance_of(erc20, evm_account), 40);
+        assert_eq!(mock_erc20_total_supply(erc20), 40);
+
+        assert_ok!(XAssetsBridge::withdraw(Origin::signed(BOB.into()), 1, 15));
+        expect_event(XAssetsBridgeEvent::WithdrawExecuted(
+            1,
+            BOB.into(),
+            evm_account,
+            15,
+            erc20,
+        ));
+        assert_eq!(XAssets::usable_balance(&BOB.into(), &1), 975);
+        assert_eq!(escrow_locked(1), 25);
+        assert_eq!(mock_erc20_total_supply(erc20), 25);
+        assert!(XAssetsBridge::check_escrow_invariant(1));
+
+        // can not withdraw more than the erc20 balance
+        assert_noop!(
+            XAssetsBridge::withdraw(Origin::signed(BOB.into()), 1, 26),
+            Error::<Test>::ExecutedFailed
+        );
+    })
+}
+
+#[test]
+fn deposits_within_one_period_should_work() {
+    new_test_ext().execute_with(|| {
+        let erc20 = H160::from_slice(&ERC20_1);
+        setup_bridged_asset(erc20);
+        assert_ok!(XAssetsBridge::set_rate_limit(
+            Origin::signed(ALICE.into()),
+            1,
+            Some((10, 100))
+        ));
+
+        assert_ok!(XAssetsBridge::deposit(Origin::signed(BOB.into()), 1, 40));
+        assert_ok!(XAssetsBridge::deposit(Origin::signed(BOB.into()), 1, 40));
+        assert_eq!(XAssetsBridge::rate_usages(1), (0, 80));
+        assert_noop!(
+            XAssetsBridge::deposit(Origin::signed(BOB.into()), 1, 30),
+            Error::<Test>::ExceedRateLimit
+        );
+        // withdrawals share the quota of the period
+        assert_ok!(XAssetsBridge::withdraw(Origin::signed(BOB.into()), 1, 20));
+        assert_eq!(XAssetsBridge::rate_usages(1), (0, 100));
+
+        System::set_block_number(10);
+        assert_ok!(XAssetsBridge::deposit(Origin::signed(BOB.into()), 1, 30));
+        assert_eq!(XAssetsBridge::rate_usages(1), (10, 30));
+
+        assert_eq!(escrow_locked(1), 90);
+        assert_eq!(mock_erc20_total_supply(erc20), 90);
+        assert!(XAssetsBridge::emergencies().is_empty());
+    })
+}
+
+#[test]
+fn escrow_mismatch_should_pause() {
+    new_test_ext().execute_with(|| {
+        let erc20 = H160::from_slice(&ERC20_1);
+        let evm_account = H160::from_slice(&EVM_ADDR);
+        setup_bridged_asset(erc20);
+
+        assert_ok!(XAssetsBridge::deposit(Origin::signed(BOB.into()), 1, 40));
+
+        // erc20 minted outside of the bridge is not backed by the escrow
+        mock_erc20_mint(erc20, evm_account, 5);
+        assert_ok!(XAssetsBridge::deposit(Origin::signed(BOB.into()), 1, 10));
+        expect_event(XAssetsBridgeEvent::EscrowMismatch(1, 50, Some(55)));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == XAssetsBridgeEvent::Paused(1).into()));
+        assert_eq!(XAssetsBridge::emergencies(), vec![1]);
+
+        assert_noop!(
+            XAssetsBridge::deposit(Origin::signed(BOB.into()), 1, 10),
+            Error::<Test>::InEmergency
+        );
+        assert_noop!(
+            XAssetsBridge::withdraw(Origin::signed(BOB.into()), 1, 10),
+            Error::<Test>::InEmergency
+        );
+    })
+}
+
+#[test]
+fn migrate_to_v1_should_seed_escrow() {
+    new_test_ext().execute_with(|| {
+        let erc20 = H160::from_slice(&ERC20_1);
+        let evm_account = H160::from_slice(&EVM_ADDR);
+        setup_bridged_asset(erc20);
+
+        // the supply minted under the burn/mint model
+        mock_erc20_mint(erc20, evm_account, 300);
+        assert!(!XAssetsBridge::check_escrow_invariant(1));
+        crate::Emergencies::<Test>::kill();
+
+        crate::migrations::migrate_to_v1::<Test>();
+        assert_eq!(escrow_locked(1), 300);
+        assert!(XAssetsBridge::check_escrow_invariant(1));
+
+        // the seeded escrow backs the legacy supply
+        assert_ok!(XAssetsBridge::withdraw(Origin::signed(BOB.into()), 1, 300));
+        assert_eq!(XAssets::usable_balance(&BOB.into(), &1), 1300);
+        assert_eq!(escrow_locked(1), 0);
+    })
+}
+
+#[test]
+fn xbtc_audit_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(XAssetsBridge::audit_xbtc(), None);
+
+        assert_noop!(
+            XAssetsBridge::set_xbtc_audit(Origin::signed(BOB.into()), Some(true)),
+            Error::<Test>::RequireAdmin
+        );
+        assert_ok!(XAssetsBridge::set_xbtc_audit(
+            Origin::signed(ALICE.into()),
+            Some(true)
+        ));
+        expect_event(XAssetsBridgeEvent::SetXBtcAudit(Some(true)));
+
+        let report = XAssetsBridge::audit_xbtc().unwrap();
+        assert!(report.is_balanced());
+
+        // a direct deposit is recorded by gateway records
+        assert_ok!(XAssetsBridge::apply_direct_deposit(
+            H160::from_slice(&EVM_ADDR),
+            1,
+            100
+        ));
+        let report = XAssetsBridge::audit_xbtc().unwrap();
+        assert!(report.is_balanced());
+
+        // btc minted without a deposit record breaks the audit
+        let mapping_account = AccountId32::from_str(MAPPING_ACCOUNT).unwrap();
+        let _ = <XBtcLedger as frame_support::traits::Currency<_>>::deposit_creating(
+            &mapping_account,
+            10,
+        );
+        let report = XAssetsBridge::audit_xbtc().unwrap();
+        assert!(!report.is_balanced());
+        assert_eq!(report.expected, 100);
+        assert_eq!(report.actual, 110);
+
+        XAssetsBridge::check_xbtc_audit();
+        expect_event(XAssetsBridgeEvent::XBtcAuditFailed(report));
+        assert_eq!(XAssetsBridge::emergencies(), vec![1]);
+        assert_eq!(XAssetsBridge::last_xbtc_audit_failure(), Some(report));
+
+        // re-enabling the audit does not accept the mismatch
+        assert_ok!(XAssetsBridge::set_xbtc_audit(
+            Origin::signed(ALICE.into()),
+            Some(false)
+        ));
+        assert_eq!(XAssetsBridge::audit_xbtc(), Some(report));
+
+        assert_ok!(XAssetsBridge::set_xbtc_audit(
+            Origin::signed(ALICE.into()),
+            None
+        ));
+        assert_eq!(XAssetsBridge::audit_xbtc(), None);
+        assert_eq!(XAssetsBridge::last_xbtc_audit_failure(), None);
+    })
+}
+
+#[test]
+fn migrate_to_v2_should_seed_xbtc_records() {
+    new_test_ext().execute_with(|| {
+        // btc held before the deposits were recorded
+        let mapping_account = AccountId32::from_str(MAPPING_ACCOUNT).unwrap();
+        let _ = <XBtcLedger as frame_support::traits::Currency<_>>::deposit_creating(
+            &mapping_account,
+            50,
+        );
+        assert_ok!(XAssetsBridge::set_xbtc_audit(
+            Origin::signed(ALICE.into()),
+            Some(true)
+        ));
+        assert!(!XAssetsBridge::audit_xbtc().unwrap().is_balanced());
+
+        crate::migrations::migrate_to_v2::<Test>();
+        assert_eq!(XGatewayRecords::total_deposited(1), 50);
+        assert!(XAssetsBridge::audit_xbtc().unwrap().is_balanced());
+    })
+}
+
 #[test]
 fn bridge_accounts_should_equal() {
     // 5TPu4DCQRSbNS9ESUcNGUn9HcF9AzrHiDP395bDxM9ZAqSD8
@@ -514,6 +832,315 @@ fn bridge_accounts_should_equal() {
     assert_eq!(bridge_admin1, bridge_admin2);
 }
 
+#[test]
+fn transfer_abi_encode() {
+    let from = H160::from_slice(&EVM_ADDR);
+    let to = H160::from_slice(&ERC20_1);
+
+    let encoded = crate::transfer_from_encode(from, to, 100_000_000u128);
+    let expected = hex!("23b872dd000000000000000000000000f24ff3a9cf04c71dbc94d0b566f7a27b94566cac00000000000000000000000001010101010101010101010101010101010101010000000000000000000000000000000000000000000000000000000005f5e100").to_vec();
+    assert_eq!(encoded, expected);
+
+    let encoded = crate::transfer_encode(from, 100_000_000u128);
+    let expected = hex!("a9059cbb000000000000000000000000f24ff3a9cf04c71dbc94d0b566f7a27b94566cac0000000000000000000000000000000000000000000000000000000005f5e100").to_vec();
+    assert_eq!(encoded, expected);
+}
+
+#[test]
+fn register_native_erc20_should_work() {
+    use xpallet_assets_registrar::{AssetInfo, Chain};
+
+    new_test_ext().execute_with(|| {
+        let erc20 = H160::from_slice(&ERC20_2);
+        let asset = |chain| {
+            AssetInfo::new::<Test>(
+                b"USDT".to_vec(),
+                b"Tether USD".to_vec(),
+                chain,
+                6,
+                b"evm native USDT".to_vec(),
+            )
+            .unwrap()
+        };
+
+        // only root can register the native erc20 assets
+        assert_noop!(
+            XAssetsBridge::register_native_erc20(
+                Origin::signed(ALICE.into()),
+                100,
+                erc20,
+                asset(Chain::Ethereum),
+                true
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            XAssetsBridge::register_native_erc20(
+                Origin::root(),
+                100,
+                erc20,
+                asset(Chain::Bitcoin),
+                true
+            ),
+            Error::<Test>::InvalidAssetChain
+        );
+
+        assert_ok!(XAssetsBridge::register_native_erc20(
+            Origin::root(),
+            100,
+            erc20,
+            asset(Chain::Ethereum),
+            true
+        ));
+        expect_event(XAssetsBridgeEvent::RegisterNativeErc20(100, erc20));
+        assert_eq!(XAssetsBridge::native_erc20s(100), Some(erc20));
+        assert_eq!(XAssetsBridge::native_asset_ids(erc20), Some(100));
+        assert!(XAssetsRegistrar::is_valid(&100));
+        assert_eq!(XAssetsRegistrar::chain_of(&100), Ok(Chain::Ethereum));
+
+        assert_noop!(
+            XAssetsBridge::register(Origin::signed(ALICE.into()), 101, erc20),
+            Error::<Test>::ContractAddressHasMapped
+        );
+        assert_noop!(
+            XAssetsBridge::register_native_erc20(
+                Origin::root(),
+                101,
+                erc20,
+                asset(Chain::Ethereum),
+                true
+            ),
+            Error::<Test>::ContractAddressHasMapped
+        );
+
+        // the asset is not mapped to an evm address
+        assert_noop!(
+            XAssetsBridge::lock_erc20(Origin::signed(BOB.into()), 100, 10),
+            Error::<Test>::EthAddressHasNotMapped
+        );
+    })
+}
+
+fn setup_native_erc20(erc20: H160, transfer_fee: u128) {
+    use xpallet_assets_registrar::{AssetInfo, Chain};
+
+    assert_ok!(XAssetsBridge::register_native_erc20(
+        Origin::root(),
+        100,
+        erc20,
+        AssetInfo::new::<Test>(
+            b"USDT".to_vec(),
+            b"Tether USD".to_vec(),
+            Chain::Ethereum,
+            6,
+            b"evm native USDT".to_vec(),
+        )
+        .unwrap(),
+        false
+    ));
+    crate::EvmAccounts::<Test>::insert(AccountId32::from(BOB), H160::from_slice(&EVM_ADDR));
+    deploy_mock_erc20(erc20, transfer_fee);
+    mock_erc20_mint(erc20, H160::from_slice(&EVM_ADDR), 100);
+}
+
+#[test]
+fn lock_and_unlock_erc20_should_work() {
+    new_test_ext().execute_with(|| {
+        let erc20 = H160::from_slice(&ERC20_2);
+        let evm_account = H160::from_slice(&EVM_ADDR);
+        let bridge = <Test as Config>::EvmCaller::get();
+        setup_native_erc20(erc20, 0);
+
+        assert_ok!(XAssetsBridge::lock_erc20(
+            Origin::signed(BOB.into()),
+            100,
+            60
+        ));
+        expect_event(XAssetsBridgeEvent::Erc20Locked(
+            100,
+            BOB.into(),
+            evm_account,
+            60,
+            erc20,
+        ));
+        assert_eq!(XAssets::usable_balance(&BOB.into(), &100), 60);
+        assert_eq!(mock_erc20_balance_of(erc20, evm_account), 40);
+        assert_eq!(mock_erc20_balance_of(erc20, bridge), 60);
+
+        assert_ok!(XAssetsBridge::unlock_erc20(
+            Origin::signed(BOB.into()),
+            100,
+            25
+        ));
+        expect_event(XAssetsBridgeEvent::Erc20Unlocked(
+            100,
+            BOB.into(),
+            evm_account,
+            25,
+            erc20,
+        ));
+        assert_eq!(XAssets::usable_balance(&BOB.into(), &100), 35);
+        assert_eq!(mock_erc20_balance_of(erc20, evm_account), 65);
+        assert_eq!(mock_erc20_balance_of(erc20, bridge), 35);
+
+        assert_noop!(
+            XAssetsBridge::unlock_erc20(Origin::signed(BOB.into()), 100, 36),
+            xpallet_assets::Error::<Test>::InsufficientBalance
+        );
+        // can not lock more than the erc20 balance
+        assert_noop!(
+            XAssetsBridge::lock_erc20(Origin::signed(BOB.into()), 100, 66),
+            Error::<Test>::ExecutedFailed
+        );
+    })
+}
+
+#[test]
+fn lock_fee_on_transfer_erc20_should_mint_received_amount() {
+    new_test_ext().execute_with(|| {
+        let erc20 = H160::from_slice(&ERC20_2);
+        let evm_account = H160::from_slice(&EVM_ADDR);
+        let bridge = <Test as Config>::EvmCaller::get();
+        setup_native_erc20(erc20, 2);
+
+        assert_ok!(XAssetsBridge::lock_erc20(
+            Origin::signed(BOB.into()),
+            100,
+            60
+        ));
+        expect_event(XAssetsBridgeEvent::Erc20Locked(
+            100,
+            BOB.into(),
+            evm_account,
+            58,
+            erc20,
+        ));
+        assert_eq!(XAssets::usable_balance(&BOB.into(), &100), 58);
+        assert_eq!(mock_erc20_balance_of(erc20, bridge), 58);
+
+        // the minted assets are fully backed by the locked erc20
+        assert_ok!(XAssetsBridge::unlock_erc20(
+            Origin::signed(BOB.into()),
+            100,
+            58
+        ));
+        assert_eq!(XAssets::usable_balance(&BOB.into(), &100), 0);
+        assert_eq!(mock_erc20_balance_of(erc20, bridge), 0);
+        assert_eq!(mock_erc20_balance_of(erc20, evm_account), 40 + 56);
+    })
+}
+
+#[test]
+fn force_unregister_native_erc20_should_work() {
+    new_test_ext().execute_with(|| {
+        let erc20 = H160::from_slice(&ERC20_2);
+        setup_native_erc20(erc20, 0);
+
+        assert_ok!(XAssetsBridge::force_unregister(
+            Origin::signed(ALICE.into()),
+            100
+        ));
+        expect_event(XAssetsBridgeEvent::ForceUnRegister(100, erc20));
+        assert_eq!(XAssetsBridge::native_erc20s(100), None);
+        assert_eq!(XAssetsBridge::native_asset_ids(erc20), None);
+
+        assert_noop!(
+            XAssetsBridge::lock_erc20(Origin::signed(BOB.into()), 100, 10),
+            Error::<Test>::ContractAddressHasNotMapped
+        );
+        // the erc20 can be registered again
+        assert_ok!(XAssetsBridge::register(
+            Origin::signed(ALICE.into()),
+            101,
+            erc20
+        ));
+    })
+}
+
+#[test]
+fn claim_account_with_eip712_should_work() {
+    use sp_core::{ecdsa, Pair};
+
+    new_test_ext().execute_with(|| {
+        let who: AccountId32 = ALICE.into();
+        let pair = ecdsa::Pair::from_seed(&[7u8; 32]);
+        let sign = |who: &AccountId32, address: &H160| {
+            let digest = XAssetsBridge::claim_digest(who, address);
+            pair.sign_prehashed(&digest)
+        };
+
+        // the signer address is recovered from the initial digest
+        let digest = XAssetsBridge::claim_digest(&who, &H160::zero());
+        let sig = pair.sign_prehashed(&digest);
+        let pubkey = sp_io::crypto::secp256k1_ecdsa_recover(&sig.0, &digest).unwrap();
+        let address = H160::from_slice(&sp_io::hashing::keccak_256(&pubkey)[12..]);
+
+        // signed for another account
+        assert_noop!(
+            XAssetsBridge::claim_account(Origin::signed(BOB.into()), address, sign(&who, &address)),
+            crate::Error::<Test>::InvalidSignature
+        );
+
+        let signature = sign(&who, &address);
+        assert_ok!(XAssetsBridge::claim_account(
+            Origin::signed(who.clone()),
+            address,
+            signature.clone()
+        ));
+        assert_eq!(XAssetsBridge::sub_accounts(address), Some(who.clone()));
+        assert_eq!(XAssetsBridge::evm_accounts(&who), Some(address));
+        assert_eq!(XAssetsBridge::claim_nonces(address), 1);
+        assert_eq!(Balances::reserved_balance(&who), 2);
+
+        // the signature can not be replayed after dissolving
+        assert_ok!(XAssetsBridge::dissolve(Origin::signed(who.clone())));
+        assert_noop!(
+            XAssetsBridge::claim_account(Origin::signed(who.clone()), address, signature),
+            crate::Error::<Test>::InvalidSignature
+        );
+        assert_ok!(XAssetsBridge::claim_account(
+            Origin::signed(who.clone()),
+            address,
+            sign(&who, &address)
+        ));
+        assert_eq!(XAssetsBridge::claim_nonces(address), 2);
+    })
+}
+
+#[test]
+fn claim_account_from_evm_should_work() {
+    use sp_core::{sr25519, Pair};
+
+    new_test_ext().execute_with(|| {
+        let pair = sr25519::Pair::from_seed(&[9u8; 32]);
+        let who: AccountId32 = pair.public().into();
+        let address = H160::from_slice(&EVM_ADDR);
+        assert_ok!(Balances::transfer(
+            Origin::signed(ALICE.into()),
+            who.clone(),
+            10
+        ));
+
+        let signature = pair.sign(&XAssetsBridge::evm_binding_payload(&address)).0;
+        assert_noop!(
+            XAssetsBridge::claim_account_from_evm(H160::zero(), who.clone(), &signature),
+            crate::Error::<Test>::InvalidSignature
+        );
+        assert_ok!(XAssetsBridge::claim_account_from_evm(
+            address,
+            who.clone(),
+            &signature
+        ));
+        assert_eq!(XAssetsBridge::sub_accounts(address), Some(who.clone()));
+        assert_eq!(XAssetsBridge::evm_accounts(&who), Some(address));
+
+        assert_noop!(
+            XAssetsBridge::claim_account_from_evm(address, who, &signature),
+            crate::Error::<Test>::InvalidSignature
+        );
+    })
+}
+
 /*
 这段代码是一个Rust语言编写的测试套件,用于测试一个名为`XAssetsBridge`的区块链智能合约或类似系统的功能.
 代码中包含了多个测试用例,每个测试用例都旨在验证系统中的特定功能是否按预期工作.以下是对代码中关键部分的解释:
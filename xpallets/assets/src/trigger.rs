@@ -10,6 +10,68 @@ use crate::{BalanceOf, Config, Event, Pallet};
 
 impl<AccountId, Balance> OnAssetChanged<AccountId, Balance> for () {}
 
+/// Triggers the hooks of both `A` and `B`, in order.
+impl<AccountId, Balance, A, B> OnAssetChanged<AccountId, Balance> for (A, B)
+where
+    Balance: Copy,
+    A: OnAssetChanged<AccountId, Balance>,
+    B: OnAssetChanged<AccountId, Balance>,
+{
+    fn on_issue_pre(id: &AssetId, who: &AccountId) {
+        A::on_issue_pre(id, who);
+        B::on_issue_pre(id, who);
+    }
+
+    fn on_issue_post(id: &AssetId, who: &AccountId, value: Balance) -> DispatchResult {
+        A::on_issue_post(id, who, value)?;
+        B::on_issue_post(id, who, value)
+    }
+
+    fn on_move_pre(
+        id: &AssetId,
+        from: &AccountId,
+        from_type: AssetType,
+        to: &AccountId,
+        to_type: AssetType,
+        value: Balance,
+    ) {
+        A::on_move_pre(id, from, from_type, to, to_type, value);
+        B::on_move_pre(id, from, from_type, to, to_type, value);
+    }
+
+    fn on_move_post(
+        id: &AssetId,
+        from: &AccountId,
+        from_type: AssetType,
+        to: &AccountId,
+        to_type: AssetType,
+        value: Balance,
+    ) -> Result<(), AssetErr> {
+        A::on_move_post(id, from, from_type, to, to_type, value)?;
+        B::on_move_post(id, from, from_type, to, to_type, value)
+    }
+
+    fn on_destroy_pre(id: &AssetId, who: &AccountId) {
+        A::on_destroy_pre(id, who);
+        B::on_destroy_pre(id, who);
+    }
+
+    fn on_destroy_post(id: &AssetId, who: &AccountId, value: Balance) -> DispatchResult {
+        A::on_destroy_post(id, who, value)?;
+        B::on_destroy_post(id, who, value)
+    }
+
+    fn on_set_balance(
+        id: &AssetId,
+        who: &AccountId,
+        type_: AssetType,
+        value: Balance,
+    ) -> DispatchResult {
+        A::on_set_balance(id, who, type_, value)?;
+        B::on_set_balance(id, who, type_, value)
+    }
+}
+
 pub struct AssetChangedTrigger<T: Config>(sp_std::marker::PhantomData<T>);
 
 impl<T: Config> AssetChangedTrigger<T> {
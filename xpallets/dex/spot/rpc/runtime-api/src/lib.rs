@@ -10,7 +10,8 @@ use sp_std::prelude::*;
 use codec::Codec;
 
 pub use xpallet_dex_spot::{
-    Depth, FullPairInfo, Handicap, OrderProperty, RpcOrder, TradingPairId, TradingPairInfo,
+    Candle, Depth, FullPairInfo, Handicap, OrderProperty, RpcOrder, TradeRecord, TradingPairId,
+    TradingPairInfo,
 };
 
 sp_api::decl_runtime_apis! {
@@ -30,5 +31,14 @@ sp_api::decl_runtime_apis! {
 
         /// Get the depth of a trading pair.
         fn depth(pair_id: TradingPairId, depth_size: u32) -> Option<Depth<Price, Balance>>;
+
+        /// Get the depth of a trading pair aggregated into buckets of `bucket_ticks` ticks.
+        fn aggregated_depth(pair_id: TradingPairId, bucket_ticks: u32, max_levels: u32) -> Option<Depth<Price, Balance>>;
+
+        /// Get the most recent trades of a trading pair.
+        fn recent_trades(pair_id: TradingPairId, count: u32) -> Vec<TradeRecord<Balance, Price, BlockNumber>>;
+
+        /// Get the OHLCV candles of a trading pair given the interval in blocks.
+        fn candles(pair_id: TradingPairId, interval: BlockNumber, count: u32) -> Vec<Candle<Price, Balance, BlockNumber>>;
     }
 }
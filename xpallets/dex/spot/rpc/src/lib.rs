@@ -19,8 +19,8 @@ use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use xp_rpc::{runtime_error_into_rpc_err, Result, RpcBalance, RpcPrice};
 
 use xpallet_dex_spot_rpc_runtime_api::{
-    Depth, FullPairInfo, Handicap, OrderProperty, RpcOrder, TradingPairId, TradingPairInfo,
-    XSpotApi as XSpotRuntimeApi,
+    Candle, Depth, FullPairInfo, Handicap, OrderProperty, RpcOrder, TradeRecord, TradingPairId,
+    TradingPairInfo, XSpotApi as XSpotRuntimeApi,
 };
 
 /// XSpot RPC methods.
@@ -67,6 +67,37 @@ where
         depth_size: u32,
         at: Option<BlockHash>,
     ) -> Result<Option<Depth<RpcPrice<Price>, RpcBalance<Balance>>>>;
+
+    /// Get the depth of a trading pair with the price levels aggregated into
+    /// buckets of `bucket_ticks` ticks, at most `max_levels` buckets for each side.
+    #[rpc(name = "xspot_getAggregatedDepth")]
+    fn aggregated_depth(
+        &self,
+        pair_id: TradingPairId,
+        bucket_ticks: u32,
+        max_levels: u32,
+        at: Option<BlockHash>,
+    ) -> Result<Option<Depth<RpcPrice<Price>, RpcBalance<Balance>>>>;
+
+    /// Get the most recent trades of a trading pair, the latest one comes first.
+    #[rpc(name = "xspot_getRecentTrades")]
+    fn recent_trades(
+        &self,
+        pair_id: TradingPairId,
+        count: u32,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<TradeRecord<RpcBalance<Balance>, RpcPrice<Price>, BlockNumber>>>;
+
+    /// Get the OHLCV candles of a trading pair given the interval in blocks,
+    /// the oldest one comes first.
+    #[rpc(name = "xspot_getCandles")]
+    fn candles(
+        &self,
+        pair_id: TradingPairId,
+        interval: BlockNumber,
+        count: u32,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<Candle<RpcPrice<Price>, RpcBalance<Balance>, BlockNumber>>>;
 }
 
 /// A struct that implements the [`XSpotApi`].
@@ -119,6 +150,7 @@ where
                             },
                             max_valid_bid: trading_pairs.max_valid_bid.into(),
                             min_valid_ask: trading_pairs.min_valid_ask.into(),
+                            status: trading_pairs.status,
                         },
                     )
                     .collect::<Vec<_>>()
@@ -206,6 +238,83 @@ where
             Err(err) => Err(runtime_error_into_rpc_err(err)),
         }
     }
+
+    fn aggregated_depth(
+        &self,
+        pair_id: TradingPairId,
+        bucket_ticks: u32,
+        max_levels: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<Depth<RpcPrice<Price>, RpcBalance<Balance>>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.aggregated_depth(&at, pair_id, bucket_ticks, max_levels)
+            .map(|depth| {
+                depth.map(|depth| Depth {
+                    asks: depth
+                        .asks
+                        .into_iter()
+                        .map(|(price, quantity)| (price.into(), quantity.into()))
+                        .collect(),
+                    bids: depth
+                        .bids
+                        .into_iter()
+                        .map(|(price, quantity)| (price.into(), quantity.into()))
+                        .collect(),
+                })
+            })
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn recent_trades(
+        &self,
+        pair_id: TradingPairId,
+        count: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<TradeRecord<RpcBalance<Balance>, RpcPrice<Price>, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.recent_trades(&at, pair_id, count)
+            .map(|trades| {
+                trades
+                    .into_iter()
+                    .map(|trade| TradeRecord {
+                        trading_history_idx: trade.trading_history_idx,
+                        price: trade.price.into(),
+                        turnover: trade.turnover.into(),
+                        taker_side: trade.taker_side,
+                        executed_at: trade.executed_at,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn candles(
+        &self,
+        pair_id: TradingPairId,
+        interval: BlockNumber,
+        count: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<Candle<RpcPrice<Price>, RpcBalance<Balance>, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.candles(&at, pair_id, interval, count)
+            .map(|candles| {
+                candles
+                    .into_iter()
+                    .map(|candle| Candle {
+                        start: candle.start,
+                        open: candle.open.into(),
+                        high: candle.high.into(),
+                        low: candle.low.into(),
+                        close: candle.close.into(),
+                        volume: candle.volume.into(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .map_err(runtime_error_into_rpc_err)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -0,0 +1,495 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! This module handles the constant-product liquidity pools of trading pairs.
+//!
+//! The pool of a trading pair holds the base and quote currency in the account
+//! derived from `PalletId`, the liquidity providers get the share asset whose ID
+//! is derived from the trading pair ID. The invariant `base_reserve * quote_reserve`
+//! never decreases by swapping, the swap fee is left in the pool.
+//!
+//! Besides the explicit `swap` call, a taker order is filled by the pool entirely
+//! if the pool beats both the order price and the best price on the order book.
+
+use super::*;
+use sp_runtime::traits::{AccountIdConversion, IntegerSquareRoot};
+use sp_runtime::PerThing;
+use xpallet_assets::{AssetInfo, AssetType};
+
+/// The asset ID of pool share is `POOL_SHARE_ASSET_ID_OFFSET + pair_id`.
+pub const POOL_SHARE_ASSET_ID_OFFSET: AssetId = 0x1000_0000;
+
+/// The shares locked in the pool forever by the first liquidity provider,
+/// so that the pool can never be drained to an empty pool at an arbitrary price.
+pub const MINIMUM_LIQUIDITY: u128 = 1_000;
+
+impl<T: Config> Pallet<T> {
+    /// Returns the asset ID of the liquidity pool share of a trading pair.
+    pub fn pool_share_asset_id(pair_id: TradingPairId) -> AssetId {
+        POOL_SHARE_ASSET_ID_OFFSET + pair_id
+    }
+
+    /// Returns the account holding the reserves of the liquidity pool.
+    pub fn pool_account(pair_id: TradingPairId) -> T::AccountId {
+        T::PalletId::get().into_sub_account(pair_id)
+    }
+
+    fn pool(pair_id: TradingPairId) -> Result<PoolInfo<T>, Error<T>> {
+        Self::pool_of(pair_id).ok_or(Error::<T>::PoolNotFound)
+    }
+
+    /// Returns the amount received by swapping `amount_in` into the pool.
+    pub(crate) fn pool_amount_out(
+        amount_in: BalanceOf<T>,
+        reserve_in: BalanceOf<T>,
+        reserve_out: BalanceOf<T>,
+        fee: Permill,
+    ) -> Option<BalanceOf<T>> {
+        let amount_in = amount_in.saturating_sub(fee.mul_ceil(amount_in));
+        let amount_in = amount_in.saturated_into::<u128>();
+        let reserve_in = reserve_in.saturated_into::<u128>();
+        let reserve_out = reserve_out.saturated_into::<u128>();
+
+        let numerator = amount_in.checked_mul(reserve_out)?;
+        let denominator = reserve_in.checked_add(amount_in)?;
+        if denominator.is_zero() {
+            return None;
+        }
+        Some((numerator / denominator).saturated_into())
+    }
+
+    /// Returns the amount required to swap `amount_out` out of the pool.
+    pub(crate) fn pool_amount_in(
+        amount_out: BalanceOf<T>,
+        reserve_in: BalanceOf<T>,
+        reserve_out: BalanceOf<T>,
+        fee: Permill,
+    ) -> Option<BalanceOf<T>> {
+        let amount_out = amount_out.saturated_into::<u128>();
+        let reserve_in = reserve_in.saturated_into::<u128>();
+        let reserve_out = reserve_out.saturated_into::<u128>();
+        if amount_out >= reserve_out {
+            return None;
+        }
+
+        let numerator = reserve_in.checked_mul(amount_out)?;
+        let amount_in = numerator / (reserve_out - amount_out) + 1;
+
+        // Gross up the fee, rounding up.
+        let one = u128::from(Permill::ACCURACY);
+        let rest = one - u128::from(fee.deconstruct());
+        let amount_in = amount_in.checked_mul(one)?.checked_add(rest - 1)? / rest;
+        Some(amount_in.saturated_into())
+    }
+
+    pub(crate) fn apply_create_pool(
+        pair: &TradingPairProfile,
+        share_asset: AssetInfo,
+        fee: Permill,
+    ) -> DispatchResult {
+        ensure!(
+            !PoolOf::<T>::contains_key(pair.id),
+            Error::<T>::PoolAlreadyExists
+        );
+        ensure!(fee <= MAX_TRADING_FEE, Error::<T>::InvalidTradingFee);
+
+        let share_asset_id = Self::pool_share_asset_id(pair.id);
+        xpallet_assets_registrar::Pallet::<T>::register(
+            frame_system::RawOrigin::Root.into(),
+            share_asset_id,
+            share_asset,
+            true,
+            false,
+        )?;
+
+        let pool = Pool {
+            share_asset_id,
+            base_reserve: Zero::zero(),
+            quote_reserve: Zero::zero(),
+            total_shares: Zero::zero(),
+            fee,
+        };
+        PoolOf::<T>::insert(pair.id, &pool);
+        Self::deposit_event(Event::<T>::PoolCreated(pair.id, pool));
+        Ok(())
+    }
+
+    pub(crate) fn apply_add_liquidity(
+        who: &T::AccountId,
+        pair_id: TradingPairId,
+        base_amount: BalanceOf<T>,
+        max_quote_amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        ensure!(
+            !base_amount.is_zero() && !max_quote_amount.is_zero(),
+            Error::<T>::ZeroAmount
+        );
+
+        let pair = Self::trading_pair(pair_id)?;
+        ensure!(pair.tradable, Error::<T>::TradingPairUntradable);
+        Self::ensure_trading_pair_active(pair_id)?;
+        let mut pool = Self::pool(pair_id)?;
+
+        let (quote_amount, shares, locked_shares) = if pool.total_shares.is_zero() {
+            // The first provider sets the initial price of pool.
+            let shares = base_amount
+                .saturated_into::<u128>()
+                .saturating_mul(max_quote_amount.saturated_into::<u128>())
+                .integer_sqrt();
+            ensure!(
+                shares > MINIMUM_LIQUIDITY,
+                Error::<T>::InsufficientLiquidity
+            );
+            (
+                max_quote_amount,
+                (shares - MINIMUM_LIQUIDITY).saturated_into(),
+                MINIMUM_LIQUIDITY.saturated_into(),
+            )
+        } else {
+            let base_reserve = pool.base_reserve.saturated_into::<u128>();
+            let quote_amount = (base_amount.saturated_into::<u128>())
+                .saturating_mul(pool.quote_reserve.saturated_into::<u128>())
+                .saturating_add(base_reserve - 1)
+                / base_reserve;
+            let shares = (base_amount.saturated_into::<u128>())
+                .saturating_mul(pool.total_shares.saturated_into::<u128>())
+                / base_reserve;
+            (
+                quote_amount.saturated_into(),
+                shares.saturated_into(),
+                Zero::zero(),
+            )
+        };
+        ensure!(
+            quote_amount <= max_quote_amount,
+            Error::<T>::SlippageExceeded
+        );
+        ensure!(!shares.is_zero(), Error::<T>::ZeroAmount);
+
+        let pool_account = Self::pool_account(pair_id);
+        Self::transfer_free_asset(pair.base(), who, &pool_account, base_amount)?;
+        Self::transfer_free_asset(pair.quote(), who, &pool_account, quote_amount)?;
+        xpallet_assets::Pallet::<T>::issue(&pool.share_asset_id, who, shares, false)?;
+
+        pool.base_reserve = pool.base_reserve.saturating_add(base_amount);
+        pool.quote_reserve = pool.quote_reserve.saturating_add(quote_amount);
+        pool.total_shares = pool
+            .total_shares
+            .saturating_add(shares)
+            .saturating_add(locked_shares);
+        PoolOf::<T>::insert(pair_id, pool);
+
+        Self::deposit_event(Event::<T>::LiquidityAdded(
+            who.clone(),
+            pair_id,
+            base_amount,
+            quote_amount,
+            shares,
+        ));
+        Ok(())
+    }
+
+    pub(crate) fn apply_remove_liquidity(
+        who: &T::AccountId,
+        pair_id: TradingPairId,
+        shares: BalanceOf<T>,
+        min_base_amount: BalanceOf<T>,
+        min_quote_amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        ensure!(!shares.is_zero(), Error::<T>::ZeroAmount);
+
+        let pair = Self::trading_pair(pair_id)?;
+        let mut pool = Self::pool(pair_id)?;
+        ensure!(
+            shares <= pool.total_shares,
+            Error::<T>::InsufficientLiquidity
+        );
+
+        let total_shares = pool.total_shares.saturated_into::<u128>();
+        let share_of = |reserve: BalanceOf<T>| -> BalanceOf<T> {
+            (reserve.saturated_into::<u128>())
+                .saturating_mul(shares.saturated_into::<u128>())
+                .checked_div(total_shares)
+                .unwrap_or_default()
+                .saturated_into()
+        };
+        let base_amount = share_of(pool.base_reserve);
+        let quote_amount = share_of(pool.quote_reserve);
+        ensure!(
+            base_amount >= min_base_amount && quote_amount >= min_quote_amount,
+            Error::<T>::SlippageExceeded
+        );
+
+        xpallet_assets::Pallet::<T>::destroy_usable(&pool.share_asset_id, who, shares)?;
+        let pool_account = Self::pool_account(pair_id);
+        Self::transfer_free_asset(pair.base(), &pool_account, who, base_amount)?;
+        Self::transfer_free_asset(pair.quote(), &pool_account, who, quote_amount)?;
+
+        pool.base_reserve = pool.base_reserve.saturating_sub(base_amount);
+        pool.quote_reserve = pool.quote_reserve.saturating_sub(quote_amount);
+        pool.total_shares = pool.total_shares.saturating_sub(shares);
+        PoolOf::<T>::insert(pair_id, pool);
+
+        Self::deposit_event(Event::<T>::LiquidityRemoved(
+            who.clone(),
+            pair_id,
+            base_amount,
+            quote_amount,
+            shares,
+        ));
+        Ok(())
+    }
+
+    /// Swaps `amount_in` of the quote currency for the base currency if `side` is `Buy`,
+    /// or `amount_in` of the base currency for the quote currency if `side` is `Sell`.
+    pub(crate) fn apply_swap(
+        who: &T::AccountId,
+        pair_id: TradingPairId,
+        side: Side,
+        amount_in: BalanceOf<T>,
+        min_amount_out: BalanceOf<T>,
+    ) -> DispatchResult {
+        ensure!(!amount_in.is_zero(), Error::<T>::ZeroAmount);
+
+        let pair = Self::trading_pair(pair_id)?;
+        ensure!(pair.tradable, Error::<T>::TradingPairUntradable);
+        Self::ensure_trading_pair_active(pair_id)?;
+        let pool = Self::pool(pair_id)?;
+
+        let (base_amount, quote_amount) = match side {
+            Side::Buy => {
+                let base_amount = Self::pool_amount_out(
+                    amount_in,
+                    pool.quote_reserve,
+                    pool.base_reserve,
+                    pool.fee,
+                )
+                .ok_or(Error::<T>::InsufficientLiquidity)?;
+                (base_amount, amount_in)
+            }
+            Side::Sell => {
+                let quote_amount = Self::pool_amount_out(
+                    amount_in,
+                    pool.base_reserve,
+                    pool.quote_reserve,
+                    pool.fee,
+                )
+                .ok_or(Error::<T>::InsufficientLiquidity)?;
+                (amount_in, quote_amount)
+            }
+        };
+        let amount_out = match side {
+            Side::Buy => base_amount,
+            Side::Sell => quote_amount,
+        };
+        ensure!(!amount_out.is_zero(), Error::<T>::InsufficientLiquidity);
+        ensure!(amount_out >= min_amount_out, Error::<T>::SlippageExceeded);
+
+        Self::execute_pool_swap(who, &pair, pool, side, base_amount, quote_amount)
+    }
+
+    /// Fills the order against the pool entirely if the pool gives a better price
+    /// than both the limit price of order and the best price on the order book.
+    ///
+    /// Returns true if the order has been filled by the pool.
+    pub(crate) fn try_fill_by_pool(
+        who: &T::AccountId,
+        pair: &TradingPairProfile,
+        side: Side,
+        amount: BalanceOf<T>,
+        limit_price: T::Price,
+    ) -> Result<bool, DispatchError> {
+        let pool = match Self::pool_of(pair.id) {
+            Some(pool) if !pool.total_shares.is_zero() => pool,
+            _ => return Ok(false),
+        };
+        let handicap = Self::handicap_of(pair.id);
+        let quote_at = |price: T::Price| Self::convert_base_to_quote(amount, price, pair).ok();
+
+        let quote_amount = match side {
+            Side::Buy => {
+                let quote_in = match Self::pool_amount_in(
+                    amount,
+                    pool.quote_reserve,
+                    pool.base_reserve,
+                    pool.fee,
+                ) {
+                    Some(quote_in) => quote_in,
+                    None => return Ok(false),
+                };
+                let within_limit = quote_at(limit_price).map_or(false, |q| quote_in <= q);
+                let beats_book = handicap.lowest_ask.is_zero()
+                    || quote_at(handicap.lowest_ask).map_or(false, |q| quote_in < q);
+                if !within_limit || !beats_book {
+                    return Ok(false);
+                }
+                quote_in
+            }
+            Side::Sell => {
+                let quote_out = match Self::pool_amount_out(
+                    amount,
+                    pool.base_reserve,
+                    pool.quote_reserve,
+                    pool.fee,
+                ) {
+                    Some(quote_out) if !quote_out.is_zero() => quote_out,
+                    _ => return Ok(false),
+                };
+                let within_limit = quote_at(limit_price).map_or(false, |q| quote_out >= q);
+                let beats_book = handicap.highest_bid.is_zero()
+                    || quote_at(handicap.highest_bid).map_or(true, |q| quote_out > q);
+                if !within_limit || !beats_book {
+                    return Ok(false);
+                }
+                quote_out
+            }
+        };
+
+        Self::execute_pool_swap(who, pair, pool, side, amount, quote_amount)?;
+        Ok(true)
+    }
+
+    /// Exchanges the assets between the trader and the pool, then updates the pool
+    /// reserves as well as the trading history and the latest price.
+    fn execute_pool_swap(
+        who: &T::AccountId,
+        pair: &TradingPairProfile,
+        mut pool: PoolInfo<T>,
+        side: Side,
+        base_amount: BalanceOf<T>,
+        quote_amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        let pool_account = Self::pool_account(pair.id);
+        match side {
+            Side::Buy => {
+                Self::transfer_free_asset(pair.quote(), who, &pool_account, quote_amount)?;
+                Self::transfer_free_asset(pair.base(), &pool_account, who, base_amount)?;
+                pool.quote_reserve = pool.quote_reserve.saturating_add(quote_amount);
+                pool.base_reserve = pool.base_reserve.saturating_sub(base_amount);
+            }
+            Side::Sell => {
+                Self::transfer_free_asset(pair.base(), who, &pool_account, base_amount)?;
+                Self::transfer_free_asset(pair.quote(), &pool_account, who, quote_amount)?;
+                pool.base_reserve = pool.base_reserve.saturating_add(base_amount);
+                pool.quote_reserve = pool.quote_reserve.saturating_sub(quote_amount);
+            }
+        }
+        PoolOf::<T>::insert(pair.id, pool);
+
+        // The average price of swap is rounded down to the tick of trading pair,
+        // the swap too small to make a valid price is not recorded.
+        let price = Self::convert_to_price(base_amount, quote_amount, pair)?;
+        let tick: T::Price = pair.tick().saturated_into();
+        let price = price - price % tick;
+        if !price.is_zero() {
+            let trading_history_idx = Self::trading_history_index_of(pair.id);
+            TradingHistoryIndexOf::<T>::insert(pair.id, trading_history_idx + 1);
+            Self::record_trade(pair.id, trading_history_idx, price, base_amount, side);
+            Self::update_latest_price(pair.id, price, base_amount);
+            Self::trigger_conditional_orders(pair.id, price);
+        }
+
+        Self::deposit_event(Event::<T>::PoolSwapped(
+            who.clone(),
+            pair.id,
+            side,
+            base_amount,
+            quote_amount,
+        ));
+        Ok(())
+    }
+
+    /// Redeems the pool shares of a delisted trading pair for the holders, at most `budget`
+    /// holders are visited in a block, the rest are visited in the following blocks.
+    ///
+    /// The shares locked by `MINIMUM_LIQUIDITY` are left in the pool.
+    ///
+    /// Returns the number of visited holders, the number of redeemed holders and whether
+    /// the pool has been settled.
+    pub(crate) fn settle_delisted_pool(
+        pair: &TradingPairProfile,
+        budget: usize,
+    ) -> (usize, usize, bool) {
+        let share_asset_id = match Self::pool_of(pair.id) {
+            Some(pool) => pool.share_asset_id,
+            None => return (0, 0, true),
+        };
+
+        let holders = PoolShareHoldersOf::<T>::iter_key_prefix(pair.id)
+            .take(budget)
+            .collect::<Vec<_>>();
+        let visited = holders.len();
+        let finished = visited < budget;
+        for who in holders.iter() {
+            PoolShareHoldersOf::<T>::remove(pair.id, who);
+        }
+
+        let mut redeemed = 0;
+        for who in holders {
+            let shares = xpallet_assets::Pallet::<T>::usable_balance(&who, &share_asset_id);
+            if shares.is_zero() {
+                continue;
+            }
+            let result = with_transaction(|| {
+                match Self::apply_remove_liquidity(
+                    &who,
+                    pair.id,
+                    shares,
+                    Zero::zero(),
+                    Zero::zero(),
+                ) {
+                    Ok(()) => TransactionOutcome::Commit(Ok(())),
+                    Err(e) => TransactionOutcome::Rollback(Err(e)),
+                }
+            });
+            match result {
+                Ok(()) => redeemed += 1,
+                Err(e) => error!(
+                    target: "runtime::dex::spot",
+                    "[settle_delisted_pool] who:{:?}, shares:{:?}, error:{:?}",
+                    who, shares, e
+                ),
+            }
+        }
+
+        (visited, redeemed, finished)
+    }
+}
+
+/// Records the holders of pool shares, so that only the holders of the pool are visited
+/// when the pool of a delisted trading pair is settled.
+impl<T: Config> xpallet_assets::OnAssetChanged<T::AccountId, BalanceOf<T>> for Pallet<T> {
+    fn on_issue_pre(id: &AssetId, who: &T::AccountId) {
+        Self::record_pool_share_holder(id, who);
+    }
+
+    fn on_move_pre(
+        id: &AssetId,
+        _: &T::AccountId,
+        _: AssetType,
+        to: &T::AccountId,
+        _: AssetType,
+        _: BalanceOf<T>,
+    ) {
+        Self::record_pool_share_holder(id, to);
+    }
+
+    fn on_set_balance(
+        id: &AssetId,
+        who: &T::AccountId,
+        _: AssetType,
+        _: BalanceOf<T>,
+    ) -> DispatchResult {
+        Self::record_pool_share_holder(id, who);
+        Ok(())
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    fn record_pool_share_holder(asset_id: &AssetId, who: &T::AccountId) {
+        if let Some(pair_id) = asset_id.checked_sub(POOL_SHARE_ASSET_ID_OFFSET) {
+            if PoolOf::<T>::contains_key(pair_id) {
+                PoolShareHoldersOf::<T>::insert(pair_id, who, ());
+            }
+        }
+    }
+}
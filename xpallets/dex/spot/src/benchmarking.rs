@@ -42,6 +42,25 @@ fn b_put_order<T: Config>(
     Ok(())
 }
 
+fn b_create_pool<T: Config>() -> DispatchResult {
+    if PoolOf::<T>::contains_key(PAIR_ID) {
+        return Ok(());
+    }
+    let share_asset = AssetInfo::new::<T>(
+        b"LP-PCX-XBTC".to_vec(),
+        b"PCX/X-BTC pool share".to_vec(),
+        xpallet_assets::Chain::ChainX,
+        8,
+        b"The liquidity pool share of PCX/X-BTC".to_vec(),
+    )?;
+    Pallet::<T>::create_pool(
+        RawOrigin::Root.into(),
+        PAIR_ID,
+        share_asset,
+        Permill::from_parts(3_000),
+    )
+}
+
 benchmarks! {
     // TODO: put_order with matching.
     put_order {
@@ -112,6 +131,131 @@ benchmarks! {
         assert_eq!(Pallet::<T>::trading_pair_of(PAIR_ID).unwrap().tick_decimals, 888);
         assert!(!Pallet::<T>::trading_pair_of(PAIR_ID).unwrap().tradable);
     }
+
+    replace_order {
+        let user: T::AccountId = account("user", 0, SEED);
+
+        b_put_order::<T>(user.clone(), 1000, 100, 1_000_200)?;
+
+    }: _(RawOrigin::Signed(user.clone()), PAIR_ID, 0, 1000u32.into(), 1_000_100u32.into())
+    verify {
+        assert!(OrderInfoOf::<T>::get(&user, 0).is_none());
+        assert!(OrderInfoOf::<T>::get(&user, 1).is_some());
+    }
+
+    put_conditional_order {
+        let user: T::AccountId = account("user", 0, SEED);
+
+        b_prepare_put_order::<T>(&user, 1000, 100)?;
+
+    }: _(RawOrigin::Signed(user.clone()), PAIR_ID, ConditionalKind::StopLoss, 1_000_200u32.into(), OrderType::Limit, Side::Buy, 1000u32.into(), 1_000_200u32.into())
+    verify {
+        assert!(ConditionalOrdersOf::<T>::get(user, 0).is_some());
+    }
+
+    cancel_conditional_order {
+        let user: T::AccountId = account("user", 0, SEED);
+
+        b_prepare_put_order::<T>(&user, 1000, 100)?;
+        Pallet::<T>::put_conditional_order(
+            RawOrigin::Signed(user.clone()).into(),
+            PAIR_ID,
+            ConditionalKind::StopLoss,
+            1_000_200u32.into(),
+            OrderType::Limit,
+            Side::Buy,
+            1000u32.into(),
+            1_000_200u32.into(),
+        )?;
+
+    }: _(RawOrigin::Signed(user.clone()), PAIR_ID, 0)
+    verify {
+        assert!(ConditionalOrdersOf::<T>::get(user, 0).is_none());
+    }
+
+    create_pool {
+        let share_asset = AssetInfo::new::<T>(
+            b"LP-PCX-XBTC".to_vec(),
+            b"PCX/X-BTC pool share".to_vec(),
+            xpallet_assets::Chain::ChainX,
+            8,
+            b"The liquidity pool share of PCX/X-BTC".to_vec(),
+        )?;
+    }: _(RawOrigin::Root, PAIR_ID, share_asset, Permill::from_parts(3_000))
+    verify {
+        assert!(PoolOf::<T>::get(PAIR_ID).is_some());
+    }
+
+    add_liquidity {
+        let user: T::AccountId = account("user", 0, SEED);
+
+        b_prepare_put_order::<T>(&user, 100_000_000, 100_000)?;
+        b_create_pool::<T>()?;
+
+    }: _(RawOrigin::Signed(user.clone()), PAIR_ID, 10_000_000u32.into(), 10_000u32.into())
+    verify {
+        assert!(!PoolOf::<T>::get(PAIR_ID).unwrap().total_shares.is_zero());
+    }
+
+    remove_liquidity {
+        let user: T::AccountId = account("user", 0, SEED);
+
+        b_prepare_put_order::<T>(&user, 100_000_000, 100_000)?;
+        b_create_pool::<T>()?;
+        Pallet::<T>::add_liquidity(
+            RawOrigin::Signed(user.clone()).into(),
+            PAIR_ID,
+            10_000_000u32.into(),
+            10_000u32.into(),
+        )?;
+        let share_asset_id = Pallet::<T>::pool_share_asset_id(PAIR_ID);
+        let shares = xpallet_assets::Pallet::<T>::usable_balance(&user, &share_asset_id);
+
+    }: _(RawOrigin::Signed(user.clone()), PAIR_ID, shares, 0u32.into(), 0u32.into())
+    verify {
+        assert!(xpallet_assets::Pallet::<T>::usable_balance(&user, &share_asset_id).is_zero());
+    }
+
+    swap {
+        let user: T::AccountId = account("user", 0, SEED);
+
+        b_prepare_put_order::<T>(&user, 100_000_000, 100_000)?;
+        b_create_pool::<T>()?;
+        Pallet::<T>::add_liquidity(
+            RawOrigin::Signed(user.clone()).into(),
+            PAIR_ID,
+            10_000_000u32.into(),
+            10_000u32.into(),
+        )?;
+        let quote_reserve = PoolOf::<T>::get(PAIR_ID).unwrap().quote_reserve;
+
+    }: _(RawOrigin::Signed(user.clone()), PAIR_ID, Side::Buy, 100u32.into(), 0u32.into())
+    verify {
+        assert_eq!(
+            PoolOf::<T>::get(PAIR_ID).unwrap().quote_reserve,
+            quote_reserve + 100u32.into()
+        );
+    }
+
+    set_trading_fee {
+        let fee = TradingFee::new(Permill::from_parts(1_000), Permill::from_parts(2_000));
+    }: _(RawOrigin::Root, PAIR_ID, fee)
+    verify {
+        assert_eq!(Pallet::<T>::trading_fee_of(PAIR_ID), fee);
+    }
+
+    set_trading_pair_status {
+    }: _(RawOrigin::Root, PAIR_ID, TradingPairStatus::Delisted)
+    verify {
+        assert_eq!(Pallet::<T>::trading_pair_status_of(PAIR_ID), TradingPairStatus::Delisted);
+        assert_eq!(Pallet::<T>::delisted_pairs(), vec![PAIR_ID]);
+    }
+
+    set_min_twap_volume {
+    }: _(RawOrigin::Root, PAIR_ID, 1000u32.into())
+    verify {
+        assert_eq!(MinTwapVolumeOf::<T>::get(PAIR_ID), 1000u32.into());
+    }
 }
 
 #[cfg(test)]
@@ -139,10 +283,20 @@ mod tests {
             assert_ok!(Pallet::<Test>::test_benchmark_put_order());
             assert_ok!(Pallet::<Test>::test_benchmark_cancel_order());
             assert_ok!(Pallet::<Test>::test_benchmark_force_cancel_order());
+            assert_ok!(Pallet::<Test>::test_benchmark_replace_order());
+            assert_ok!(Pallet::<Test>::test_benchmark_put_conditional_order());
+            assert_ok!(Pallet::<Test>::test_benchmark_cancel_conditional_order());
+            assert_ok!(Pallet::<Test>::test_benchmark_create_pool());
+            assert_ok!(Pallet::<Test>::test_benchmark_add_liquidity());
+            assert_ok!(Pallet::<Test>::test_benchmark_remove_liquidity());
+            assert_ok!(Pallet::<Test>::test_benchmark_swap());
             assert_ok!(Pallet::<Test>::test_benchmark_set_handicap());
             assert_ok!(Pallet::<Test>::test_benchmark_set_price_fluctuation());
             assert_ok!(Pallet::<Test>::test_benchmark_add_trading_pair());
             assert_ok!(Pallet::<Test>::test_benchmark_update_trading_pair());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_trading_fee());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_trading_pair_status());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_min_twap_volume());
         });
     }
 }
@@ -4,9 +4,13 @@
 
 use super::*;
 use xpallet_assets::AssetType::{self, ReservedDexSpot, Usable};
+use xpallet_support::traits::TreasuryAccount;
 
 impl<T: Config> Pallet<T> {
     /// Delivery the assets to maker and taker respectively when executing the order.
+    ///
+    /// Returns the turnover amount of maker and taker, and the fees charged
+    /// from them in the currency they receive.
     pub(super) fn delivery_asset_to_each_other(
         maker_order_side: Side,
         pair: &TradingPairProfile,
@@ -14,7 +18,7 @@ impl<T: Config> Pallet<T> {
         price: T::Price,
         maker_order: &mut OrderInfo<T>,
         taker_order: &mut OrderInfo<T>,
-    ) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+    ) -> Result<((BalanceOf<T>, BalanceOf<T>), (BalanceOf<T>, BalanceOf<T>)), DispatchError> {
         let maker = &maker_order.submitter();
         let taker = &taker_order.submitter();
 
@@ -24,6 +28,8 @@ impl<T: Config> Pallet<T> {
         let turnover_in_quote =
             Self::convert_base_to_quote(turnover, price, pair).unwrap_or_else(|_| Zero::zero());
 
+        let fee = Self::trading_fee_of(pair.id);
+
         match maker_order_side {
             Side::Sell => {
                 // maker(seller): unreserve the base currency and move to the taker.
@@ -31,10 +37,25 @@ impl<T: Config> Pallet<T> {
                 let maker_turnover_amount = turnover;
                 let taker_turnover_amount = turnover_in_quote;
 
-                Self::apply_delivery(base, maker_turnover_amount, maker, taker)?;
-                Self::apply_delivery(quote, taker_turnover_amount, taker, maker)?;
-
-                Ok((maker_turnover_amount, taker_turnover_amount))
+                let taker_fee = Self::apply_delivery_with_fee(
+                    base,
+                    maker_turnover_amount,
+                    fee.taker,
+                    maker,
+                    taker,
+                )?;
+                let maker_fee = Self::apply_delivery_with_fee(
+                    quote,
+                    taker_turnover_amount,
+                    fee.maker,
+                    taker,
+                    maker,
+                )?;
+
+                Ok((
+                    (maker_turnover_amount, taker_turnover_amount),
+                    (maker_fee, taker_fee),
+                ))
             }
             Side::Buy => {
                 // maker(buyer): unreserve the quote currency and move to the taker.
@@ -42,10 +63,50 @@ impl<T: Config> Pallet<T> {
                 let maker_turnover_amount = turnover_in_quote;
                 let taker_turnover_amount = turnover;
 
-                Self::apply_delivery(base, taker_turnover_amount, taker, maker)?;
-                Self::apply_delivery(quote, maker_turnover_amount, maker, taker)?;
+                let maker_fee = Self::apply_delivery_with_fee(
+                    base,
+                    taker_turnover_amount,
+                    fee.maker,
+                    taker,
+                    maker,
+                )?;
+                let taker_fee = Self::apply_delivery_with_fee(
+                    quote,
+                    maker_turnover_amount,
+                    fee.taker,
+                    maker,
+                    taker,
+                )?;
+
+                Ok((
+                    (maker_turnover_amount, taker_turnover_amount),
+                    (maker_fee, taker_fee),
+                ))
+            }
+        }
+    }
 
-                Ok((maker_turnover_amount, taker_turnover_amount))
+    /// Move `value` of the locked balance in Spot of account `from` to `to`,
+    /// the trading fee is deducted from it and goes to the treasury.
+    ///
+    /// Returns the fee actually charged, no fee is charged if there is no treasury account.
+    fn apply_delivery_with_fee(
+        asset_id: AssetId,
+        value: BalanceOf<T>,
+        fee_rate: Permill,
+        from: &T::AccountId,
+        to: &T::AccountId,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let fee = fee_rate.mul_floor(value);
+        match <T as xpallet_assets::Config>::TreasuryAccount::treasury_account() {
+            Some(treasury) if !fee.is_zero() => {
+                Self::apply_delivery(asset_id, value - fee, from, to)?;
+                Self::apply_delivery(asset_id, fee, from, &treasury)?;
+                Ok(fee)
+            }
+            _ => {
+                Self::apply_delivery(asset_id, value, from, to)?;
+                Ok(Zero::zero())
             }
         }
     }
@@ -105,6 +166,29 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Transfer the free balance of `from` to another account.
+    pub(crate) fn transfer_free_asset(
+        asset_id: AssetId,
+        from: &T::AccountId,
+        to: &T::AccountId,
+        value: BalanceOf<T>,
+    ) -> DispatchResult {
+        if Self::is_native_asset(asset_id) {
+            <T as xpallet_assets::Config>::Currency::transfer(
+                from,
+                to,
+                value,
+                frame_support::traits::ExistenceRequirement::AllowDeath,
+            )
+        } else {
+            ensure!(
+                <xpallet_assets::Pallet<T>>::usable_balance(from, &asset_id) >= value,
+                Error::<T>::InsufficientBalance
+            );
+            Self::move_foreign_asset(asset_id, from, Usable, to, Usable, value)
+        }
+    }
+
     /// Transfer some locked native token balance of `from` to another account.
     fn transfer_native_asset(
         from: &T::AccountId,
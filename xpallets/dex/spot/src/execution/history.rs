@@ -0,0 +1,66 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! This module maintains the recent trades and the candles of trading pairs.
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+    /// Returns the slot of ring buffer with `capacity` slots for the item at `index`.
+    pub(crate) fn ring_slot(index: u64, capacity: u32) -> u32 {
+        (index % u64::from(capacity)) as u32
+    }
+
+    /// Returns the period of block `at` given the candle interval.
+    pub(crate) fn candle_period(at: T::BlockNumber, interval: T::BlockNumber) -> u64 {
+        (at / interval).saturated_into()
+    }
+
+    /// Records an executed trade and updates the candles of all intervals.
+    pub(crate) fn record_trade(
+        pair_id: TradingPairId,
+        trading_history_idx: TradingHistoryIndex,
+        price: T::Price,
+        turnover: BalanceOf<T>,
+        taker_side: Side,
+    ) {
+        let current_block = <frame_system::Pallet<T>>::block_number();
+
+        let max_trades = T::MaxRecentTrades::get();
+        if !max_trades.is_zero() {
+            RecentTradesOf::<T>::insert(
+                pair_id,
+                Self::ring_slot(trading_history_idx, max_trades),
+                TradeRecord {
+                    trading_history_idx,
+                    price,
+                    turnover,
+                    taker_side,
+                    executed_at: current_block,
+                },
+            );
+        }
+
+        let max_candles = T::MaxCandles::get();
+        if max_candles.is_zero() {
+            return;
+        }
+
+        for interval in T::CandleIntervals::get() {
+            if interval.is_zero() {
+                continue;
+            }
+            let start = current_block - current_block % interval;
+            let slot = Self::ring_slot(Self::candle_period(current_block, interval), max_candles);
+            CandlesOf::<T>::mutate(
+                (pair_id, interval),
+                slot,
+                |maybe_candle| match maybe_candle {
+                    // The slot is still occupied by the candle of current period.
+                    Some(candle) if candle.start == start => candle.update(price, turnover),
+                    // Overwrite the candle of the period long ago.
+                    _ => *maybe_candle = Some(Candle::new(start, price, turnover)),
+                },
+            );
+        }
+    }
+}
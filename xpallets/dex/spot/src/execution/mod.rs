@@ -1,6 +1,7 @@
 // Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
 
 mod asset;
+mod history;
 mod order;
 mod state;
 
@@ -77,6 +78,65 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Ensures the post-only order would not match immediately, i.e.,
+    ///
+    /// - buy: less than the lowest_ask
+    /// - sell: larger than the highest_bid
+    pub(crate) fn ensure_not_match(
+        price: T::Price,
+        side: Side,
+        pair_id: TradingPairId,
+    ) -> Result<(), Error<T>> {
+        let handicap = <HandicapOf<T>>::get(pair_id);
+        let would_match = match side {
+            Side::Buy => !handicap.lowest_ask.is_zero() && price >= handicap.lowest_ask,
+            Side::Sell => !handicap.highest_bid.is_zero() && price <= handicap.highest_bid,
+        };
+        ensure!(!would_match, Error::<T>::PostOnlyOrderWouldMatch);
+        Ok(())
+    }
+
+    /// Returns the price that a market order can sweep the order book to.
+    ///
+    /// The worst price given by the submitter is restricted within the price
+    /// fluctuation of the handicap:
+    ///
+    /// - buy: [lowest_ask, min(worst_price, lowest_ask + fluctuation)]
+    /// - sell: [max(worst_price, highest_bid - fluctuation), highest_bid]
+    pub(crate) fn market_order_bound(
+        worst_price: T::Price,
+        side: Side,
+        pair: &TradingPairProfile,
+    ) -> Result<T::Price, Error<T>> {
+        let handicap = <HandicapOf<T>>::get(pair.id);
+        let fluctuation: T::Price = pair.calc_fluctuation::<T>().saturated_into();
+
+        match side {
+            Side::Buy => {
+                let lowest_ask = handicap.lowest_ask;
+                ensure!(
+                    !lowest_ask.is_zero() && worst_price >= lowest_ask,
+                    Error::<T>::MarketOrderNotMatched
+                );
+                Ok(cmp::min(
+                    worst_price,
+                    lowest_ask.saturating_add(fluctuation),
+                ))
+            }
+            Side::Sell => {
+                let highest_bid = handicap.highest_bid;
+                ensure!(
+                    !highest_bid.is_zero() && worst_price <= highest_bid,
+                    Error::<T>::MarketOrderNotMatched
+                );
+                Ok(cmp::max(
+                    worst_price,
+                    highest_bid.saturating_sub(fluctuation),
+                ))
+            }
+        }
+    }
+
     /// Returns true if there are already too many orders at the `price` and `side` for a trading pair.
     pub(crate) fn has_too_many_backlog_orders(
         pair_id: TradingPairId,
@@ -96,7 +156,7 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
-    fn currency_decimals_of(asset_id: AssetId) -> Option<u8> {
+    pub(crate) fn currency_decimals_of(asset_id: AssetId) -> Option<u8> {
         <xpallet_assets_registrar::Pallet<T>>::asset_info_of(asset_id).map(|x| x.decimals())
     }
 
@@ -152,4 +212,39 @@ impl<T: Config> Pallet<T> {
             Err(Error::<T>::InvalidTradingPairAsset)
         }
     }
+
+    /// Returns the average price of converting `amount` of the base currency
+    /// to `volume` of the quote currency, the reverse of `convert_base_to_quote`.
+    ///
+    /// price = volume * 10^(base.decimals + price.decimals - quote.decimals) / amount
+    pub(crate) fn convert_to_price(
+        amount: BalanceOf<T>,
+        volume: BalanceOf<T>,
+        pair: &TradingPairProfile,
+    ) -> Result<T::Price, Error<T>> {
+        ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+        if let (Some(base_p), Some(quote_p)) = (
+            Self::currency_decimals_of(pair.base()),
+            Self::currency_decimals_of(pair.quote()),
+        ) {
+            let (base_p, quote_p, pair_p) =
+                (u32::from(base_p), u32::from(quote_p), pair.pip_decimals);
+
+            let amount = amount.saturated_into::<u128>();
+            let volume = volume.saturated_into::<u128>();
+
+            let price = if quote_p >= (base_p + pair_p) {
+                volume / amount / 10_u128.pow(quote_p - base_p - pair_p)
+            } else {
+                volume
+                    .checked_mul(10_u128.pow(base_p + pair_p - quote_p))
+                    .map(|v| v / amount)
+                    .unwrap_or(u128::MAX)
+            };
+
+            Ok(price.saturated_into())
+        } else {
+            Err(Error::<T>::InvalidTradingPairAsset)
+        }
+    }
 }
@@ -12,13 +12,18 @@ impl<T: Config> Pallet<T> {
     /// - sell: larger than the highest_bid
     /// what we only need to do is to check if the handicap should be updated.
     /// Or else we should match the order.
+    ///
+    /// The order which can not rest on the order book is handled according to the time in force:
+    /// - ImmediateOrCancel: the unfilled part is canceled.
+    /// - FillOrKill: fails if there are not enough counterparty orders to fill it completely.
     pub(crate) fn try_match_order(
         pair: &TradingPairProfile,
         order: &mut OrderInfo<T>,
         pair_index: TradingPairId,
         side: Side,
         price: T::Price,
-    ) {
+        time_in_force: TimeInForceOf<T>,
+    ) -> Result<(), Error<T>> {
         let handicap = <HandicapOf<T>>::get(pair_index);
         let (lowest_ask, highest_bid) = (handicap.lowest_ask, handicap.highest_bid);
 
@@ -29,8 +34,35 @@ impl<T: Config> Pallet<T> {
             Side::Sell => highest_bid.is_zero() || price > highest_bid,
         };
 
-        // If there is no chance to match order, we only have to insert this quote and update handicap.
-        if skip_match_order {
+        if time_in_force == TimeInForce::FillOrKill {
+            let levels = if skip_match_order {
+                None
+            } else {
+                Self::fill_or_kill_levels(pair, order, &handicap)
+            };
+            let levels = levels.ok_or(Error::<T>::FillOrKillNotFilled)?;
+
+            // Only the counterparty price levels found above are visited again.
+            let counterparty_side = match side {
+                Side::Buy => Side::Sell,
+                Side::Sell => Side::Buy,
+            };
+            for counterparty_price in levels {
+                Self::apply_match_order_given_counterparty(
+                    order,
+                    pair,
+                    counterparty_price,
+                    counterparty_side,
+                );
+            }
+            Self::settle_matched_order(pair, order, false);
+            return Ok(());
+        }
+
+        if skip_match_order && !time_in_force.rests_on_book() {
+            Self::cancel_unfilled_order(order, pair);
+        } else if skip_match_order {
+            // If there is no chance to match order, we only have to insert this quote and update handicap.
             <QuotationsOf<T>>::mutate(order.pair_id(), order.price(), |quotations| {
                 quotations.push((order.submitter(), order.id()))
             });
@@ -45,8 +77,70 @@ impl<T: Config> Pallet<T> {
                 _ => (),
             }
         } else {
-            Self::match_order(pair, order, &handicap);
+            Self::match_order(pair, order, &handicap, time_in_force.rests_on_book());
         }
+
+        Ok(())
+    }
+
+    /// Returns the counterparty price levels that can fill the order completely, stops
+    /// collecting once it's enough to fill the order, None if they are not enough.
+    fn fill_or_kill_levels(
+        pair: &TradingPairProfile,
+        order: &OrderInfo<T>,
+        handicap: &HandicapInfo<T>,
+    ) -> Option<Vec<T::Price>> {
+        let tick = pair.tick();
+        let wanted = order.remaining_in_base();
+        let mut matchable: BalanceOf<T> = Zero::zero();
+        let mut levels = Vec::new();
+
+        //  Buy: [ lowest_ask  , my_quote ]
+        // Sell: [ my_quote , highest_bid ]
+        let (mut counterparty_price, counterparty_side) = match order.side() {
+            Side::Buy => (handicap.lowest_ask, Side::Sell),
+            Side::Sell => (handicap.highest_bid, Side::Buy),
+        };
+        let in_range = |p: T::Price| match order.side() {
+            Side::Buy => p <= order.price(),
+            Side::Sell => p >= order.price(),
+        };
+
+        while !counterparty_price.is_zero() && in_range(counterparty_price) && matchable < wanted {
+            let level_start = matchable;
+            for (who, order_id) in <QuotationsOf<T>>::get(pair.id, counterparty_price).iter() {
+                if let Some(maker_order) = <OrderInfoOf<T>>::get(who, order_id) {
+                    if maker_order.side() == counterparty_side {
+                        matchable = matchable.saturating_add(maker_order.remaining_in_base());
+                    }
+                }
+            }
+            if matchable > level_start {
+                levels.push(counterparty_price);
+            }
+            counterparty_price = match order.side() {
+                Side::Buy => Self::tick_up(counterparty_price, tick),
+                Side::Sell => Self::tick_down(counterparty_price, tick),
+            };
+        }
+
+        if matchable >= wanted {
+            Some(levels)
+        } else {
+            None
+        }
+    }
+
+    /// Refund the unfilled part of the order which can not rest on the order book.
+    fn cancel_unfilled_order(order: &mut OrderInfo<T>, pair: &TradingPairProfile) {
+        let who = order.submitter();
+        let refund_result = Self::update_order_and_unreserve_on_cancel(order, pair, &who);
+        assert!(
+            refund_result.is_ok(),
+            "Unreserve the unfilled order can not fail"
+        );
+        <OrderInfoOf<T>>::remove(&who, order.id());
+        <OpenOrdersOf<T>>::remove(&who, order.id());
     }
 
     /// Insert a fresh order and return the inserted result.
@@ -68,6 +162,7 @@ impl<T: Config> Pallet<T> {
 
         debug!(target: "runtime::dex::spot", "[inject_order] New order:{:?}", order);
         <OrderInfoOf<T>>::insert(&who, order_id, &order);
+        <OpenOrdersOf<T>>::insert(&who, order_id, pair_id);
 
         // The order count of user should be increased after a new order is created.
         <OrderCountOf<T>>::insert(&who, order_id + 1);
@@ -119,6 +214,7 @@ impl<T: Config> Pallet<T> {
         pair: &TradingPairProfile,
         order: &mut OrderInfo<T>,
         handicap: &HandicapInfo<T>,
+        rests_on_book: bool,
     ) {
         #[cfg(feature = "std")]
         let now = std::time::Instant::now();
@@ -128,11 +224,29 @@ impl<T: Config> Pallet<T> {
         #[cfg(feature = "std")]
         debug!(target: "runtime::dex::spot", "Took {:?}ms to match this order", now.elapsed().as_millis());
 
+        Self::settle_matched_order(pair, order, rests_on_book);
+    }
+
+    /// Settle the order after matching.
+    fn settle_matched_order(
+        pair: &TradingPairProfile,
+        order: &mut OrderInfo<T>,
+        rests_on_book: bool,
+    ) {
+        if !order.already_filled.is_zero() {
+            if let Some(info) = Self::trading_pair_info_of(pair.id) {
+                Self::trigger_conditional_orders(pair.id, info.latest_price);
+            }
+        }
+
         // Remove the full filled order, otherwise the quotations, order status and handicap
         // should be updated.
         if order.is_fulfilled() {
             order.status = OrderStatus::Filled;
             <OrderInfoOf<T>>::remove(order.submitter(), order.id());
+            <OpenOrdersOf<T>>::remove(order.submitter(), order.id());
+        } else if !rests_on_book {
+            Self::cancel_unfilled_order(order, pair);
         } else {
             <QuotationsOf<T>>::mutate(order.pair_id(), order.price(), |quotations| {
                 quotations.push((order.submitter(), order.id()))
@@ -192,7 +306,7 @@ impl<T: Config> Pallet<T> {
                     Self::update_handicap(pair, counterparty_price, maker_order.side());
                 }
 
-                Self::update_latest_price(pair.id, counterparty_price);
+                Self::update_latest_price(pair.id, counterparty_price, turnover);
             }
         }
 
@@ -284,6 +398,7 @@ impl<T: Config> Pallet<T> {
         order_side: Side,
     ) {
         <OrderInfoOf<T>>::remove(&who, order_index);
+        <OpenOrdersOf<T>>::remove(&who, order_index);
 
         let order_key = (who, order_index);
         Self::remove_quotation(pair_id, price, order_key);
@@ -358,14 +473,15 @@ impl<T: Config> Pallet<T> {
         Self::update_order_on_execute(maker_order, &turnover, trading_history_idx);
         Self::update_order_on_execute(taker_order, &turnover, trading_history_idx);
 
-        let (maker_turnover_amount, taker_turnover_amount) = Self::delivery_asset_to_each_other(
-            maker_order.side(),
-            &pair,
-            turnover,
-            price,
-            maker_order,
-            taker_order,
-        )?;
+        let ((maker_turnover_amount, taker_turnover_amount), fees) =
+            Self::delivery_asset_to_each_other(
+                maker_order.side(),
+                &pair,
+                turnover,
+                price,
+                maker_order,
+                taker_order,
+            )?;
 
         maker_order.decrease_remaining_on_execute(maker_turnover_amount);
         taker_order.decrease_remaining_on_execute(taker_turnover_amount);
@@ -384,11 +500,20 @@ impl<T: Config> Pallet<T> {
         // FIXME: The information delivered by these events seems be redundant.
         Self::deposit_event(Event::<T>::MakerOrderUpdated(maker_order.clone()));
         Self::deposit_event(Event::<T>::TakerOrderUpdated(taker_order.clone()));
+        Self::record_trade(
+            pair_id,
+            trading_history_idx,
+            price,
+            turnover,
+            taker_order.side(),
+        );
+
         Self::deposit_event(Event::<T>::OrderExecuted(OrderExecutedInfo::new(
             trading_history_idx,
             pair_id,
             price,
             turnover,
+            fees,
             maker_order,
             taker_order,
             <frame_system::Pallet<T>>::block_number(),
@@ -410,6 +535,29 @@ impl<T: Config> Pallet<T> {
 
         Self::generic_unreserve(who, refund_asset, refund_amount)?;
 
+        Self::update_order_on_cancel(order, refund_amount);
+
+        Ok(())
+    }
+
+    /// Cancels the order but keeps its remaining asset reserved.
+    ///
+    /// Returns the asset and the amount still reserved by the order.
+    pub(crate) fn update_order_and_keep_reserve_on_cancel(
+        order: &mut OrderInfo<T>,
+        pair: &TradingPairProfile,
+    ) -> (AssetId, BalanceOf<T>) {
+        let (reserved_asset, reserved_amount) = match order.side() {
+            Side::Sell => (pair.base(), order.remaining_in_base()),
+            Side::Buy => (pair.quote(), order.remaining),
+        };
+
+        Self::update_order_on_cancel(order, reserved_amount);
+
+        (reserved_asset, reserved_amount)
+    }
+
+    fn update_order_on_cancel(order: &mut OrderInfo<T>, refund_amount: BalanceOf<T>) {
         order.update_status_on_cancel();
         order.decrease_remaining_on_cancel(refund_amount);
         order.last_update_at = <frame_system::Pallet<T>>::block_number();
@@ -417,7 +565,5 @@ impl<T: Config> Pallet<T> {
         OrderInfoOf::<T>::insert(order.submitter(), order.id(), order.clone());
 
         Self::deposit_event(Event::<T>::CanceledOrderUpdated(order.clone()));
-
-        Ok(())
     }
 }
@@ -137,7 +137,7 @@ impl<T: Config> Pallet<T> {
     /// Removes the order as well as the quotations from the order list.
     ///
     /// This happens when the maker orders have been completely filled.
-    pub(super) fn remove_orders_and_quotations(
+    pub(crate) fn remove_orders_and_quotations(
         pair_id: TradingPairId,
         price: T::Price,
         fulfilled_orders: Vec<(T::AccountId, OrderId)>,
@@ -149,6 +149,7 @@ impl<T: Config> Pallet<T> {
         );
         for (who, order_idx) in fulfilled_orders.iter() {
             <OrderInfoOf<T>>::remove(who, order_idx);
+            <OpenOrdersOf<T>>::remove(who, order_idx);
         }
 
         <QuotationsOf<T>>::mutate(pair_id, price, |quotations| {
@@ -179,8 +180,13 @@ impl<T: Config> Pallet<T> {
 
     /// Updates the latest price of a trading pair.
     ///
-    /// This happens after an order is executed every time.
-    pub(crate) fn update_latest_price(pair_index: TradingPairId, latest: T::Price) {
+    /// This happens after an order is executed every time, the price of trade whose
+    /// `turnover` is less than `MinTwapVolumeOf` is not counted in the TWAP.
+    pub(crate) fn update_latest_price(
+        pair_index: TradingPairId,
+        latest: T::Price,
+        turnover: BalanceOf<T>,
+    ) {
         let current_block = <frame_system::Pallet<T>>::block_number();
 
         <TradingPairInfoOf<T>>::insert(
@@ -190,5 +196,48 @@ impl<T: Config> Pallet<T> {
                 last_updated: current_block,
             },
         );
+
+        if turnover >= Self::min_twap_volume_of(pair_index) {
+            Self::accumulate_price(pair_index, latest);
+        }
+    }
+
+    /// Moves the conditional orders crossed by the latest price to the triggered queue.
+    ///
+    /// This happens once an order has been matched, the price only moves towards one
+    /// direction while matching an order, so the final price crosses all the trigger
+    /// prices crossed by the intermediate prices.
+    pub(crate) fn trigger_conditional_orders(pair_id: TradingPairId, latest: T::Price) {
+        let triggers = ConditionalTriggersOf::<T>::get(pair_id);
+        if triggers.is_empty() {
+            return;
+        }
+
+        let (triggered, dormant): (Vec<_>, Vec<_>) =
+            triggers
+                .into_iter()
+                .partition(|(kind, side, trigger_price, _, _)| {
+                    kind.is_triggered(*side, *trigger_price, latest)
+                });
+        if triggered.is_empty() {
+            return;
+        }
+
+        ConditionalTriggersOf::<T>::insert(pair_id, dormant);
+        TriggeredOrders::<T>::mutate(|orders| {
+            for (_, _, _, who, order_id) in triggered {
+                debug!(
+                    target: "runtime::dex::spot",
+                    "[trigger_conditional_orders] who: {:?}, order_id: {:?}, latest_price: {:?}",
+                    who, order_id, latest
+                );
+                Self::deposit_event(Event::<T>::ConditionalOrderTriggered(
+                    who.clone(),
+                    order_id,
+                    latest,
+                ));
+                orders.push((who, order_id));
+            }
+        });
     }
 }
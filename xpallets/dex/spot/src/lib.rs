@@ -5,7 +5,10 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::type_complexity)]
 
+mod amm;
 mod execution;
+mod migrations;
+mod oracle;
 mod rpc;
 mod types;
 pub mod weights;
@@ -20,24 +23,29 @@ mod tests;
 use codec::Codec;
 
 use sp_runtime::traits::{
-    AtLeast32BitUnsigned, MaybeSerializeDeserialize, Member, SaturatedConversion, StaticLookup,
-    Zero,
+    AtLeast32BitUnsigned, MaybeSerializeDeserialize, Member, One, SaturatedConversion, Saturating,
+    StaticLookup, Zero,
 };
+use sp_runtime::{Permill, TransactionOutcome};
 use sp_std::prelude::*;
 use sp_std::{cmp, fmt::Debug};
 
 use frame_support::{
     dispatch::{DispatchError, DispatchResult},
     ensure,
-    log::info,
+    log::{error, info},
+    storage::with_transaction,
     traits::{Currency, Get, ReservableCurrency},
-    Parameter,
+    transactional,
+    weights::Weight,
+    PalletId, Parameter,
 };
 use frame_system::{ensure_root, ensure_signed};
 
 use chainx_primitives::AssetId;
-use xpallet_assets::AssetErr;
+use xpallet_assets::{AssetErr, AssetInfo};
 
+pub use self::amm::POOL_SHARE_ASSET_ID_OFFSET;
 pub use self::rpc::*;
 pub use self::types::*;
 pub use self::weights::WeightInfo;
@@ -45,6 +53,31 @@ pub use self::weights::WeightInfo;
 /// Maximum of backlog orders.
 const MAX_BACKLOG_ORDER: usize = 1000;
 
+/// Maximum of orders in a batch call.
+const MAX_BATCH_ORDERS: usize = 100;
+
+/// Maximum of dormant conditional orders of a trading pair.
+const MAX_CONDITIONAL_ORDERS: usize = 200;
+
+/// Maximum of dormant conditional orders of an account.
+const MAX_CONDITIONAL_ORDERS_PER_ACCOUNT: usize = 10;
+
+/// Maximum of triggered conditional orders placed in a block.
+const MAX_TRIGGERED_ORDERS_PER_BLOCK: usize = 50;
+
+/// The maximum of maker and taker fee rate, 10%.
+const MAX_TRADING_FEE: Permill = Permill::from_parts(100_000);
+
+/// Maximum of expired orders canceled in a block, the rest are postponed to the next block.
+const MAX_EXPIRED_ORDERS_PER_BLOCK: usize = 100;
+
+/// Maximum of price observations kept in the TWAP window of a trading pair.
+const MAX_PRICE_OBSERVATIONS: u32 = 8;
+
+/// Maximum of orders of the delisted trading pairs canceled in a block,
+/// the rest are canceled in the following blocks.
+const MAX_DELISTED_ORDERS_PER_BLOCK: usize = 100;
+
 /// The maximum ticks that a price can deviated from the handicap.
 ///
 /// NOTE:
@@ -69,8 +102,20 @@ pub type OrderInfo<T> = Order<
     <T as frame_system::Config>::BlockNumber,
 >;
 
+pub type ConditionalOrderInfo<T> = ConditionalOrder<
+    TradingPairId,
+    <T as frame_system::Config>::AccountId,
+    BalanceOf<T>,
+    <T as Config>::Price,
+    <T as frame_system::Config>::BlockNumber,
+>;
+
 pub type HandicapInfo<T> = Handicap<<T as Config>::Price>;
 
+pub type PoolInfo<T> = Pool<BalanceOf<T>>;
+
+pub type TimeInForceOf<T> = TimeInForce<<T as frame_system::Config>::BlockNumber>;
+
 pub use pallet::*;
 
 #[frame_support::pallet]
@@ -94,17 +139,70 @@ pub mod pallet {
             + MaybeSerializeDeserialize
             + Debug;
 
+        /// The pallet ID from which the accounts of liquidity pools are derived.
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+
+        /// The candle intervals of trading pairs, measured in blocks.
+        type CandleIntervals: Get<Vec<Self::BlockNumber>>;
+
+        /// The maximum number of candles kept for each interval of a trading pair.
+        #[pallet::constant]
+        type MaxCandles: Get<u32>;
+
+        /// The maximum number of recent trades kept for a trading pair.
+        #[pallet::constant]
+        type MaxRecentTrades: Get<u32>;
+
+        /// The number of blocks over which the time-weighted average price is calculated.
+        #[pallet::constant]
+        type TwapWindow: Get<Self::BlockNumber>;
+
+        /// The native deposit reserved for each dormant conditional order, returned
+        /// once the order is canceled or triggered.
+        #[pallet::constant]
+        type ConditionalOrderDeposit: Get<BalanceOf<Self>>;
+
         type WeightInfo: WeightInfo;
     }
 
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
     #[pallet::generate_store(pub(crate) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(PhantomData<T>);
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            Self::cancel_expired_orders(n)
+                .saturating_add(Self::place_triggered_orders())
+                .saturating_add(Self::settle_delisted_pairs())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            if StorageVersion::get::<Pallet<T>>() < 1 {
+                let weight = migrations::migrate_to_v1::<T>();
+                STORAGE_VERSION.put::<Pallet<T>>();
+                weight
+            } else {
+                0
+            }
+        }
+    }
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
+        /// Put a new order.
+        ///
+        /// For the market order, `price` is the worst price the submitter accepts,
+        /// the order sweeps the order book from the handicap until it's filled or
+        /// the worst price is reached, the unfilled part will be refunded.
         #[pallet::weight(<T as Config>::WeightInfo::put_order())]
+        #[transactional]
         pub fn put_order(
             origin: OriginFor<T>,
             #[pallet::compact] pair_id: TradingPairId,
@@ -114,49 +212,242 @@ pub mod pallet {
             #[pallet::compact] price: T::Price,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
+            Self::do_put_order(
+                who,
+                pair_id,
+                order_type,
+                side,
+                amount,
+                price,
+                TimeInForce::GoodTillCancel,
+            )
+        }
 
-            ensure!(!price.is_zero(), Error::<T>::InvalidPrice);
-            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
-            ensure!(order_type == OrderType::Limit, Error::<T>::InvalidOrderType);
+        /// Put a new order with the time in force.
+        ///
+        /// - `ImmediateOrCancel`: the unfilled part is canceled after matching.
+        /// - `FillOrKill`: the order is rejected if it can not be filled completely.
+        /// - `PostOnly`: the order is rejected if it would match immediately.
+        /// - `GoodTillBlock`: the unfilled part is canceled at the given block.
+        #[pallet::weight(<T as Config>::WeightInfo::put_order())]
+        #[transactional]
+        pub fn put_order_with_time_in_force(
+            origin: OriginFor<T>,
+            #[pallet::compact] pair_id: TradingPairId,
+            order_type: OrderType,
+            side: Side,
+            #[pallet::compact] amount: BalanceOf<T>,
+            #[pallet::compact] price: T::Price,
+            time_in_force: TimeInForceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::do_put_order(who, pair_id, order_type, side, amount, price, time_in_force)
+        }
 
-            let pair = Self::trading_pair(pair_id)?;
+        #[pallet::weight(<T as Config>::WeightInfo::cancel_order())]
+        pub fn cancel_order(
+            origin: OriginFor<T>,
+            #[pallet::compact] pair_id: TradingPairId,
+            #[pallet::compact] order_id: OrderId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::do_cancel_order(&who, pair_id, order_id)?;
+            Ok(())
+        }
 
-            ensure!(pair.tradable, Error::<T>::TradingPairUntradable);
-            ensure!(pair.is_valid_price(price), Error::<T>::InvalidPrice);
+        /// Put a batch of orders of a trading pair atomically.
+        ///
+        /// Each order is given in `(order_type, side, amount, price)`, the whole
+        /// batch fails if any of them fails.
+        #[pallet::weight(
+            <T as Config>::WeightInfo::put_order().saturating_mul(orders.len() as Weight)
+        )]
+        #[transactional]
+        pub fn put_orders(
+            origin: OriginFor<T>,
+            #[pallet::compact] pair_id: TradingPairId,
+            orders: Vec<(OrderType, Side, BalanceOf<T>, T::Price)>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                orders.len() <= MAX_BATCH_ORDERS,
+                Error::<T>::TooManyOrdersInBatch
+            );
+            for (order_type, side, amount, price) in orders {
+                Self::do_put_order(
+                    who.clone(),
+                    pair_id,
+                    order_type,
+                    side,
+                    amount,
+                    price,
+                    TimeInForce::GoodTillCancel,
+                )?;
+            }
+            Ok(())
+        }
 
-            Self::is_valid_quote(price, side, pair_id)?;
-            Self::has_too_many_backlog_orders(pair_id, price, side)?;
+        /// Cancel a batch of orders of a trading pair atomically.
+        #[pallet::weight(
+            <T as Config>::WeightInfo::cancel_order().saturating_mul(order_ids.len() as Weight)
+        )]
+        #[transactional]
+        pub fn cancel_orders(
+            origin: OriginFor<T>,
+            #[pallet::compact] pair_id: TradingPairId,
+            order_ids: Vec<OrderId>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                order_ids.len() <= MAX_BATCH_ORDERS,
+                Error::<T>::TooManyOrdersInBatch
+            );
+            for order_id in order_ids {
+                Self::do_cancel_order(&who, pair_id, order_id)?;
+            }
+            Ok(())
+        }
 
-            // Reserve the token according to the order side.
-            let (reserve_asset, reserve_amount) = match side {
-                Side::Buy => (
-                    pair.quote(),
-                    Self::convert_base_to_quote(amount, price, &pair)?,
-                ),
-                Side::Sell => (pair.base(), amount),
-            };
-            Self::put_order_reserve(&who, reserve_asset, reserve_amount)?;
-            Self::apply_put_order(
+        /// Cancel all the orders of the sender in a trading pair.
+        ///
+        /// At most `MAX_BATCH_ORDERS` orders are canceled in one call.
+        #[pallet::weight(
+            <T as Config>::WeightInfo::cancel_order().saturating_mul(MAX_BATCH_ORDERS as Weight)
+        )]
+        #[transactional]
+        pub fn cancel_all_orders(
+            origin: OriginFor<T>,
+            #[pallet::compact] pair_id: TradingPairId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let canceled = Self::do_cancel_all_orders(&who, pair_id)?;
+            Ok(
+                Some(<T as Config>::WeightInfo::cancel_order().saturating_mul(canceled as Weight))
+                    .into(),
+            )
+        }
+
+        /// Replace an order with the new amount and price.
+        ///
+        /// The remaining reserve of the old order is moved to the new one directly,
+        /// only the difference is reserved or unreserved.
+        #[pallet::weight(<T as Config>::WeightInfo::replace_order())]
+        #[transactional]
+        pub fn replace_order(
+            origin: OriginFor<T>,
+            #[pallet::compact] pair_id: TradingPairId,
+            #[pallet::compact] order_id: OrderId,
+            #[pallet::compact] amount: BalanceOf<T>,
+            #[pallet::compact] price: T::Price,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::do_replace_order(who, pair_id, order_id, amount, price)
+        }
+
+        /// Put a new conditional order.
+        ///
+        /// The balance is reserved in the same way as `put_order`, the order
+        /// stays dormant until the latest price crosses `trigger_price`, then
+        /// it's placed as an order of `order_type` in the next block.
+        #[pallet::weight(<T as Config>::WeightInfo::put_conditional_order())]
+        #[transactional]
+        #[allow(clippy::too_many_arguments)]
+        pub fn put_conditional_order(
+            origin: OriginFor<T>,
+            #[pallet::compact] pair_id: TradingPairId,
+            kind: ConditionalKind,
+            #[pallet::compact] trigger_price: T::Price,
+            order_type: OrderType,
+            side: Side,
+            #[pallet::compact] amount: BalanceOf<T>,
+            #[pallet::compact] price: T::Price,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::do_put_conditional_order(
                 who,
                 pair_id,
+                kind,
+                trigger_price,
                 order_type,
                 side,
                 amount,
                 price,
-                reserve_amount,
-            )?;
-            Ok(())
+            )
         }
 
-        #[pallet::weight(<T as Config>::WeightInfo::cancel_order())]
-        pub fn cancel_order(
+        /// Cancel a dormant conditional order.
+        #[pallet::weight(<T as Config>::WeightInfo::cancel_conditional_order())]
+        pub fn cancel_conditional_order(
             origin: OriginFor<T>,
             #[pallet::compact] pair_id: TradingPairId,
             #[pallet::compact] order_id: OrderId,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            Self::do_cancel_order(&who, pair_id, order_id)?;
-            Ok(())
+            Self::do_cancel_conditional_order(&who, pair_id, order_id)
+        }
+
+        /// Create the liquidity pool of a trading pair.
+        ///
+        /// The pool share is registered as a new asset of ID
+        /// `POOL_SHARE_ASSET_ID_OFFSET + pair_id` with the given `share_asset` info.
+        #[pallet::weight(<T as Config>::WeightInfo::create_pool())]
+        #[transactional]
+        pub fn create_pool(
+            origin: OriginFor<T>,
+            #[pallet::compact] pair_id: TradingPairId,
+            share_asset: AssetInfo,
+            fee: Permill,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            let pair = Self::trading_pair(pair_id)?;
+            Self::apply_create_pool(&pair, share_asset, fee)
+        }
+
+        /// Add liquidity to the pool of a trading pair.
+        ///
+        /// The quote currency is taken in proportion to the pool reserves, at most
+        /// `max_quote_amount`. The first provider of the pool decides the initial price.
+        #[pallet::weight(<T as Config>::WeightInfo::add_liquidity())]
+        #[transactional]
+        pub fn add_liquidity(
+            origin: OriginFor<T>,
+            #[pallet::compact] pair_id: TradingPairId,
+            #[pallet::compact] base_amount: BalanceOf<T>,
+            #[pallet::compact] max_quote_amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::apply_add_liquidity(&who, pair_id, base_amount, max_quote_amount)
+        }
+
+        /// Burn the pool shares and withdraw the liquidity in proportion.
+        #[pallet::weight(<T as Config>::WeightInfo::remove_liquidity())]
+        #[transactional]
+        pub fn remove_liquidity(
+            origin: OriginFor<T>,
+            #[pallet::compact] pair_id: TradingPairId,
+            #[pallet::compact] shares: BalanceOf<T>,
+            #[pallet::compact] min_base_amount: BalanceOf<T>,
+            #[pallet::compact] min_quote_amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::apply_remove_liquidity(&who, pair_id, shares, min_base_amount, min_quote_amount)
+        }
+
+        /// Swap against the pool of a trading pair directly.
+        ///
+        /// `amount_in` is measured by the quote currency if `side` is `Buy`,
+        /// otherwise the base currency.
+        #[pallet::weight(<T as Config>::WeightInfo::swap())]
+        #[transactional]
+        pub fn swap(
+            origin: OriginFor<T>,
+            #[pallet::compact] pair_id: TradingPairId,
+            side: Side,
+            #[pallet::compact] amount_in: BalanceOf<T>,
+            #[pallet::compact] min_amount_out: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::apply_swap(&who, pair_id, side, amount_in, min_amount_out)
         }
 
         /// Force cancel an order.
@@ -236,9 +527,60 @@ pub mod pallet {
                 tick_decimals >= pair.tick_decimals,
                 Error::<T>::InvalidTickdecimals
             );
+            ensure!(
+                Self::trading_pair_status_of(pair_id) != TradingPairStatus::Delisted,
+                Error::<T>::TradingPairDelisted
+            );
             Self::apply_update_trading_pair(pair_id, tick_decimals, tradable);
             Ok(())
         }
+
+        /// Set the maker and taker fee rates of the trading pair.
+        #[pallet::weight(<T as Config>::WeightInfo::set_trading_fee())]
+        pub fn set_trading_fee(
+            origin: OriginFor<T>,
+            #[pallet::compact] pair_id: TradingPairId,
+            fee: TradingFee,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::trading_pair(pair_id)?;
+            ensure!(
+                fee.maker <= MAX_TRADING_FEE && fee.taker <= MAX_TRADING_FEE,
+                Error::<T>::InvalidTradingFee
+            );
+            TradingFeeOf::<T>::insert(pair_id, fee);
+            Self::deposit_event(Event::<T>::TradingFeeUpdated(pair_id, fee));
+            Ok(())
+        }
+
+        /// Move the trading pair to the next listing status.
+        ///
+        /// Proposed -> Active <-> ReduceOnly -> Delisted. Once delisted, the resting
+        /// orders of the trading pair are canceled and refunded in the following blocks.
+        #[pallet::weight(<T as Config>::WeightInfo::set_trading_pair_status())]
+        pub fn set_trading_pair_status(
+            origin: OriginFor<T>,
+            #[pallet::compact] pair_id: TradingPairId,
+            status: TradingPairStatus,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::apply_set_trading_pair_status(pair_id, status)
+        }
+
+        /// Set the minimum turnover of a trade, measured by the base currency, for
+        /// its price to be counted in the TWAP of the trading pair.
+        #[pallet::weight(<T as Config>::WeightInfo::set_min_twap_volume())]
+        pub fn set_min_twap_volume(
+            origin: OriginFor<T>,
+            #[pallet::compact] pair_id: TradingPairId,
+            #[pallet::compact] volume: BalanceOf<T>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::trading_pair(pair_id)?;
+            MinTwapVolumeOf::<T>::insert(pair_id, volume);
+            Self::deposit_event(Event::<T>::MinTwapVolumeUpdated(pair_id, volume));
+            Ok(())
+        }
     }
 
     #[pallet::event]
@@ -266,6 +608,49 @@ pub mod pallet {
         TradingPairUpdated(TradingPairProfile),
         /// Price fluctuation of trading pair has been updated. [pair_id, price_fluctuation]
         PriceFluctuationUpdated(TradingPairId, PriceFluctuation),
+        /// A new conditional order has been created. [conditional_order_info]
+        ConditionalOrderCreated(ConditionalOrderInfo<T>),
+        /// The conditional order has been canceled. [who, order_id]
+        ConditionalOrderCanceled(T::AccountId, OrderId),
+        /// The conditional order has been triggered by the latest price. [who, order_id, latest_price]
+        ConditionalOrderTriggered(T::AccountId, OrderId, T::Price),
+        /// Failed to place the triggered conditional order, the reserved balance
+        /// has been returned. [who, order_id, error]
+        ConditionalOrderFailed(T::AccountId, OrderId, DispatchError),
+        /// The liquidity pool of trading pair has been created. [pair_id, pool]
+        PoolCreated(TradingPairId, PoolInfo<T>),
+        /// Liquidity has been added to the pool. [who, pair_id, base_amount, quote_amount, shares]
+        LiquidityAdded(
+            T::AccountId,
+            TradingPairId,
+            BalanceOf<T>,
+            BalanceOf<T>,
+            BalanceOf<T>,
+        ),
+        /// Liquidity has been removed from the pool. [who, pair_id, base_amount, quote_amount, shares]
+        LiquidityRemoved(
+            T::AccountId,
+            TradingPairId,
+            BalanceOf<T>,
+            BalanceOf<T>,
+            BalanceOf<T>,
+        ),
+        /// Swapped against the pool. [who, pair_id, side, base_amount, quote_amount]
+        PoolSwapped(
+            T::AccountId,
+            TradingPairId,
+            Side,
+            BalanceOf<T>,
+            BalanceOf<T>,
+        ),
+        /// Trading fee rates of trading pair has been updated. [pair_id, trading_fee]
+        TradingFeeUpdated(TradingPairId, TradingFee),
+        /// The listing status of trading pair has been changed. [pair_id, status]
+        TradingPairStatusChanged(TradingPairId, TradingPairStatus),
+        /// All the resting orders of the delisted trading pair have been canceled. [pair_id]
+        TradingPairSettled(TradingPairId),
+        /// The minimum turnover of trade counted in the TWAP has been updated. [pair_id, volume]
+        MinTwapVolumeUpdated(TradingPairId, BalanceOf<T>),
     }
 
     /// Error for the spot module.
@@ -307,6 +692,40 @@ pub mod pallet {
         InvalidOrderId,
         /// Error from assets module.
         AssetError,
+        /// There is no counterparty order within the worst price of the market order.
+        MarketOrderNotMatched,
+        /// The post-only order would match immediately.
+        PostOnlyOrderWouldMatch,
+        /// The fill-or-kill order can not be filled completely.
+        FillOrKillNotFilled,
+        /// The expiry of good-till-block order must be a future block.
+        InvalidExpiry,
+        /// The trading fee rate can not exceed 10%.
+        InvalidTradingFee,
+        /// The liquidity pool of trading pair already exists.
+        PoolAlreadyExists,
+        /// The liquidity pool of trading pair does not exist.
+        PoolNotFound,
+        /// The liquidity of pool is insufficient.
+        InsufficientLiquidity,
+        /// The amount exceeds the slippage limit given by the sender.
+        SlippageExceeded,
+        /// Too many orders in a batch call.
+        TooManyOrdersInBatch,
+        /// The conditional order would be triggered immediately.
+        ConditionalOrderAlreadyTriggered,
+        /// Too many dormant conditional orders in the trading pair.
+        TooManyConditionalOrders,
+        /// Too many dormant conditional orders of the account.
+        TooManyAccountConditionalOrders,
+        /// The trading pair has no latest price to trigger the conditional order.
+        LatestPriceNotFound,
+        /// The trading pair does not accept new orders in its current listing status.
+        TradingPairNotActive,
+        /// The trading pair has been delisted.
+        TradingPairDelisted,
+        /// The trading pair can not move to the given listing status.
+        InvalidTradingPairStatus,
     }
 
     /// How many trading pairs so far.
@@ -338,6 +757,58 @@ pub mod pallet {
     pub(crate) type TradingHistoryIndexOf<T: Config> =
         StorageMap<_, Twox64Concat, TradingPairId, TradingHistoryIndex, ValueQuery>;
 
+    /// Recent trades of a trading pair.
+    ///
+    /// This is a ring buffer of `MaxRecentTrades` slots, the trade is
+    /// stored at the slot `trading_history_idx % MaxRecentTrades`.
+    #[pallet::storage]
+    #[pallet::getter(fn recent_trade_of)]
+    pub(crate) type RecentTradesOf<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        TradingPairId,
+        Twox64Concat,
+        u32,
+        TradeRecord<BalanceOf<T>, T::Price, T::BlockNumber>,
+    >;
+
+    /// Candles of a trading pair given the interval, i.e., `(pair_id, interval)`.
+    ///
+    /// This is a ring buffer of `MaxCandles` slots, the candle of the period
+    /// `block_number / interval` is stored at the slot `period % MaxCandles`.
+    #[pallet::storage]
+    #[pallet::getter(fn candle_of)]
+    pub(crate) type CandlesOf<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        (TradingPairId, T::BlockNumber),
+        Twox64Concat,
+        u32,
+        Candle<T::Price, BalanceOf<T>, T::BlockNumber>,
+    >;
+
+    /// The accumulated price of trading pair for calculating the time-weighted average price.
+    #[pallet::storage]
+    #[pallet::getter(fn price_accumulator_of)]
+    pub(crate) type PriceAccumulatorOf<T: Config> =
+        StorageMap<_, Twox64Concat, TradingPairId, PriceAccumulator<T::Price, T::BlockNumber>>;
+
+    /// Snapshots of the accumulated price of trading pair, i.e., `(block_number, cumulative)`.
+    ///
+    /// At most `MAX_PRICE_OBSERVATIONS` snapshots are taken in the TWAP window,
+    /// only one snapshot older than the window is kept.
+    #[pallet::storage]
+    #[pallet::getter(fn price_observations_of)]
+    pub(crate) type PriceObservationsOf<T: Config> =
+        StorageMap<_, Twox64Concat, TradingPairId, Vec<(T::BlockNumber, u128)>, ValueQuery>;
+
+    /// The minimum turnover of a trade, measured by the base currency, for its price
+    /// to be counted in the TWAP, so that the TWAP can not be moved by dust trades.
+    #[pallet::storage]
+    #[pallet::getter(fn min_twap_volume_of)]
+    pub(crate) type MinTwapVolumeOf<T: Config> =
+        StorageMap<_, Twox64Concat, TradingPairId, BalanceOf<T>, ValueQuery>;
+
     /// Total orders made by an account.
     #[pallet::storage]
     #[pallet::getter(fn order_count_of)]
@@ -350,6 +821,15 @@ pub mod pallet {
     pub(crate) type OrderInfoOf<T: Config> =
         StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, OrderId, OrderInfo<T>>;
 
+    /// Index of the open orders of an account, i.e., the orders resting on the
+    /// order book that are unfilled or partially filled.
+    ///
+    /// AccountId, OrderId => TradingPairId
+    #[pallet::storage]
+    #[pallet::getter(fn open_orders_of)]
+    pub(crate) type OpenOrdersOf<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, OrderId, TradingPairId>;
+
     /// All the accounts and the order number given the trading pair ID and price.
     #[pallet::storage]
     #[pallet::getter(fn quotations_of)]
@@ -369,6 +849,76 @@ pub mod pallet {
     pub(crate) type HandicapOf<T: Config> =
         StorageMap<_, Twox64Concat, TradingPairId, HandicapInfo<T>, ValueQuery>;
 
+    /// The listing status of trading pair.
+    #[pallet::storage]
+    #[pallet::getter(fn trading_pair_status_of)]
+    pub(crate) type TradingPairStatusOf<T: Config> =
+        StorageMap<_, Twox64Concat, TradingPairId, TradingPairStatus, ValueQuery>;
+
+    /// The delisted trading pairs whose resting orders are being canceled.
+    #[pallet::storage]
+    #[pallet::getter(fn delisted_pairs)]
+    pub(crate) type DelistedPairs<T: Config> = StorageValue<_, Vec<TradingPairId>, ValueQuery>;
+
+    /// The accounts that have received the pool shares of trading pair, whose shares are
+    /// redeemed once the trading pair is delisted.
+    #[pallet::storage]
+    pub(crate) type PoolShareHoldersOf<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, TradingPairId, Twox64Concat, T::AccountId, ()>;
+
+    /// The maker and taker fee rates of trading pair, the fees go to the treasury.
+    #[pallet::storage]
+    #[pallet::getter(fn trading_fee_of)]
+    pub(crate) type TradingFeeOf<T: Config> =
+        StorageMap<_, Twox64Concat, TradingPairId, TradingFee, ValueQuery>;
+
+    /// The good-till-block orders expired at the given block.
+    #[pallet::storage]
+    #[pallet::getter(fn order_expiries_of)]
+    pub(crate) type OrderExpiriesOf<T: Config> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, Vec<(T::AccountId, OrderId)>, ValueQuery>;
+
+    /// The constant-product liquidity pool of a trading pair.
+    #[pallet::storage]
+    #[pallet::getter(fn pool_of)]
+    pub(crate) type PoolOf<T: Config> = StorageMap<_, Twox64Concat, TradingPairId, PoolInfo<T>>;
+
+    /// Total conditional orders made by an account.
+    #[pallet::storage]
+    #[pallet::getter(fn conditional_order_count_of)]
+    pub(crate) type ConditionalOrderCountOf<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, OrderId, ValueQuery>;
+
+    /// Details of the dormant conditional order given account and conditional order ID.
+    #[pallet::storage]
+    #[pallet::getter(fn conditional_order_of)]
+    pub(crate) type ConditionalOrdersOf<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        OrderId,
+        ConditionalOrderInfo<T>,
+    >;
+
+    /// The triggers of dormant conditional orders of a trading pair,
+    /// i.e., `(kind, side, trigger_price, who, order_id)`.
+    #[pallet::storage]
+    #[pallet::getter(fn conditional_triggers_of)]
+    pub(crate) type ConditionalTriggersOf<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        TradingPairId,
+        Vec<(ConditionalKind, Side, T::Price, T::AccountId, OrderId)>,
+        ValueQuery,
+    >;
+
+    /// The triggered conditional orders waiting to be placed.
+    #[pallet::storage]
+    #[pallet::getter(fn triggered_orders)]
+    pub(crate) type TriggeredOrders<T: Config> =
+        StorageValue<_, Vec<(T::AccountId, OrderId)>, ValueQuery>;
+
     #[pallet::type_value]
     pub fn DefaultForPriceFluctuationOf() -> PriceFluctuation {
         DEFAULT_FLUCTUATION
@@ -473,6 +1023,9 @@ impl<T: Config> Pallet<T> {
         info!(target: "runtime::dex::spot", "New trading pair: {:?}", pair);
 
         TradingPairOf::<T>::insert(pair_id, &pair);
+        if !tradable {
+            TradingPairStatusOf::<T>::insert(pair_id, TradingPairStatus::Proposed);
+        }
         TradingPairInfoOf::<T>::insert(
             pair_id,
             TradingPairInfo {
@@ -499,8 +1052,243 @@ impl<T: Config> Pallet<T> {
                 Self::deposit_event(Event::<T>::TradingPairUpdated(pair.clone()));
             }
         });
+
+        // The proposed trading pair goes active once it's made tradable.
+        if tradable && Self::trading_pair_status_of(pair_id) == TradingPairStatus::Proposed {
+            TradingPairStatusOf::<T>::insert(pair_id, TradingPairStatus::Active);
+            Self::deposit_event(Event::<T>::TradingPairStatusChanged(
+                pair_id,
+                TradingPairStatus::Active,
+            ));
+        }
+    }
+
+    fn apply_set_trading_pair_status(
+        pair_id: TradingPairId,
+        status: TradingPairStatus,
+    ) -> DispatchResult {
+        let mut pair = Self::trading_pair(pair_id)?;
+        ensure!(
+            Self::trading_pair_status_of(pair_id).can_transit_to(status),
+            Error::<T>::InvalidTradingPairStatus
+        );
+
+        info!(
+            target: "runtime::dex::spot",
+            "[set_trading_pair_status] pair_id: {:}, status: {:?}",
+            pair_id, status
+        );
+
+        // The resting orders of a reduce-only trading pair can still be canceled.
+        let tradable = status != TradingPairStatus::Delisted;
+        if pair.tradable != tradable {
+            pair.tradable = tradable;
+            TradingPairOf::<T>::insert(pair_id, &pair);
+            Self::deposit_event(Event::<T>::TradingPairUpdated(pair));
+        }
+
+        TradingPairStatusOf::<T>::insert(pair_id, status);
+        if status == TradingPairStatus::Delisted {
+            DelistedPairs::<T>::append(pair_id);
+        }
+
+        Self::deposit_event(Event::<T>::TradingPairStatusChanged(pair_id, status));
+
+        Ok(())
+    }
+
+    fn ensure_trading_pair_active(pair_id: TradingPairId) -> DispatchResult {
+        match Self::trading_pair_status_of(pair_id) {
+            TradingPairStatus::Active => Ok(()),
+            TradingPairStatus::Delisted => Err(Error::<T>::TradingPairDelisted.into()),
+            _ => Err(Error::<T>::TradingPairNotActive.into()),
+        }
+    }
+
+    /// The orders of a delisted trading pair can still be canceled by the submitters
+    /// before they are canceled by the settlement.
+    fn ensure_trading_pair_cancelable(pair: &TradingPairProfile) -> DispatchResult {
+        ensure!(
+            pair.tradable || Self::trading_pair_status_of(pair.id) == TradingPairStatus::Delisted,
+            Error::<T>::TradingPairUntradable
+        );
+        Ok(())
+    }
+
+    /// Cancels the conditional orders and resting orders of the delisted trading pairs and
+    /// refunds the reserved balances, at most `MAX_DELISTED_ORDERS_PER_BLOCK` orders are
+    /// canceled in a block, the rest are canceled in the following blocks.
+    ///
+    /// The liquidity pool is settled after all the orders are canceled, see `settle_delisted_pool`.
+    fn settle_delisted_pairs() -> Weight {
+        let mut delisted = Self::delisted_pairs();
+        let pair_id = match delisted.first() {
+            Some(pair_id) => *pair_id,
+            None => return T::DbWeight::get().reads(1),
+        };
+
+        let pair = match Self::trading_pair_of(pair_id) {
+            Some(pair) => pair,
+            None => {
+                delisted.remove(0);
+                DelistedPairs::<T>::put(delisted);
+                return T::DbWeight::get().reads_writes(2, 1);
+            }
+        };
+
+        let mut budget = MAX_DELISTED_ORDERS_PER_BLOCK;
+
+        let mut triggers = ConditionalTriggersOf::<T>::take(pair_id);
+        if triggers.len() > budget {
+            let rest = triggers.split_off(budget);
+            ConditionalTriggersOf::<T>::insert(pair_id, rest);
+        }
+        budget -= triggers.len();
+        for (_, _, _, who, order_id) in triggers.iter() {
+            if let Some(order) = ConditionalOrdersOf::<T>::take(who, order_id) {
+                if let Err(e) = Self::unreserve_conditional_order(&order, &pair) {
+                    error!(
+                        target: "runtime::dex::spot",
+                        "[settle_delisted_pairs] who:{:?}, conditional order_id:{}, error:{:?}",
+                        who, order_id, e
+                    );
+                }
+                Self::deposit_event(Event::<T>::ConditionalOrderCanceled(who.clone(), *order_id));
+            }
+        }
+
+        let levels = QuotationsOf::<T>::iter_prefix(pair_id)
+            .take(budget)
+            .collect::<Vec<_>>();
+        let orders_settled = levels.is_empty() && budget > 0;
+
+        let mut canceled = triggers.len();
+        for (price, quotations) in levels {
+            if budget == 0 {
+                break;
+            }
+            let count = cmp::min(budget, quotations.len());
+            for (who, order_id) in quotations.iter().take(count) {
+                if let Err(e) = Self::apply_cancel_order(who, pair_id, *order_id) {
+                    error!(
+                        target: "runtime::dex::spot",
+                        "[settle_delisted_pairs] who:{:?}, order_id:{}, error:{:?}",
+                        who, order_id, e
+                    );
+                    // Drop the order anyway, otherwise the settlement never ends.
+                    Self::remove_orders_and_quotations(
+                        pair_id,
+                        price,
+                        vec![(who.clone(), *order_id)],
+                    );
+                }
+            }
+            if count == quotations.len() {
+                QuotationsOf::<T>::remove(pair_id, price);
+            }
+            budget = budget.saturating_sub(cmp::max(count, 1));
+            canceled += count;
+        }
+
+        let (visited, redeemed, settled) = if orders_settled {
+            Self::settle_delisted_pool(&pair, budget)
+        } else {
+            (0, 0, false)
+        };
+
+        if settled {
+            delisted.remove(0);
+            DelistedPairs::<T>::put(delisted);
+            HandicapOf::<T>::remove(pair_id);
+            Self::deposit_event(Event::<T>::TradingPairSettled(pair_id));
+        }
+
+        T::DbWeight::get()
+            .reads_writes(3 + visited as Weight, 3 + visited as Weight)
+            .saturating_add(
+                <T as Config>::WeightInfo::cancel_order().saturating_mul(canceled as Weight),
+            )
+            .saturating_add(
+                <T as Config>::WeightInfo::remove_liquidity().saturating_mul(redeemed as Weight),
+            )
+    }
+
+    fn do_put_order(
+        who: T::AccountId,
+        pair_id: TradingPairId,
+        order_type: OrderType,
+        side: Side,
+        amount: BalanceOf<T>,
+        price: T::Price,
+        time_in_force: TimeInForceOf<T>,
+    ) -> DispatchResult {
+        ensure!(!price.is_zero(), Error::<T>::InvalidPrice);
+        ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+
+        let pair = Self::trading_pair(pair_id)?;
+
+        ensure!(pair.tradable, Error::<T>::TradingPairUntradable);
+        Self::ensure_trading_pair_active(pair_id)?;
+        ensure!(pair.is_valid_price(price), Error::<T>::InvalidPrice);
+
+        // The market order never rests on the order book.
+        let time_in_force = match (order_type, time_in_force) {
+            (OrderType::Market, TimeInForce::GoodTillCancel) => TimeInForce::ImmediateOrCancel,
+            (OrderType::Market, TimeInForce::PostOnly)
+            | (OrderType::Market, TimeInForce::GoodTillBlock(_)) => {
+                return Err(Error::<T>::InvalidOrderType.into())
+            }
+            (_, time_in_force) => time_in_force,
+        };
+
+        match time_in_force {
+            TimeInForce::PostOnly => Self::ensure_not_match(price, side, pair_id)?,
+            TimeInForce::GoodTillBlock(expiry) => ensure!(
+                expiry > <frame_system::Pallet<T>>::block_number(),
+                Error::<T>::InvalidExpiry
+            ),
+            _ => (),
+        }
+
+        let price = match order_type {
+            OrderType::Limit => {
+                Self::is_valid_quote(price, side, pair_id)?;
+                Self::has_too_many_backlog_orders(pair_id, price, side)?;
+                price
+            }
+            OrderType::Market => Self::market_order_bound(price, side, &pair)?,
+        };
+
+        // The taker order is filled by the pool directly if the pool offers a better price.
+        if time_in_force != TimeInForce::PostOnly
+            && Self::try_fill_by_pool(&who, &pair, side, amount, price)?
+        {
+            return Ok(());
+        }
+
+        // Reserve the token according to the order side.
+        let (reserve_asset, reserve_amount) = match side {
+            Side::Buy => (
+                pair.quote(),
+                Self::convert_base_to_quote(amount, price, &pair)?,
+            ),
+            Side::Sell => (pair.base(), amount),
+        };
+        Self::put_order_reserve(&who, reserve_asset, reserve_amount)?;
+        Self::apply_put_order(
+            who,
+            pair_id,
+            order_type,
+            side,
+            amount,
+            price,
+            reserve_amount,
+            time_in_force,
+        )?;
+        Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn apply_put_order(
         who: T::AccountId,
         pair_id: TradingPairId,
@@ -509,11 +1297,12 @@ impl<T: Config> Pallet<T> {
         amount: BalanceOf<T>,
         price: T::Price,
         reserve_amount: BalanceOf<T>,
+        time_in_force: TimeInForceOf<T>,
     ) -> Result<(), Error<T>> {
         info!(
             target: "runtime::dex::spot",
-            "transactor:{:?}, pair_id:{:}, type:{:?}, side:{:?}, amount:{:?}, price:{:?}",
-            who, pair_id, order_type, side, amount, price
+            "transactor:{:?}, pair_id:{:}, type:{:?}, side:{:?}, amount:{:?}, price:{:?}, time_in_force:{:?}",
+            who, pair_id, order_type, side, amount, price, time_in_force
         );
 
         let pair = Self::trading_pair(pair_id)?;
@@ -528,18 +1317,237 @@ impl<T: Config> Pallet<T> {
             reserve_amount,
         );
 
-        Self::try_match_order(&pair, &mut order, pair_id, side, price);
+        Self::try_match_order(&pair, &mut order, pair_id, side, price, time_in_force)?;
+
+        if let TimeInForce::GoodTillBlock(expiry) = time_in_force {
+            if OrderInfoOf::<T>::contains_key(order.submitter(), order.id()) {
+                OrderExpiriesOf::<T>::append(expiry, (order.submitter(), order.id()));
+            }
+        }
 
         Ok(())
     }
 
+    /// Cancels the good-till-block orders expired at block `n`.
+    fn cancel_expired_orders(n: T::BlockNumber) -> Weight {
+        let mut expired = OrderExpiriesOf::<T>::take(n);
+        if expired.is_empty() {
+            return T::DbWeight::get().reads(1);
+        }
+
+        // Postpone the rest to the next block.
+        if expired.len() > MAX_EXPIRED_ORDERS_PER_BLOCK {
+            let postponed = expired.split_off(MAX_EXPIRED_ORDERS_PER_BLOCK);
+            OrderExpiriesOf::<T>::mutate(n + One::one(), |orders| orders.extend(postponed));
+        }
+
+        for (who, order_id) in expired.iter() {
+            if let Some(order) = Self::order_info_of(who, order_id) {
+                if let Err(e) = Self::apply_cancel_order(who, order.pair_id(), *order_id) {
+                    error!(
+                        target: "runtime::dex::spot",
+                        "[cancel_expired_orders] who:{:?}, order_id:{}, error:{:?}",
+                        who, order_id, e
+                    );
+                }
+            }
+        }
+
+        T::DbWeight::get().reads_writes(2, 2).saturating_add(
+            <T as Config>::WeightInfo::cancel_order().saturating_mul(expired.len() as Weight),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn do_put_conditional_order(
+        who: T::AccountId,
+        pair_id: TradingPairId,
+        kind: ConditionalKind,
+        trigger_price: T::Price,
+        order_type: OrderType,
+        side: Side,
+        amount: BalanceOf<T>,
+        price: T::Price,
+    ) -> DispatchResult {
+        ensure!(!price.is_zero(), Error::<T>::InvalidPrice);
+        ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+
+        let pair = Self::trading_pair(pair_id)?;
+
+        ensure!(pair.tradable, Error::<T>::TradingPairUntradable);
+        Self::ensure_trading_pair_active(pair_id)?;
+        ensure!(
+            pair.is_valid_price(price) && pair.is_valid_price(trigger_price),
+            Error::<T>::InvalidPrice
+        );
+
+        // The zero price is not a real trade, it would trigger the sell stops at once.
+        let latest_price = Self::trading_pair_info_of(pair_id)
+            .map(|info| info.latest_price)
+            .filter(|price| !price.is_zero())
+            .ok_or(Error::<T>::LatestPriceNotFound)?;
+        ensure!(
+            !kind.is_triggered(side, trigger_price, latest_price),
+            Error::<T>::ConditionalOrderAlreadyTriggered
+        );
+        ensure!(
+            Self::conditional_triggers_of(pair_id).len() < MAX_CONDITIONAL_ORDERS,
+            Error::<T>::TooManyConditionalOrders
+        );
+        ensure!(
+            ConditionalOrdersOf::<T>::iter_prefix_values(&who)
+                .take(MAX_CONDITIONAL_ORDERS_PER_ACCOUNT)
+                .count()
+                < MAX_CONDITIONAL_ORDERS_PER_ACCOUNT,
+            Error::<T>::TooManyAccountConditionalOrders
+        );
+
+        let (reserve_asset, reserved) = match side {
+            Side::Buy => (
+                pair.quote(),
+                Self::convert_base_to_quote(amount, price, &pair)?,
+            ),
+            Side::Sell => (pair.base(), amount),
+        };
+        Self::put_order_reserve(&who, reserve_asset, reserved)?;
+        let deposit = T::ConditionalOrderDeposit::get();
+        <T as xpallet_assets::Config>::Currency::reserve(&who, deposit)?;
+
+        let id = Self::conditional_order_count_of(&who);
+        ConditionalOrderCountOf::<T>::insert(&who, id + 1);
+
+        let order = ConditionalOrder {
+            id,
+            pair_id,
+            submitter: who.clone(),
+            kind,
+            trigger_price,
+            order_type,
+            side,
+            amount,
+            price,
+            reserved,
+            deposit,
+            created_at: <frame_system::Pallet<T>>::block_number(),
+        };
+
+        ConditionalTriggersOf::<T>::append(pair_id, (kind, side, trigger_price, who.clone(), id));
+        ConditionalOrdersOf::<T>::insert(&who, id, &order);
+
+        Self::deposit_event(Event::<T>::ConditionalOrderCreated(order));
+
+        Ok(())
+    }
+
+    fn do_cancel_conditional_order(
+        who: &T::AccountId,
+        pair_id: TradingPairId,
+        order_id: OrderId,
+    ) -> DispatchResult {
+        let pair = Self::trading_pair(pair_id)?;
+        let order = Self::conditional_order_of(who, order_id)
+            .filter(|order| order.pair_id == pair_id)
+            .ok_or(Error::<T>::InvalidOrderId)?;
+
+        // The triggered order is going to be placed.
+        ensure!(
+            !Self::triggered_orders().contains(&(who.clone(), order_id)),
+            Error::<T>::CancelOrderNotAllowed
+        );
+
+        Self::unreserve_conditional_order(&order, &pair)?;
+
+        ConditionalTriggersOf::<T>::mutate(pair_id, |triggers| {
+            triggers.retain(|(_, _, _, submitter, id)| !(submitter == who && *id == order_id))
+        });
+        ConditionalOrdersOf::<T>::remove(who, order_id);
+
+        Self::deposit_event(Event::<T>::ConditionalOrderCanceled(who.clone(), order_id));
+
+        Ok(())
+    }
+
+    fn unreserve_conditional_order(
+        order: &ConditionalOrderInfo<T>,
+        pair: &TradingPairProfile,
+    ) -> DispatchResult {
+        let reserve_asset = match order.side {
+            Side::Buy => pair.quote(),
+            Side::Sell => pair.base(),
+        };
+        Self::generic_unreserve(&order.submitter, reserve_asset, order.reserved)?;
+        <T as xpallet_assets::Config>::Currency::unreserve(&order.submitter, order.deposit);
+        Ok(())
+    }
+
+    /// Places the triggered conditional orders, at most `MAX_TRIGGERED_ORDERS_PER_BLOCK`
+    /// orders are placed in a block, the rest are postponed to the next block.
+    fn place_triggered_orders() -> Weight {
+        let mut triggered = TriggeredOrders::<T>::take();
+        if triggered.is_empty() {
+            return T::DbWeight::get().reads(1);
+        }
+
+        if triggered.len() > MAX_TRIGGERED_ORDERS_PER_BLOCK {
+            let postponed = triggered.split_off(MAX_TRIGGERED_ORDERS_PER_BLOCK);
+            TriggeredOrders::<T>::put(postponed);
+        }
+
+        for (who, order_id) in triggered.iter() {
+            if let Some(order) = Self::conditional_order_of(who, order_id) {
+                if let Err(e) = Self::place_conditional_order(&order) {
+                    error!(
+                        target: "runtime::dex::spot",
+                        "[place_triggered_orders] who:{:?}, order_id:{}, error:{:?}",
+                        who, order_id, e
+                    );
+                    Self::deposit_event(Event::<T>::ConditionalOrderFailed(
+                        who.clone(),
+                        *order_id,
+                        e,
+                    ));
+                }
+            }
+        }
+
+        T::DbWeight::get().reads_writes(2, 2).saturating_add(
+            <T as Config>::WeightInfo::put_order().saturating_mul(triggered.len() as Weight),
+        )
+    }
+
+    /// Converts the conditional order into a normal order.
+    ///
+    /// The reserved balance is returned first and then reserved again by the new order,
+    /// it remains free if the new order can not be placed. The conditional order is
+    /// kept if its reserved balance can not be returned, so it can still be canceled.
+    fn place_conditional_order(order: &ConditionalOrderInfo<T>) -> DispatchResult {
+        let pair = Self::trading_pair(order.pair_id)?;
+        Self::unreserve_conditional_order(order, &pair)?;
+        ConditionalOrdersOf::<T>::remove(&order.submitter, order.id);
+
+        with_transaction(|| {
+            match Self::do_put_order(
+                order.submitter.clone(),
+                order.pair_id,
+                order.order_type,
+                order.side,
+                order.amount,
+                order.price,
+                TimeInForce::GoodTillCancel,
+            ) {
+                Ok(()) => TransactionOutcome::Commit(Ok(())),
+                Err(e) => TransactionOutcome::Rollback(Err(e)),
+            }
+        })
+    }
+
     fn do_cancel_order(
         who: &T::AccountId,
         pair_id: TradingPairId,
         order_id: OrderId,
     ) -> DispatchResult {
         let pair = Self::trading_pair(pair_id)?;
-        ensure!(pair.tradable, Error::<T>::TradingPairUntradable);
+        Self::ensure_trading_pair_cancelable(&pair)?;
 
         let order = Self::get_order(who, order_id)?;
         ensure!(
@@ -552,6 +1560,91 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Returns the number of canceled orders.
+    fn do_cancel_all_orders(
+        who: &T::AccountId,
+        pair_id: TradingPairId,
+    ) -> Result<usize, DispatchError> {
+        let pair = Self::trading_pair(pair_id)?;
+        Self::ensure_trading_pair_cancelable(&pair)?;
+
+        let order_ids = OpenOrdersOf::<T>::iter_prefix(who)
+            .filter(|(_, order_pair_id)| *order_pair_id == pair_id)
+            .map(|(order_id, _)| order_id)
+            .take(MAX_BATCH_ORDERS)
+            .collect::<Vec<_>>();
+
+        for order_id in order_ids.iter() {
+            Self::apply_cancel_order(who, pair_id, *order_id)?;
+        }
+
+        Ok(order_ids.len())
+    }
+
+    fn do_replace_order(
+        who: T::AccountId,
+        pair_id: TradingPairId,
+        order_id: OrderId,
+        amount: BalanceOf<T>,
+        price: T::Price,
+    ) -> DispatchResult {
+        ensure!(!price.is_zero(), Error::<T>::InvalidPrice);
+        ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+
+        let pair = Self::trading_pair(pair_id)?;
+
+        ensure!(pair.tradable, Error::<T>::TradingPairUntradable);
+        Self::ensure_trading_pair_active(pair_id)?;
+        ensure!(pair.is_valid_price(price), Error::<T>::InvalidPrice);
+
+        let mut order = Self::get_order(&who, order_id)?;
+        ensure!(order.pair_id() == pair_id, Error::<T>::InvalidOrderId);
+        ensure!(
+            order.status == OrderStatus::Created || order.status == OrderStatus::PartialFill,
+            Error::<T>::CancelOrderNotAllowed
+        );
+
+        let side = order.side();
+
+        // Cancel the old order without releasing the reserve.
+        let (reserve_asset, reserved) =
+            Self::update_order_and_keep_reserve_on_cancel(&mut order, &pair);
+        Self::kill_order(
+            pair_id,
+            order.price(),
+            who.clone(),
+            order_id,
+            pair.clone(),
+            side,
+        );
+
+        Self::is_valid_quote(price, side, pair_id)?;
+        Self::has_too_many_backlog_orders(pair_id, price, side)?;
+
+        let reserve_amount = match side {
+            Side::Buy => Self::convert_base_to_quote(amount, price, &pair)?,
+            Side::Sell => amount,
+        };
+        if reserve_amount > reserved {
+            Self::put_order_reserve(&who, reserve_asset, reserve_amount - reserved)?;
+        } else if reserve_amount < reserved {
+            Self::generic_unreserve(&who, reserve_asset, reserved - reserve_amount)?;
+        }
+
+        Self::apply_put_order(
+            who,
+            pair_id,
+            OrderType::Limit,
+            side,
+            amount,
+            price,
+            reserve_amount,
+            TimeInForce::GoodTillCancel,
+        )?;
+
+        Ok(())
+    }
+
     fn apply_cancel_order(
         who: &T::AccountId,
         pair_id: TradingPairId,
@@ -598,7 +1691,7 @@ impl<T: Config> xpallet_assets_registrar::RegistrarHandler for Pallet<T> {
 }
 
 /*
-这段 Rust 代码是一个实现了 `xpallet_assets_registrar::RegistrarHandler` trait 的函数,用于处理特定于 
+这段 Rust 代码是一个实现了 `xpallet_assets_registrar::RegistrarHandler` trait 的函数,用于处理特定于
 ChainX 区块链 pallet 的资产注销(deregistration)逻辑.当一个资产被注销时,这个函数会检查所有交易对(trading pairs),并更新那些包含已注销资产的交易对的状态.
 
 代码解释如下:
@@ -0,0 +1,23 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{log::info, traits::Get, weights::Weight};
+
+use crate::{Config, OpenOrdersOf, OrderInfoOf, OrderStatus};
+
+/// Builds the index of open orders from the existing orders.
+pub fn migrate_to_v1<T: Config>() -> Weight {
+    let mut visited = 0u64;
+    let mut indexed = 0u64;
+    for (who, order_id, order) in OrderInfoOf::<T>::iter() {
+        visited += 1;
+        if order.status == OrderStatus::Created || order.status == OrderStatus::PartialFill {
+            OpenOrdersOf::<T>::insert(who, order_id, order.pair_id());
+            indexed += 1;
+        }
+    }
+    info!(
+        target: "runtime::dex::spot",
+        "[migrate_to_v1] {} of {} orders indexed", indexed, visited
+    );
+    T::DbWeight::get().reads_writes(visited, indexed)
+}
@@ -9,6 +9,7 @@ use frame_support::{
     parameter_types,
     traits::{GenesisBuild, Get},
     weights::Weight,
+    PalletId,
 };
 use sp_core::H256;
 use sp_runtime::{
@@ -103,9 +104,24 @@ impl pallet_balances::Config for Test {
     type MaxReserves = MaxReserves;
 }
 
+parameter_types! {
+    pub const SpotPalletId: PalletId = PalletId(*b"pcx/spot");
+    pub CandleIntervals: Vec<u64> = vec![10, 100];
+    pub const MaxCandles: u32 = 5;
+    pub const MaxRecentTrades: u32 = 3;
+    pub const TwapWindow: u64 = 16;
+    pub const ConditionalOrderDeposit: Balance = 10;
+}
+
 impl Config for Test {
     type Event = ();
     type Price = Price;
+    type PalletId = SpotPalletId;
+    type CandleIntervals = CandleIntervals;
+    type MaxCandles = MaxCandles;
+    type MaxRecentTrades = MaxRecentTrades;
+    type TwapWindow = TwapWindow;
+    type ConditionalOrderDeposit = ConditionalOrderDeposit;
     type WeightInfo = ();
 }
 
@@ -120,12 +136,22 @@ impl xpallet_assets_registrar::Config for Test {
     type WeightInfo = ();
 }
 
+pub struct DummyTreasuryAccount;
+
+pub(crate) const TREASURY_ACCOUNT: AccountId = 100_000;
+
+impl xpallet_support::traits::TreasuryAccount<AccountId> for DummyTreasuryAccount {
+    fn treasury_account() -> Option<AccountId> {
+        Some(TREASURY_ACCOUNT)
+    }
+}
+
 impl xpallet_assets::Config for Test {
     type Event = ();
     type Currency = Balances;
-    type TreasuryAccount = ();
+    type TreasuryAccount = DummyTreasuryAccount;
     type OnCreatedAccount = frame_system::Provider<Test>;
-    type OnAssetChanged = ();
+    type OnAssetChanged = XSpot;
     type WeightInfo = ();
 }
 
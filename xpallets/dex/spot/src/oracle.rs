@@ -0,0 +1,124 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! This module maintains the time-weighted average prices of trading pairs.
+
+use super::*;
+use sp_runtime::{FixedPointNumber, FixedU128};
+use xpallet_support::traits::PriceOracle;
+
+impl<T: Config> Pallet<T> {
+    /// Returns the accumulated price at block `at`.
+    fn cumulative_at(
+        accumulator: &PriceAccumulator<T::Price, T::BlockNumber>,
+        at: T::BlockNumber,
+    ) -> u128 {
+        let elapsed: u128 = at.saturating_sub(accumulator.last_updated).saturated_into();
+        accumulator.cumulative.saturating_add(
+            accumulator
+                .latest_price
+                .saturated_into::<u128>()
+                .saturating_mul(elapsed),
+        )
+    }
+
+    /// Accumulates the previous price over the blocks since the last update,
+    /// then records the latest price.
+    ///
+    /// The latest price takes effect since the next block, so that the TWAP
+    /// can not be manipulated by the trades within a block.
+    pub(crate) fn accumulate_price(pair_id: TradingPairId, latest: T::Price) {
+        let current_block = <frame_system::Pallet<T>>::block_number();
+
+        let cumulative = Self::price_accumulator_of(pair_id)
+            .map(|accumulator| Self::cumulative_at(&accumulator, current_block))
+            .unwrap_or_default();
+        PriceAccumulatorOf::<T>::insert(
+            pair_id,
+            PriceAccumulator {
+                cumulative,
+                latest_price: latest,
+                last_updated: current_block,
+            },
+        );
+
+        let window = T::TwapWindow::get();
+        let interval = cmp::max(window / MAX_PRICE_OBSERVATIONS.into(), One::one());
+        PriceObservationsOf::<T>::mutate(pair_id, |observations| {
+            if matches!(observations.last(), Some((at, _)) if current_block < *at + interval) {
+                return;
+            }
+            observations.push((current_block, cumulative));
+
+            let window_start = current_block.saturating_sub(window);
+            let expired = observations
+                .iter()
+                .take_while(|(at, _)| *at <= window_start)
+                .count();
+            if expired > 1 {
+                observations.drain(..expired - 1);
+            }
+        });
+    }
+
+    /// Returns the time-weighted average price of a trading pair over the last
+    /// `TwapWindow` blocks, or since the first trade if the pair is younger.
+    pub fn twap_of(pair_id: TradingPairId) -> Option<T::Price> {
+        let accumulator = Self::price_accumulator_of(pair_id)?;
+        let current_block = <frame_system::Pallet<T>>::block_number();
+        let window_start = current_block.saturating_sub(T::TwapWindow::get());
+
+        let observations = Self::price_observations_of(pair_id);
+        let (start, start_cumulative) = observations
+            .iter()
+            .rev()
+            .find(|(at, _)| *at <= window_start)
+            .or_else(|| observations.first())
+            .copied()?;
+
+        let elapsed: u128 = current_block.saturating_sub(start).saturated_into();
+        if elapsed.is_zero() {
+            return None;
+        }
+
+        let cumulative = Self::cumulative_at(&accumulator, current_block);
+        Some((cumulative.saturating_sub(start_cumulative) / elapsed).saturated_into())
+    }
+
+    /// Returns the TWAP of a trading pair measured by the smallest units of both currencies,
+    /// i.e., how many smallest units of the quote currency one smallest unit of the base
+    /// currency is worth.
+    fn normalized_twap_of(pair: &TradingPairProfile) -> Option<FixedU128> {
+        let price = Self::twap_of(pair.id)?.saturated_into::<u128>();
+        let base_p = u32::from(Self::currency_decimals_of(pair.base())?);
+        let quote_p = u32::from(Self::currency_decimals_of(pair.quote())?);
+
+        if quote_p >= base_p + pair.pip_decimals {
+            let exp = 10_u128.checked_pow(quote_p - base_p - pair.pip_decimals)?;
+            FixedU128::checked_from_integer(price.checked_mul(exp)?)
+        } else {
+            let exp = 10_u128.checked_pow(base_p + pair.pip_decimals - quote_p)?;
+            FixedU128::checked_from_rational(price, exp)
+        }
+    }
+}
+
+impl<T: Config> PriceOracle<TradingPairId, T::Price> for Pallet<T> {
+    fn twap(pair_id: TradingPairId) -> Option<T::Price> {
+        Self::twap_of(pair_id)
+    }
+}
+
+/// The price of `base` measured by `quote` in their smallest units, the trading pair
+/// of the inverse currency pair is used if `base/quote` does not exist.
+impl<T: Config> PriceOracle<(AssetId, AssetId), FixedU128> for Pallet<T> {
+    fn twap((base, quote): (AssetId, AssetId)) -> Option<FixedU128> {
+        match Self::get_trading_pair_by_currency_pair(&CurrencyPair::new(base, quote)) {
+            Some(pair) => Self::normalized_twap_of(&pair),
+            None => {
+                let pair =
+                    Self::get_trading_pair_by_currency_pair(&CurrencyPair::new(quote, base))?;
+                Self::normalized_twap_of(&pair)?.reciprocal()
+            }
+        }
+    }
+}
@@ -20,6 +20,8 @@ pub struct FullPairInfo<Price, BlockNumber> {
     pub max_valid_bid: Price,
     /// The minimum valid ask price.
     pub min_valid_ask: Price,
+    /// The listing status of the trading pair.
+    pub status: TradingPairStatus,
 }
 
 /// Details of an order.
@@ -91,13 +93,14 @@ impl<T: Config> Pallet<T> {
                     pair_info,
                     max_valid_bid,
                     min_valid_ask,
+                    status: Self::trading_pair_status_of(pair_id),
                 });
             }
         }
         pairs
     }
 
-    /// Get the orders of an account.
+    /// Get the open orders of an account.
     ///
     /// The returned data will be empty if `page_index` is invalid.
     ///
@@ -107,7 +110,10 @@ impl<T: Config> Pallet<T> {
         page_index: u32,
         page_size: u32,
     ) -> Vec<RpcOrder<TradingPairId, T::AccountId, BalanceOf<T>, T::Price, T::BlockNumber>> {
-        OrderInfoOf::<T>::iter_prefix_values(who)
+        OpenOrdersOf::<T>::iter_key_prefix(&who)
+            .skip((page_index * page_size) as usize)
+            .take(page_size as usize)
+            .filter_map(|order_id| OrderInfoOf::<T>::get(&who, order_id))
             .flat_map(|order| {
                 Self::trading_pair(order.pair_id())
                     .ok()
@@ -130,11 +136,55 @@ impl<T: Config> Pallet<T> {
                         last_update_at: order.last_update_at,
                     })
             })
-            .skip((page_index * page_size) as usize)
-            .take(page_size as usize)
             .collect()
     }
 
+    /// Get the most recent `count` trades of a trading pair, the latest one comes first.
+    pub fn recent_trades(
+        pair_id: TradingPairId,
+        count: u32,
+    ) -> Vec<TradeRecord<BalanceOf<T>, T::Price, T::BlockNumber>> {
+        let max_trades = T::MaxRecentTrades::get();
+        let next_idx = Self::trading_history_index_of(pair_id);
+        let count = cmp::min(u64::from(cmp::min(count, max_trades)), next_idx);
+        (1..=count)
+            .map(|i| next_idx - i)
+            .filter_map(|idx| {
+                RecentTradesOf::<T>::get(pair_id, Self::ring_slot(idx, max_trades))
+                    .filter(|trade| trade.trading_history_idx == idx)
+            })
+            .collect()
+    }
+
+    /// Get the candles of a trading pair in the most recent `count` periods
+    /// given the interval, the oldest one comes first.
+    ///
+    /// The periods without any trade are skipped.
+    pub fn candles(
+        pair_id: TradingPairId,
+        interval: T::BlockNumber,
+        count: u32,
+    ) -> Vec<Candle<T::Price, BalanceOf<T>, T::BlockNumber>> {
+        if interval.is_zero() || !T::CandleIntervals::get().contains(&interval) {
+            return Vec::new();
+        }
+        let max_candles = T::MaxCandles::get();
+        let current_block = <frame_system::Pallet<T>>::block_number();
+        let current_period = Self::candle_period(current_block, interval);
+        let count = cmp::min(u64::from(cmp::min(count, max_candles)), current_period + 1);
+        let mut candles = (0..count)
+            .map(|i| current_period - i)
+            .filter_map(|period| {
+                let start: T::BlockNumber =
+                    (period * interval.saturated_into::<u64>()).saturated_into();
+                CandlesOf::<T>::get((pair_id, interval), Self::ring_slot(period, max_candles))
+                    .filter(|candle| candle.start == start)
+            })
+            .collect::<Vec<_>>();
+        candles.reverse();
+        candles
+    }
+
     /// Returns the sum of unfilled quantities at `price` of a trading pair `pair_id`.
     fn get_commulative_qty(pair_id: TradingPairId, price: T::Price) -> u128 {
         QuotationsOf::<T>::get(pair_id, price)
@@ -188,6 +238,86 @@ impl<T: Config> Pallet<T> {
             Depth { asks, bids }
         })
     }
+
+    /// Get the depth of a trading pair with the price levels aggregated into
+    /// buckets of `bucket_ticks` ticks, at most `max_levels` buckets for each side.
+    ///
+    /// The asks are rounded up and the bids are rounded down to the bucket boundary,
+    /// the buckets closest to the handicap come first. Only the price levels within the
+    /// valid quotation range are counted, the same as `depth`.
+    pub fn aggregated_depth(
+        pair_id: TradingPairId,
+        bucket_ticks: u32,
+        max_levels: u32,
+    ) -> Option<Depth<T::Price, BalanceOf<T>>> {
+        let pair = Self::trading_pair_of(pair_id)?;
+        let bucket = pair
+            .tick()
+            .saturating_mul(u64::from(cmp::max(bucket_ticks, 1)))
+            .saturated_into::<u128>();
+
+        let (min_valid_ask, max_valid_bid) = Self::get_quotation_range(&pair);
+        let Handicap {
+            lowest_ask,
+            highest_bid,
+        } = Self::handicap_of(pair_id);
+        let step = pair.tick().saturated_into::<u128>();
+
+        let lowest_ask = lowest_ask.saturated_into::<u128>();
+        let max_valid_bid = max_valid_bid.saturated_into::<u128>();
+        let asks = (0..)
+            .map(|x| lowest_ask + step * x)
+            .take_while(|&price| price <= max_valid_bid);
+        let highest_bid = highest_bid.saturated_into::<u128>();
+        let min_valid_ask = min_valid_ask.saturated_into::<u128>();
+        let bids = (0..)
+            .map_while(|x| highest_bid.checked_sub(step * x))
+            .take_while(|&price| price >= min_valid_ask);
+
+        Some(Depth {
+            asks: Self::aggregate_levels(
+                pair_id,
+                asks,
+                |price| (price + bucket - 1) / bucket * bucket,
+                max_levels,
+            ),
+            bids: Self::aggregate_levels(
+                pair_id,
+                bids,
+                |price| price / bucket * bucket,
+                max_levels,
+            ),
+        })
+    }
+
+    /// Aggregates the quantities at `prices` into the buckets given by `to_bucket`,
+    /// stops once `max_levels` buckets are collected.
+    ///
+    /// `prices` must be ordered from the handicap outward.
+    fn aggregate_levels(
+        pair_id: TradingPairId,
+        prices: impl Iterator<Item = u128>,
+        to_bucket: impl Fn(u128) -> u128,
+        max_levels: u32,
+    ) -> Vec<(T::Price, BalanceOf<T>)> {
+        let mut levels = Vec::<(u128, u128)>::new();
+        for price in prices {
+            let qty = Self::get_commulative_qty(pair_id, price.saturated_into());
+            if qty.is_zero() {
+                continue;
+            }
+            let boundary = to_bucket(price);
+            match levels.last_mut() {
+                Some((last, level)) if *last == boundary => *level = level.saturating_add(qty),
+                _ if levels.len() >= max_levels as usize => break,
+                _ => levels.push((boundary, qty)),
+            }
+        }
+        levels
+            .into_iter()
+            .map(|(price, qty)| (price.saturated_into(), qty.saturated_into()))
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -219,4 +349,44 @@ mod rpc_tests {
             });
         });
     }
+
+    #[test]
+    fn rpc_aggregated_depth_and_open_orders_should_work() {
+        ExtBuilder::default().build_and_execute(|| {
+            let pair_id = 0;
+            let who = 1;
+
+            t_set_handicap(pair_id, 1_000_000, 1_100_000);
+
+            t_issue_pcx(who, 1000);
+            assert_ok!(t_put_order_sell(who, pair_id, 100, 1_210_000));
+            assert_ok!(t_put_order_sell(who, pair_id, 100, 1_109_000));
+            assert_ok!(t_put_order_sell(who, pair_id, 200, 1_108_000));
+
+            // The asks are rounded up to the boundary of 50 ticks,
+            // the order out of the valid quotation range is not counted in.
+            assert_eq!(
+                XSpot::aggregated_depth(pair_id, 50, 10).unwrap(),
+                Depth {
+                    asks: vec![(1_110_000, 300)],
+                    bids: vec![],
+                }
+            );
+            assert_eq!(
+                XSpot::aggregated_depth(pair_id, 1, 1).unwrap().asks,
+                vec![(1_108_000, 200)]
+            );
+            assert_eq!(XSpot::aggregated_depth(3, 50, 10), None);
+
+            assert_eq!(OpenOrdersOf::<Test>::iter_prefix(who).count(), 3);
+            assert_ok!(XSpot::cancel_order(Origin::signed(who), pair_id, 1));
+            assert_eq!(OpenOrdersOf::<Test>::get(who, 1), None);
+            let mut open_orders = XSpot::orders(who, 0, 10)
+                .into_iter()
+                .map(|order| order.props.id)
+                .collect::<Vec<_>>();
+            open_orders.sort_unstable();
+            assert_eq!(open_orders, vec![0, 2]);
+        });
+    }
 }
@@ -5,6 +5,7 @@
 use sp_std::collections::btree_map::BTreeMap;
 
 use frame_support::{assert_noop, assert_ok};
+use xp_protocol::{PCX, X_BTC};
 use xpallet_assets::AssetType;
 
 use super::mock::*;
@@ -69,6 +70,42 @@ pub(crate) fn t_put_order_sell(
     )
 }
 
+fn t_put_market_order(
+    who: AccountId,
+    pair_idx: TradingPairId,
+    side: Side,
+    amount: Balance,
+    worst_price: Price,
+) -> DispatchResult {
+    XSpot::put_order(
+        Origin::signed(who),
+        pair_idx,
+        OrderType::Market,
+        side,
+        amount,
+        worst_price,
+    )
+}
+
+fn t_put_order_with_tif(
+    who: AccountId,
+    pair_idx: TradingPairId,
+    side: Side,
+    amount: Balance,
+    price: Price,
+    time_in_force: TimeInForce<u64>,
+) -> DispatchResult {
+    XSpot::put_order_with_time_in_force(
+        Origin::signed(who),
+        pair_idx,
+        OrderType::Limit,
+        side,
+        amount,
+        price,
+        time_in_force,
+    )
+}
+
 fn t_cancel_order(who: AccountId, pair_id: TradingPairId, order_id: OrderId) -> DispatchResult {
     XSpot::cancel_order(Origin::signed(who), pair_id, order_id)
 }
@@ -507,3 +544,951 @@ fn quotations_order_should_be_preserved_when_removing_orders_and_quotations() {
         assert_eq!(XSpot::quotations_of(0, 2_000_000), [(2, 1), (5, 0), (6, 0)]);
     })
 }
+
+#[test]
+fn market_order_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let trading_pair = XSpot::trading_pair_of(0).unwrap();
+        let (base, quote) = (trading_pair.base(), trading_pair.quote());
+
+        t_issue_pcx(1, 10_000);
+        t_issue_pcx(2, 10_000);
+        t_generic_issue(quote, 3, 100);
+
+        // No counterparty yet.
+        assert_noop!(
+            t_put_market_order(3, 0, Side::Buy, 1_000, 2_000_000),
+            Error::<Test>::MarketOrderNotMatched
+        );
+
+        assert_ok!(t_put_order_sell(1, 0, 1_000, 1_000_000));
+        assert_ok!(t_put_order_sell(2, 0, 2_000, 1_000_100));
+        assert_ok!(t_put_order_sell(2, 0, 3_000, 1_500_000));
+
+        // The worst price is lower than the lowest ask.
+        assert_noop!(
+            t_put_market_order(3, 0, Side::Buy, 1_000, 900_000),
+            Error::<Test>::MarketOrderNotMatched
+        );
+
+        // Sweep [1_000_000, 1_000_100], the order at 1_500_000 is out of the worst price.
+        assert_ok!(t_put_market_order(3, 0, Side::Buy, 5_000, 1_200_000));
+
+        assert_eq!(XSpot::order_info_of(1, 0), None);
+        assert_eq!(XSpot::order_info_of(2, 0), None);
+        assert_eq!(XSpot::order_info_of(3, 0), None);
+        assert_eq!(XSpot::quotations_of(0, 1_010_000), vec![]);
+        assert_eq!(XSpot::quotations_of(0, 1_500_000), vec![(2, 1)]);
+        assert_eq!(XSpot::handicap_of(0).highest_bid, 0);
+
+        assert_eq!(t_generic_free_balance(3, base), 3_000);
+        // The unfilled quote currency is refunded.
+        let cost = t_convert_base_to_quote(1_000, 1_000_000, &trading_pair)
+            + t_convert_base_to_quote(2_000, 1_000_100, &trading_pair);
+        assert_eq!(t_generic_free_balance(3, quote), 100 - cost);
+        assert_eq!(
+            XAssets::asset_balance_of(&3, &quote, AssetType::ReservedDexSpot),
+            0
+        );
+    })
+}
+
+#[test]
+fn market_sell_order_should_respect_worst_price() {
+    ExtBuilder::default().build_and_execute(|| {
+        let trading_pair = XSpot::trading_pair_of(0).unwrap();
+        let quote = trading_pair.quote();
+
+        t_generic_issue(quote, 1, 100);
+        t_issue_pcx(2, 10_000);
+
+        assert_ok!(t_put_order_buy(1, 0, 1_000, 1_000_000));
+        assert_ok!(t_put_order_buy(1, 0, 2_000, 900_000));
+
+        assert_ok!(t_put_market_order(2, 0, Side::Sell, 3_000, 950_000));
+
+        assert_eq!(XSpot::order_info_of(1, 0), None);
+        assert_eq!(XSpot::order_info_of(1, 1).unwrap().already_filled, 0);
+        assert_eq!(XSpot::order_info_of(2, 0), None);
+        assert_eq!(XSpot::quotations_of(0, 990_000), vec![]);
+
+        // 1_000 sold, 2_000 refunded.
+        assert_eq!(Balances::free_balance(2), 9_000);
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert_eq!(XSpot::native_reserves(&2), 0);
+    })
+}
+
+#[test]
+fn immediate_or_cancel_order_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let trading_pair = XSpot::trading_pair_of(0).unwrap();
+        let quote = trading_pair.quote();
+
+        t_issue_pcx(1, 10_000);
+        t_generic_issue(quote, 2, 100);
+
+        assert_ok!(t_put_order_sell(1, 0, 1_000, 1_000_000));
+
+        // Not matched at all, canceled.
+        assert_ok!(t_put_order_with_tif(
+            2,
+            0,
+            Side::Buy,
+            2_000,
+            900_000,
+            TimeInForce::ImmediateOrCancel
+        ));
+        assert_eq!(XSpot::order_info_of(2, 0), None);
+        assert_eq!(XSpot::quotations_of(0, 900_000), vec![]);
+        assert_eq!(XSpot::handicap_of(0).highest_bid, 0);
+        assert_eq!(t_generic_free_balance(2, quote), 100);
+
+        // Partially matched, the rest is canceled.
+        assert_ok!(t_put_order_with_tif(
+            2,
+            0,
+            Side::Buy,
+            3_000,
+            1_000_000,
+            TimeInForce::ImmediateOrCancel
+        ));
+        assert_eq!(XSpot::order_info_of(2, 1), None);
+        assert_eq!(XSpot::quotations_of(0, 1_000_000), vec![]);
+        assert_eq!(t_generic_free_balance(2, trading_pair.base()), 1_000);
+        assert_eq!(t_generic_free_balance(2, quote), 99);
+    })
+}
+
+#[test]
+fn fill_or_kill_order_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let trading_pair = XSpot::trading_pair_of(0).unwrap();
+        let quote = trading_pair.quote();
+
+        t_issue_pcx(1, 10_000);
+        t_generic_issue(quote, 2, 100);
+
+        assert_ok!(t_put_order_sell(1, 0, 1_000, 1_000_000));
+        assert_ok!(t_put_order_sell(1, 0, 1_000, 1_000_100));
+
+        assert_noop!(
+            t_put_order_with_tif(2, 0, Side::Buy, 3_000, 1_000_100, TimeInForce::FillOrKill),
+            Error::<Test>::FillOrKillNotFilled
+        );
+        assert_noop!(
+            t_put_order_with_tif(2, 0, Side::Buy, 2_000, 1_000_000, TimeInForce::FillOrKill),
+            Error::<Test>::FillOrKillNotFilled
+        );
+
+        assert_ok!(t_put_order_with_tif(
+            2,
+            0,
+            Side::Buy,
+            2_000,
+            1_000_100,
+            TimeInForce::FillOrKill
+        ));
+        assert_eq!(XSpot::order_info_of(2, 0), None);
+        assert_eq!(t_generic_free_balance(2, trading_pair.base()), 2_000);
+    })
+}
+
+#[test]
+fn post_only_order_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let trading_pair = XSpot::trading_pair_of(0).unwrap();
+
+        t_issue_pcx(1, 10_000);
+        t_generic_issue(trading_pair.quote(), 2, 100);
+
+        assert_ok!(t_put_order_sell(1, 0, 1_000, 1_000_000));
+
+        assert_noop!(
+            t_put_order_with_tif(2, 0, Side::Buy, 1_000, 1_000_000, TimeInForce::PostOnly),
+            Error::<Test>::PostOnlyOrderWouldMatch
+        );
+
+        assert_ok!(t_put_order_with_tif(
+            2,
+            0,
+            Side::Buy,
+            2_000,
+            999_900,
+            TimeInForce::PostOnly
+        ));
+        assert_eq!(XSpot::quotations_of(0, 999_900), vec![(2, 0)]);
+        assert_eq!(XSpot::handicap_of(0).highest_bid, 999_900);
+
+        assert_noop!(
+            XSpot::put_order_with_time_in_force(
+                Origin::signed(2),
+                0,
+                OrderType::Market,
+                Side::Buy,
+                1_000,
+                1_000_000,
+                TimeInForce::PostOnly
+            ),
+            Error::<Test>::InvalidOrderType
+        );
+    })
+}
+
+#[test]
+fn good_till_block_order_should_expire() {
+    use frame_support::traits::Hooks;
+
+    ExtBuilder::default().build_and_execute(|| {
+        let trading_pair = XSpot::trading_pair_of(0).unwrap();
+        let quote = trading_pair.quote();
+
+        t_generic_issue(quote, 2, 100);
+
+        assert_noop!(
+            t_put_order_with_tif(
+                2,
+                0,
+                Side::Buy,
+                1_000,
+                1_000_000,
+                TimeInForce::GoodTillBlock(1)
+            ),
+            Error::<Test>::InvalidExpiry
+        );
+
+        assert_ok!(t_put_order_with_tif(
+            2,
+            0,
+            Side::Buy,
+            1_000,
+            1_000_000,
+            TimeInForce::GoodTillBlock(3)
+        ));
+        assert_ok!(t_put_order_with_tif(
+            2,
+            0,
+            Side::Buy,
+            2_000,
+            1_000_000,
+            TimeInForce::GoodTillBlock(3)
+        ));
+        assert_ok!(t_cancel_order(2, 0, 1));
+        assert_eq!(XSpot::order_expiries_of(3), vec![(2, 0), (2, 1)]);
+        assert_eq!(t_generic_free_balance(2, quote), 99);
+
+        XSpot::on_initialize(2);
+        assert!(XSpot::order_info_of(2, 0).is_some());
+
+        System::set_block_number(3);
+        XSpot::on_initialize(3);
+        assert_eq!(XSpot::order_info_of(2, 0), None);
+        assert_eq!(XSpot::order_expiries_of(3), vec![]);
+        assert_eq!(XSpot::quotations_of(0, 1_000_000), vec![]);
+        assert_eq!(t_generic_free_balance(2, quote), 100);
+    })
+}
+
+#[test]
+fn set_trading_fee_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let fee = TradingFee::new(Permill::from_parts(1_000), Permill::from_parts(2_000));
+
+        assert_noop!(
+            XSpot::set_trading_fee(Origin::signed(1), 0, fee),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            XSpot::set_trading_fee(Origin::root(), 2, fee),
+            Error::<Test>::InvalidTradingPair
+        );
+        assert_noop!(
+            XSpot::set_trading_fee(
+                Origin::root(),
+                0,
+                TradingFee::new(Permill::from_percent(11), Permill::zero())
+            ),
+            Error::<Test>::InvalidTradingFee
+        );
+
+        assert_ok!(XSpot::set_trading_fee(Origin::root(), 0, fee));
+        assert_eq!(XSpot::trading_fee_of(0), fee);
+        assert_eq!(XSpot::trading_fee_of(1), TradingFee::default());
+    })
+}
+
+#[test]
+fn trading_fee_should_go_to_treasury() {
+    ExtBuilder::default().build_and_execute(|| {
+        let trading_pair = XSpot::trading_pair_of(0).unwrap();
+        let (base, quote) = (trading_pair.base(), trading_pair.quote());
+
+        let fee = TradingFee::new(Permill::from_parts(1_000), Permill::from_parts(2_000));
+        assert_ok!(XSpot::set_trading_fee(Origin::root(), 0, fee));
+
+        t_issue_pcx(1, 10_000_000);
+        t_generic_issue(quote, 2, 10_000);
+
+        // Maker sells 10_000_000 PCX for 10_000 quote currency.
+        assert_ok!(t_put_order_sell(1, 0, 10_000_000, 1_000_000));
+        assert_ok!(t_put_order_buy(2, 0, 10_000_000, 1_000_000));
+
+        // The maker pays 0.1% in the quote currency, the taker pays 0.2% in PCX.
+        assert_eq!(t_generic_free_balance(1, base), 0);
+        assert_eq!(t_generic_free_balance(1, quote), 9_990);
+        assert_eq!(t_generic_free_balance(2, base), 9_980_000);
+        assert_eq!(t_generic_free_balance(2, quote), 0);
+        assert_eq!(t_generic_free_balance(TREASURY_ACCOUNT, base), 20_000);
+        assert_eq!(t_generic_free_balance(TREASURY_ACCOUNT, quote), 10);
+    })
+}
+
+#[test]
+fn recent_trades_and_candles_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let quote = t_trading_pair_of(0).quote();
+
+        t_issue_pcx(1, 100_000);
+        t_generic_issue(quote, 2, 100);
+
+        let trade_at = |block_number: u64, amount: Balance, price: Price| {
+            System::set_block_number(block_number);
+            assert_ok!(t_put_order_sell(1, 0, amount, price));
+            assert_ok!(t_put_order_buy(2, 0, amount, price));
+        };
+
+        trade_at(5, 1_000, 1_000_000);
+        trade_at(7, 2_000, 1_100_000);
+        trade_at(12, 3_000, 900_000);
+        trade_at(13, 1_000, 1_000_000);
+
+        // Only the latest `MaxRecentTrades` trades are kept.
+        let trades = XSpot::recent_trades(0, 10);
+        assert_eq!(
+            trades
+                .iter()
+                .map(|t| (t.trading_history_idx, t.price, t.turnover, t.executed_at))
+                .collect::<Vec<_>>(),
+            vec![
+                (3, 1_000_000, 1_000, 13),
+                (2, 900_000, 3_000, 12),
+                (1, 1_100_000, 2_000, 7)
+            ]
+        );
+        assert!(trades.iter().all(|t| t.taker_side == Side::Buy));
+        assert_eq!(XSpot::recent_trades(0, 1).len(), 1);
+        assert!(XSpot::recent_trades(1, 10).is_empty());
+
+        assert_eq!(
+            XSpot::candles(0, 10, 10),
+            vec![
+                Candle {
+                    start: 0,
+                    open: 1_000_000,
+                    high: 1_100_000,
+                    low: 1_000_000,
+                    close: 1_100_000,
+                    volume: 3_000,
+                },
+                Candle {
+                    start: 10,
+                    open: 900_000,
+                    high: 1_000_000,
+                    low: 900_000,
+                    close: 1_000_000,
+                    volume: 4_000,
+                }
+            ]
+        );
+        assert_eq!(
+            XSpot::candles(0, 100, 10),
+            vec![Candle {
+                start: 0,
+                open: 1_000_000,
+                high: 1_100_000,
+                low: 900_000,
+                close: 1_000_000,
+                volume: 7_000,
+            }]
+        );
+        // Not a configured interval.
+        assert!(XSpot::candles(0, 20, 10).is_empty());
+
+        // The slot of the candle starting at block 0 is reused by the one starting at block 50.
+        trade_at(50, 1_000, 1_000_000);
+        assert_eq!(
+            XSpot::candles(0, 10, 10)
+                .iter()
+                .map(|c| c.start)
+                .collect::<Vec<_>>(),
+            vec![10, 50]
+        );
+    })
+}
+
+fn t_put_conditional_order(
+    who: AccountId,
+    pair_idx: TradingPairId,
+    kind: ConditionalKind,
+    trigger_price: Price,
+    side: Side,
+    amount: Balance,
+    price: Price,
+) -> DispatchResult {
+    XSpot::put_conditional_order(
+        Origin::signed(who),
+        pair_idx,
+        kind,
+        trigger_price,
+        OrderType::Limit,
+        side,
+        amount,
+        price,
+    )
+}
+
+#[test]
+fn stop_loss_order_should_be_placed_once_triggered() {
+    use frame_support::traits::Hooks;
+
+    ExtBuilder::default().build_and_execute(|| {
+        let trading_pair = XSpot::trading_pair_of(0).unwrap();
+        let quote = trading_pair.quote();
+
+        t_issue_pcx(1, 100_000);
+        t_generic_issue(quote, 2, 1_000);
+        t_issue_pcx(3, 10_010);
+
+        // The latest price becomes 1_000_000.
+        assert_ok!(t_put_order_sell(1, 0, 10_000, 1_000_000));
+        assert_ok!(t_put_order_buy(2, 0, 10_000, 1_000_000));
+
+        assert_noop!(
+            t_put_conditional_order(
+                3,
+                0,
+                ConditionalKind::StopLoss,
+                1_000_000,
+                Side::Sell,
+                10_000,
+                950_000
+            ),
+            Error::<Test>::ConditionalOrderAlreadyTriggered
+        );
+        assert_ok!(t_put_conditional_order(
+            3,
+            0,
+            ConditionalKind::StopLoss,
+            950_000,
+            Side::Sell,
+            10_000,
+            950_000
+        ));
+        assert_eq!(XSpot::native_reserves(&3), 10_000);
+        assert_eq!(Balances::reserved_balance(3), 10_010);
+        assert_eq!(XSpot::conditional_triggers_of(0).len(), 1);
+
+        // The latest price falls to 950_000.
+        assert_ok!(t_put_order_buy(2, 0, 10_000, 950_000));
+        assert_ok!(t_put_order_sell(1, 0, 10_000, 950_000));
+        assert!(XSpot::conditional_triggers_of(0).is_empty());
+        assert_eq!(XSpot::triggered_orders(), vec![(3, 0)]);
+
+        // The counterparty of the stop-loss order.
+        assert_ok!(t_put_order_buy(2, 0, 10_000, 950_000));
+
+        System::set_block_number(2);
+        XSpot::on_initialize(2);
+
+        assert!(XSpot::triggered_orders().is_empty());
+        assert_eq!(XSpot::conditional_order_of(3, 0), None);
+        assert_eq!(XSpot::order_info_of(3, 0), None);
+        assert_eq!(XSpot::native_reserves(&3), 0);
+        assert_eq!(Balances::free_balance(3), 10);
+        assert_eq!(
+            t_generic_free_balance(3, quote),
+            t_convert_base_to_quote(10_000, 950_000, &trading_pair)
+        );
+    })
+}
+
+#[test]
+fn cancel_conditional_order_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let quote = t_trading_pair_of(0).quote();
+
+        t_generic_issue(quote, 1, 100);
+        t_issue_pcx(1, 10);
+
+        // The genesis latest price is 100_000.
+        assert_ok!(t_put_conditional_order(
+            1,
+            0,
+            ConditionalKind::StopLoss,
+            1_000_000,
+            Side::Buy,
+            10_000,
+            1_000_000
+        ));
+        assert_eq!(t_generic_free_balance(1, quote), 90);
+        assert_eq!(Balances::free_balance(1), 0);
+
+        assert_noop!(
+            XSpot::cancel_conditional_order(Origin::signed(2), 0, 0),
+            Error::<Test>::InvalidOrderId
+        );
+        assert_ok!(XSpot::cancel_conditional_order(Origin::signed(1), 0, 0));
+        assert_eq!(t_generic_free_balance(1, quote), 100);
+        assert_eq!(Balances::free_balance(1), 10);
+        assert_eq!(XSpot::conditional_order_of(1, 0), None);
+        assert!(XSpot::conditional_triggers_of(0).is_empty());
+    })
+}
+
+#[test]
+fn put_conditional_order_should_be_limited() {
+    ExtBuilder::default().build_and_execute(|| {
+        let quote = t_trading_pair_of(0).quote();
+
+        t_generic_issue(quote, 1, 1_000);
+        t_issue_pcx(1, 1_000);
+
+        for _ in 0..MAX_CONDITIONAL_ORDERS_PER_ACCOUNT {
+            assert_ok!(t_put_conditional_order(
+                1,
+                0,
+                ConditionalKind::StopLoss,
+                1_000_000,
+                Side::Buy,
+                10_000,
+                1_000_000
+            ));
+        }
+        assert_noop!(
+            t_put_conditional_order(
+                1,
+                0,
+                ConditionalKind::StopLoss,
+                1_000_000,
+                Side::Buy,
+                10_000,
+                1_000_000
+            ),
+            Error::<Test>::TooManyAccountConditionalOrders
+        );
+
+        // No stop order is accepted before the trading pair has a price.
+        TradingPairInfoOf::<Test>::remove(0);
+        assert_noop!(
+            t_put_conditional_order(
+                2,
+                0,
+                ConditionalKind::StopLoss,
+                1_000_000,
+                Side::Sell,
+                10_000,
+                1_000_000
+            ),
+            Error::<Test>::LatestPriceNotFound
+        );
+    })
+}
+
+#[test]
+fn put_orders_should_be_atomic() {
+    ExtBuilder::default().build_and_execute(|| {
+        t_issue_pcx(1, 1_000);
+
+        assert_noop!(
+            XSpot::put_orders(
+                Origin::signed(1),
+                0,
+                vec![
+                    (OrderType::Limit, Side::Sell, 100, 1_100_000),
+                    (OrderType::Limit, Side::Sell, 100, 0),
+                ]
+            ),
+            Error::<Test>::InvalidPrice
+        );
+        assert_eq!(XSpot::order_count_of(1), 0);
+
+        assert_ok!(XSpot::put_orders(
+            Origin::signed(1),
+            0,
+            vec![
+                (OrderType::Limit, Side::Sell, 100, 1_100_000),
+                (OrderType::Limit, Side::Sell, 200, 1_200_000),
+                (OrderType::Limit, Side::Sell, 300, 1_300_000),
+            ]
+        ));
+        assert_eq!(XSpot::order_count_of(1), 3);
+        assert_eq!(XSpot::native_reserves(&1), 600);
+
+        assert_ok!(XSpot::cancel_orders(Origin::signed(1), 0, vec![0, 2]));
+        assert_eq!(XSpot::native_reserves(&1), 200);
+        assert!(XSpot::order_info_of(1, 1).is_some());
+
+        assert_noop!(
+            XSpot::cancel_orders(Origin::signed(1), 0, vec![1, 2]),
+            Error::<Test>::InvalidOrderId
+        );
+        assert!(XSpot::order_info_of(1, 1).is_some());
+    })
+}
+
+#[test]
+fn cancel_all_orders_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        t_add_trading_pair(CurrencyPair::new(X_BTC, PCX), 2, 1, 100, true);
+
+        t_issue_pcx(1, 1_000);
+        t_generic_issue(X_BTC, 1, 1_000);
+
+        assert_ok!(t_put_order_sell(1, 0, 100, 1_100_000));
+        assert_ok!(t_put_order_sell(1, 0, 200, 1_200_000));
+        assert_ok!(t_put_order_sell(1, 2, 100, 1_000));
+
+        assert_ok!(XSpot::cancel_all_orders(Origin::signed(1), 0));
+        assert_eq!(XSpot::order_info_of(1, 0), None);
+        assert_eq!(XSpot::order_info_of(1, 1), None);
+        // The orders of other trading pairs are untouched.
+        assert!(XSpot::order_info_of(1, 2).is_some());
+    })
+}
+
+#[test]
+fn replace_order_should_keep_the_reserve() {
+    ExtBuilder::default().build_and_execute(|| {
+        t_issue_pcx(1, 1_000);
+
+        assert_ok!(t_put_order_sell(1, 0, 500, 1_100_000));
+        assert_eq!(XSpot::native_reserves(&1), 500);
+
+        // Only the difference is reserved.
+        assert_ok!(XSpot::replace_order(
+            Origin::signed(1),
+            0,
+            0,
+            800,
+            1_200_000
+        ));
+        assert_eq!(XSpot::native_reserves(&1), 800);
+        assert_eq!(Balances::free_balance(1), 200);
+        assert_eq!(XSpot::order_info_of(1, 0), None);
+        assert_eq!(XSpot::quotations_of(0, 1_100_000), vec![]);
+        assert_eq!(XSpot::quotations_of(0, 1_200_000), vec![(1, 1)]);
+
+        assert_noop!(
+            XSpot::replace_order(Origin::signed(1), 0, 1, 1_200, 1_200_000),
+            pallet_balances::Error::<Test>::InsufficientBalance
+        );
+
+        // The surplus is unreserved.
+        assert_ok!(XSpot::replace_order(
+            Origin::signed(1),
+            0,
+            1,
+            300,
+            1_100_000
+        ));
+        assert_eq!(XSpot::native_reserves(&1), 300);
+        assert_eq!(Balances::free_balance(1), 700);
+        assert_eq!(XSpot::order_info_of(1, 2).unwrap().amount(), 300);
+    })
+}
+
+fn t_create_pool(pair_id: TradingPairId) {
+    let share_asset = xpallet_assets::AssetInfo::new::<Test>(
+        b"LP-PCX-XBTC".to_vec(),
+        b"PCX/X-BTC pool share".to_vec(),
+        xpallet_assets::Chain::ChainX,
+        8,
+        b"The liquidity pool share of PCX/X-BTC".to_vec(),
+    )
+    .unwrap();
+    assert_ok!(XSpot::create_pool(
+        Origin::root(),
+        pair_id,
+        share_asset,
+        Permill::from_parts(3_000)
+    ));
+}
+
+#[test]
+fn amm_pool_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let quote = t_trading_pair_of(0).quote();
+        let share_asset_id = XSpot::pool_share_asset_id(0);
+
+        t_create_pool(0);
+        assert_noop!(
+            XSpot::add_liquidity(Origin::signed(1), 1, 100, 100),
+            Error::<Test>::PoolNotFound
+        );
+
+        t_issue_pcx(1, 100_000_000);
+        t_generic_issue(quote, 1, 100_000);
+        assert_ok!(XSpot::add_liquidity(
+            Origin::signed(1),
+            0,
+            100_000_000,
+            100_000
+        ));
+        assert_eq!(
+            XSpot::pool_of(0).unwrap(),
+            Pool {
+                share_asset_id,
+                base_reserve: 100_000_000,
+                quote_reserve: 100_000,
+                total_shares: 3_162_277,
+                fee: Permill::from_parts(3_000),
+            }
+        );
+        // The first `MINIMUM_LIQUIDITY` shares are locked in the pool.
+        assert_eq!(XAssets::usable_balance(&1, &share_asset_id), 3_161_277);
+
+        // Swap 1_000 quote currency for the base currency, 0.3% of which is the fee.
+        t_generic_issue(quote, 2, 1_000);
+        assert_noop!(
+            XSpot::swap(Origin::signed(2), 0, Side::Buy, 1_000, 1_000_000),
+            Error::<Test>::SlippageExceeded
+        );
+        assert_ok!(XSpot::swap(Origin::signed(2), 0, Side::Buy, 1_000, 0));
+        assert_eq!(Balances::free_balance(2), 987_158);
+        assert_eq!(t_generic_free_balance(2, quote), 0);
+
+        // The order book is empty, the sell order is filled by the pool.
+        t_issue_pcx(3, 10_000_000);
+        assert_ok!(t_put_order_sell(3, 0, 10_000_000, 900_000));
+        assert_eq!(XSpot::order_count_of(3), 0);
+        assert_eq!(Balances::free_balance(3), 0);
+        assert_eq!(t_generic_free_balance(3, quote), 9_239);
+        assert_eq!(
+            XSpot::trading_pair_info_of(0).unwrap().latest_price,
+            923_900
+        );
+        assert_eq!(XSpot::recent_trades(0, 1)[0].turnover, 10_000_000);
+
+        let pool = XSpot::pool_of(0).unwrap();
+        assert_eq!(pool.base_reserve, 109_012_842);
+        assert_eq!(pool.quote_reserve, 91_761);
+
+        // The order book offers a better price than the pool.
+        t_issue_pcx(4, 1_000_000);
+        assert_ok!(t_put_order_with_tif(
+            4,
+            0,
+            Side::Sell,
+            1_000_000,
+            800_000,
+            TimeInForce::PostOnly
+        ));
+        t_generic_issue(quote, 5, 1_000);
+        assert_ok!(t_put_order_buy(5, 0, 1_000_000, 900_000));
+        assert_eq!(XSpot::order_info_of(4, 0), None);
+        assert_eq!(Balances::free_balance(5), 1_000_000);
+        assert_eq!(t_generic_free_balance(5, quote), 200);
+        assert_eq!(XSpot::pool_of(0).unwrap(), pool);
+
+        // Withdraw all the liquidity except the locked shares.
+        assert_ok!(XSpot::remove_liquidity(
+            Origin::signed(1),
+            0,
+            3_161_277,
+            0,
+            0
+        ));
+        assert_eq!(Balances::free_balance(1), 108_978_369);
+        assert_eq!(t_generic_free_balance(1, quote), 91_731);
+        assert_eq!(XAssets::usable_balance(&1, &share_asset_id), 0);
+        let pool = XSpot::pool_of(0).unwrap();
+        assert_eq!(pool.total_shares, 1_000);
+        assert_eq!(pool.base_reserve, 34_473);
+        assert_eq!(pool.quote_reserve, 30);
+    })
+}
+
+#[test]
+fn delisted_trading_pair_should_be_settled() {
+    use frame_support::traits::Hooks;
+
+    ExtBuilder::default().build_and_execute(|| {
+        let quote = t_trading_pair_of(0).quote();
+
+        t_issue_pcx(1, 1_000);
+        t_generic_issue(quote, 2, 9);
+        t_generic_issue(quote, 3, 20);
+        t_issue_pcx(3, 10);
+
+        t_create_pool(0);
+        t_issue_pcx(6, 1_000_000);
+        t_generic_issue(quote, 6, 1_000);
+        assert_ok!(XSpot::add_liquidity(Origin::signed(6), 0, 1_000_000, 1_000));
+        assert_eq!(
+            XAssets::usable_balance(&6, &XSpot::pool_share_asset_id(0)),
+            30_622
+        );
+        assert!(PoolShareHoldersOf::<Test>::contains_key(0, 6));
+
+        assert_ok!(t_put_order_buy(2, 0, 10_000, 900_000));
+        assert_ok!(t_put_order_sell(1, 0, 1_000, 1_000_000));
+        assert_ok!(t_put_conditional_order(
+            3,
+            0,
+            ConditionalKind::StopLoss,
+            2_000_000,
+            Side::Buy,
+            10_000,
+            2_000_000
+        ));
+
+        // No new order is accepted in the reduce-only phase.
+        assert_ok!(XSpot::set_trading_pair_status(
+            Origin::root(),
+            0,
+            TradingPairStatus::ReduceOnly
+        ));
+        assert_noop!(
+            t_put_order_sell(1, 0, 1_000, 1_000_000),
+            Error::<Test>::TradingPairNotActive
+        );
+        assert_ok!(XSpot::set_trading_pair_status(
+            Origin::root(),
+            0,
+            TradingPairStatus::Active
+        ));
+
+        assert_ok!(XSpot::set_trading_pair_status(
+            Origin::root(),
+            0,
+            TradingPairStatus::Delisted
+        ));
+        assert!(!t_trading_pair_of(0).tradable);
+        assert_eq!(XSpot::delisted_pairs(), vec![0]);
+        assert_noop!(
+            XSpot::set_trading_pair_status(Origin::root(), 0, TradingPairStatus::Active),
+            Error::<Test>::InvalidTradingPairStatus
+        );
+        assert_noop!(
+            XSpot::update_trading_pair(Origin::root(), 0, 2, true),
+            Error::<Test>::TradingPairDelisted
+        );
+        assert_eq!(XSpot::native_reserves(&1), 1_000);
+        assert_eq!(t_generic_free_balance(2, quote), 0);
+        assert_eq!(t_generic_free_balance(3, quote), 0);
+
+        // The orders of a delisted trading pair can still be canceled before the settlement.
+        assert_ok!(XSpot::cancel_order(Origin::signed(1), 0, 0));
+
+        // All the orders are canceled and refunded.
+        System::set_block_number(2);
+        XSpot::on_initialize(2);
+        assert_eq!(XSpot::order_info_of(1, 0), None);
+        assert_eq!(XSpot::order_info_of(2, 0), None);
+        assert_eq!(XSpot::conditional_order_of(3, 0), None);
+        assert_eq!(XSpot::native_reserves(&1), 0);
+        assert_eq!(Balances::free_balance(1), 1_000);
+        assert_eq!(t_generic_free_balance(2, quote), 9);
+        assert_eq!(t_generic_free_balance(3, quote), 20);
+        assert_eq!(Balances::free_balance(3), 10);
+        assert_eq!(XSpot::delisted_pairs(), vec![0]);
+
+        // The pool shares are redeemed once all the orders are canceled.
+        System::set_block_number(3);
+        XSpot::on_initialize(3);
+        assert!(XSpot::delisted_pairs().is_empty());
+        assert_eq!(XSpot::handicap_of(0), Default::default());
+        assert_eq!(
+            XAssets::usable_balance(&6, &XSpot::pool_share_asset_id(0)),
+            0
+        );
+        assert_eq!(Balances::free_balance(6), 968_376);
+        assert_eq!(t_generic_free_balance(6, quote), 968);
+        assert_eq!(XSpot::pool_of(0).unwrap().total_shares, 1_000);
+        assert_eq!(PoolShareHoldersOf::<Test>::iter_prefix(0).count(), 0);
+    })
+}
+
+#[test]
+fn proposed_trading_pair_should_go_active_once_tradable() {
+    ExtBuilder::default().build_and_execute(|| {
+        t_add_trading_pair(CurrencyPair::new(EOS, ETH), 2, 1, 100, false);
+        let pair_id = XSpot::trading_pair_count() - 1;
+        assert_eq!(
+            XSpot::trading_pair_status_of(pair_id),
+            TradingPairStatus::Proposed
+        );
+        assert_noop!(
+            XSpot::set_trading_pair_status(Origin::root(), pair_id, TradingPairStatus::ReduceOnly),
+            Error::<Test>::InvalidTradingPairStatus
+        );
+
+        assert_ok!(XSpot::update_trading_pair(Origin::root(), pair_id, 1, true));
+        assert_eq!(
+            XSpot::trading_pair_status_of(pair_id),
+            TradingPairStatus::Active
+        );
+    })
+}
+
+#[test]
+fn twap_should_work() {
+    use sp_runtime::{FixedPointNumber, FixedU128};
+    use xpallet_support::traits::PriceOracle;
+
+    ExtBuilder::default().build_and_execute(|| {
+        let quote = t_trading_pair_of(0).quote();
+
+        t_issue_pcx(1, 20_000);
+        t_generic_issue(quote, 2, 30);
+
+        assert_eq!(XSpot::twap_of(0), None);
+
+        System::set_block_number(1);
+        assert_ok!(t_put_order_sell(1, 0, 10_000, 1_000_000));
+        assert_ok!(t_put_order_buy(2, 0, 10_000, 1_000_000));
+        // The price of current block is not taken into account.
+        assert_eq!(XSpot::twap_of(0), None);
+
+        System::set_block_number(5);
+        assert_ok!(t_put_order_sell(1, 0, 10_000, 2_000_000));
+        assert_ok!(t_put_order_buy(2, 0, 10_000, 2_000_000));
+        assert_eq!(
+            XSpot::price_observations_of(0),
+            vec![(1, 0), (5, 4_000_000)]
+        );
+
+        // (1_000_000 * 4 + 2_000_000 * 4) / 8
+        System::set_block_number(9);
+        assert_eq!(XSpot::twap_of(0), Some(1_500_000));
+
+        // The window covers the blocks since 14.
+        System::set_block_number(30);
+        assert_eq!(XSpot::twap_of(0), Some(2_000_000));
+        assert_eq!(
+            <XSpot as PriceOracle<(AssetId, AssetId), FixedU128>>::twap((PCX, X_BTC)),
+            Some(FixedU128::saturating_from_rational(2, 1_000))
+        );
+        assert_eq!(
+            <XSpot as PriceOracle<(AssetId, AssetId), FixedU128>>::twap((X_BTC, PCX)),
+            Some(FixedU128::saturating_from_integer(500))
+        );
+
+        // The trade less than the minimum turnover is not counted in the TWAP.
+        assert_noop!(
+            XSpot::set_min_twap_volume(Origin::root(), 99, 10_001),
+            Error::<Test>::InvalidTradingPair
+        );
+        assert_ok!(XSpot::set_min_twap_volume(Origin::root(), 0, 10_001));
+        t_issue_pcx(1, 10_000);
+        t_generic_issue(quote, 2, 30);
+        assert_ok!(t_put_order_sell(1, 0, 10_000, 3_000_000));
+        assert_ok!(t_put_order_buy(2, 0, 10_000, 3_000_000));
+        assert_eq!(
+            XSpot::trading_pair_info_of(0).unwrap().latest_price,
+            3_000_000
+        );
+        System::set_block_number(40);
+        assert_eq!(XSpot::twap_of(0), Some(2_000_000));
+    })
+}
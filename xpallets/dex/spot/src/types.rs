@@ -8,7 +8,7 @@ use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_arithmetic::traits::BaseArithmetic;
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{Permill, RuntimeDebug};
 
 /// Type for counting the number of user orders.
 pub type OrderId = u64;
@@ -27,7 +27,8 @@ pub type PriceFluctuation = u32;
 
 /// Type of an order.
 ///
-/// Currently only Limit Order is supported.
+/// The market order is filled immediately within the worst price given by
+/// the submitter and never rests on the order book.
 #[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum OrderType {
@@ -41,6 +42,35 @@ impl Default for OrderType {
     }
 }
 
+/// How long an order remains active before it's executed or expired.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TimeInForce<BlockNumber> {
+    /// Rests on the order book until it's filled or canceled.
+    GoodTillCancel,
+    /// Fills as much as possible immediately, the unfilled part is canceled.
+    ImmediateOrCancel,
+    /// Fills completely immediately, or the order is rejected.
+    FillOrKill,
+    /// Only rests on the order book, the order is rejected if it would match immediately.
+    PostOnly,
+    /// Rests on the order book until it's filled, canceled or expired at the given block.
+    GoodTillBlock(BlockNumber),
+}
+
+impl<BlockNumber> Default for TimeInForce<BlockNumber> {
+    fn default() -> Self {
+        Self::GoodTillCancel
+    }
+}
+
+impl<BlockNumber> TimeInForce<BlockNumber> {
+    /// Returns true if the unfilled part of the order can rest on the order book.
+    pub fn rests_on_book(&self) -> bool {
+        !matches!(self, Self::ImmediateOrCancel | Self::FillOrKill)
+    }
+}
+
 /// Direction of an order.
 #[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -218,6 +248,59 @@ impl TradingPairProfile {
     }
 }
 
+/// Listing status of a trading pair.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TradingPairStatus {
+    /// The trading pair has been listed but is not open for trading yet.
+    Proposed,
+    /// The trading pair is open for trading.
+    Active,
+    /// No new order is accepted, the resting orders can still be filled or canceled.
+    ReduceOnly,
+    /// The trading pair has been delisted, all the resting orders are being canceled.
+    Delisted,
+}
+
+impl Default for TradingPairStatus {
+    fn default() -> Self {
+        Self::Active
+    }
+}
+
+impl TradingPairStatus {
+    /// Returns true if the trading pair can move from the current status to `next`.
+    pub fn can_transit_to(&self, next: Self) -> bool {
+        matches!(
+            (self, next),
+            (Self::Proposed, Self::Active)
+                | (Self::Active, Self::ReduceOnly)
+                | (Self::ReduceOnly, Self::Active)
+                | (
+                    Self::Proposed | Self::Active | Self::ReduceOnly,
+                    Self::Delisted
+                )
+        )
+    }
+}
+
+/// The trading fee rates of a trading pair.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TradingFee {
+    /// The fee rate of the maker, charged in the currency the maker receives.
+    pub maker: Permill,
+    /// The fee rate of the taker, charged in the currency the taker receives.
+    pub taker: Permill,
+}
+
+impl TradingFee {
+    pub fn new(maker: Permill, taker: Permill) -> Self {
+        Self { maker, taker }
+    }
+}
+
 /// Immutable information of an order.
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -381,6 +464,63 @@ where
     }
 }
 
+/// Kind of a conditional order.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ConditionalKind {
+    StopLoss,
+    TakeProfit,
+}
+
+impl ConditionalKind {
+    /// Returns true if the latest price has crossed the trigger price.
+    ///
+    /// The stop-loss sell order and the take-profit buy order are triggered when the
+    /// price falls to the trigger price, the other ones are triggered when the price
+    /// rises to the trigger price.
+    pub fn is_triggered<Price: PartialOrd>(
+        &self,
+        side: Side,
+        trigger_price: Price,
+        latest_price: Price,
+    ) -> bool {
+        match (self, side) {
+            (Self::StopLoss, Side::Sell) | (Self::TakeProfit, Side::Buy) => {
+                latest_price <= trigger_price
+            }
+            (Self::StopLoss, Side::Buy) | (Self::TakeProfit, Side::Sell) => {
+                latest_price >= trigger_price
+            }
+        }
+    }
+}
+
+/// An order stays dormant until the latest price of trading pair crosses the trigger price,
+/// then it will be converted into a normal order.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ConditionalOrder<PairId, AccountId, Balance, Price, BlockNumber> {
+    pub id: OrderId,
+    pub pair_id: PairId,
+    pub submitter: AccountId,
+    pub kind: ConditionalKind,
+    pub trigger_price: Price,
+    /// Type of the order to be placed once triggered.
+    pub order_type: OrderType,
+    pub side: Side,
+    /// The amount of order, measured by the **base** currency.
+    pub amount: Balance,
+    /// The limit price, or the worst price of the market order.
+    pub price: Price,
+    /// The locked balance of this order, measured by the **quote** currency
+    /// for the buy order and the **base** currency for the sell order.
+    pub reserved: Balance,
+    /// The native deposit of this order.
+    pub deposit: Balance,
+    pub created_at: BlockNumber,
+}
+
 /// Latest price of a trading pair.
 #[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -392,6 +532,93 @@ pub struct TradingPairInfo<Price, BlockNumber> {
     pub last_updated: BlockNumber,
 }
 
+/// The constant-product liquidity pool of a trading pair.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct Pool<Balance> {
+    /// The asset ID of the liquidity provider share.
+    pub share_asset_id: AssetId,
+    pub base_reserve: Balance,
+    pub quote_reserve: Balance,
+    /// Total shares issued to the liquidity providers.
+    pub total_shares: Balance,
+    /// The fee rate of swapping, which is left in the pool for the liquidity providers.
+    pub fee: Permill,
+}
+
+/// A trade executed in a trading pair.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TradeRecord<Balance, Price, BlockNumber> {
+    /// Index of the trade in the trading history of the pair.
+    pub trading_history_idx: TradingHistoryIndex,
+    /// The execution price.
+    pub price: Price,
+    /// The executed amount, measured by the **base** currency.
+    pub turnover: Balance,
+    /// Direction of the taker order.
+    pub taker_side: Side,
+    /// Block number at which the trade was executed.
+    pub executed_at: BlockNumber,
+}
+
+/// Accumulator of the price of a trading pair weighted by the number of blocks.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PriceAccumulator<Price, BlockNumber> {
+    /// Sum of the price multiplied by the number of blocks it lasted.
+    pub cumulative: u128,
+    /// The latest price.
+    pub latest_price: Price,
+    /// Block number at which the accumulator updated.
+    pub last_updated: BlockNumber,
+}
+
+/// Open, high, low, close prices and the volume of a trading pair in a period of blocks.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct Candle<Price, Balance, BlockNumber> {
+    /// The first block of the period.
+    pub start: BlockNumber,
+    pub open: Price,
+    pub high: Price,
+    pub low: Price,
+    pub close: Price,
+    /// The executed amount in the period, measured by the **base** currency.
+    pub volume: Balance,
+}
+
+impl<Price: Copy + PartialOrd, Balance: BaseArithmetic, BlockNumber>
+    Candle<Price, Balance, BlockNumber>
+{
+    pub fn new(start: BlockNumber, price: Price, volume: Balance) -> Self {
+        Self {
+            start,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume,
+        }
+    }
+
+    /// Updates the candle with a new trade.
+    pub fn update(&mut self, price: Price, volume: Balance) {
+        if price > self.high {
+            self.high = price;
+        }
+        if price < self.low {
+            self.low = price;
+        }
+        self.close = price;
+        self.volume = self.volume.saturating_add(volume);
+    }
+}
+
 /// Information about the executed orders.
 #[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct OrderExecutedInfo<AccountId, Balance, BlockNumber, Price> {
@@ -403,6 +630,10 @@ pub struct OrderExecutedInfo<AccountId, Balance, BlockNumber, Price> {
     maker_order_id: OrderId,
     taker_order_id: OrderId,
     turnover: Balance,
+    /// The fee paid by the maker, measured by the currency the maker receives.
+    maker_fee: Balance,
+    /// The fee paid by the taker, measured by the currency the taker receives.
+    taker_fee: Balance,
     executed_at: BlockNumber,
 }
 
@@ -414,6 +645,7 @@ impl<AccountId: Clone, Balance: Copy + Ord + BaseArithmetic, BlockNumber: Copy,
         pair_id: TradingPairId,
         price: Price,
         turnover: Balance,
+        (maker_fee, taker_fee): (Balance, Balance),
         maker_order: &Order<TradingPairId, AccountId, Balance, Price, BlockNumber>,
         taker_order: &Order<TradingPairId, AccountId, Balance, Price, BlockNumber>,
         executed_at: BlockNumber,
@@ -423,6 +655,8 @@ impl<AccountId: Clone, Balance: Copy + Ord + BaseArithmetic, BlockNumber: Copy,
             pair_id,
             price,
             turnover,
+            maker_fee,
+            taker_fee,
             executed_at,
             maker: maker_order.submitter(),
             taker: taker_order.submitter(),
@@ -38,15 +38,25 @@ pub trait WeightInfo {
     fn set_price_fluctuation() -> Weight;
     fn add_trading_pair() -> Weight;
     fn update_trading_pair() -> Weight;
+    fn set_trading_fee() -> Weight;
+    fn replace_order() -> Weight;
+    fn put_conditional_order() -> Weight;
+    fn cancel_conditional_order() -> Weight;
+    fn create_pool() -> Weight;
+    fn add_liquidity() -> Weight;
+    fn remove_liquidity() -> Weight;
+    fn swap() -> Weight;
+    fn set_trading_pair_status() -> Weight;
+    fn set_min_twap_volume() -> Weight;
 }
 
 /// Weights for xpallet_dex_spot using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     fn put_order() -> Weight {
-        (142_883_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(11 as Weight))
-            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+        (183_517_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(20 as Weight))
+            .saturating_add(T::DbWeight::get().writes(16 as Weight))
     }
     fn cancel_order() -> Weight {
         (133_946_000 as Weight)
@@ -74,14 +84,64 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(1 as Weight))
             .saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn set_trading_fee() -> Weight {
+        (24_180_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_trading_pair_status() -> Weight {
+        (31_562_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn set_min_twap_volume() -> Weight {
+        (21_354_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn replace_order() -> Weight {
+        (172_059_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(19 as Weight))
+            .saturating_add(T::DbWeight::get().writes(18 as Weight))
+    }
+    fn put_conditional_order() -> Weight {
+        (98_760_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(17 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn cancel_conditional_order() -> Weight {
+        (82_410_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn create_pool() -> Weight {
+        (64_532_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn add_liquidity() -> Weight {
+        (156_318_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+    }
+    fn remove_liquidity() -> Weight {
+        (149_027_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+    }
+    fn swap() -> Weight {
+        (162_328_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(19 as Weight))
+            .saturating_add(T::DbWeight::get().writes(19 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
     fn put_order() -> Weight {
-        (142_883_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(11 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+        (183_517_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(20 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(16 as Weight))
     }
     fn cancel_order() -> Weight {
         (133_946_000 as Weight)
@@ -109,4 +169,54 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(1 as Weight))
             .saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn set_trading_fee() -> Weight {
+        (24_180_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_trading_pair_status() -> Weight {
+        (31_562_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn set_min_twap_volume() -> Weight {
+        (21_354_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn replace_order() -> Weight {
+        (172_059_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(19 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(18 as Weight))
+    }
+    fn put_conditional_order() -> Weight {
+        (98_760_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(17 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+    }
+    fn cancel_conditional_order() -> Weight {
+        (82_410_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn create_pool() -> Weight {
+        (64_532_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn add_liquidity() -> Weight {
+        (156_318_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(8 as Weight))
+    }
+    fn remove_liquidity() -> Weight {
+        (149_027_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(8 as Weight))
+    }
+    fn swap() -> Weight {
+        (162_328_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(19 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(19 as Weight))
+    }
 }
@@ -31,6 +31,7 @@ pub use light_bitcoin::{
 use light_bitcoin::{
     chain::Transaction,
     keys::{Address, DisplayLayout},
+    merkle::PartialMerkleTree,
     serialization::{deserialize, Reader},
 };
 
@@ -51,7 +52,7 @@ use self::{
     tx::remove_pending_deposit,
     types::{
         BtcDepositCache, BtcHeaderIndex, BtcHeaderInfo, BtcRelayedTx, BtcRelayedTxInfo,
-        BtcTxResult, BtcTxState,
+        BtcSpvProof, BtcTxOutput, BtcTxResult, BtcTxState,
     },
 };
 
@@ -370,6 +371,8 @@ pub mod pallet {
         TxOutputNotColdAddr,
         /// The total amount of the trust must be transferred out in full
         TxNotFullAmount,
+        /// The header of the block is not relayed
+        HeaderNotExisted,
     }
 
     #[pallet::event]
@@ -903,6 +906,56 @@ pub mod pallet {
         pub fn get_btc_block_header(txid: H256) -> Option<BtcHeaderInfo> {
             Self::headers(txid)
         }
+
+        /// Verify that `raw_tx` is included in the relayed main chain block `block_hash`,
+        /// with the same merkle checks as `push_transaction`, and return its txid,
+        /// confirmation count and decoded outputs.
+        ///
+        /// Unlike `push_transaction`, the tx is not processed and may be any BTC transaction.
+        pub fn verify_spv_proof(
+            raw_tx: &[u8],
+            merkle_proof: &[u8],
+            block_hash: H256,
+        ) -> Result<BtcSpvProof, DispatchError> {
+            let raw = Self::deserialize_tx(raw_tx)?;
+            let merkle_proof: PartialMerkleTree =
+                deserialize(Reader::new(merkle_proof)).map_err(|_| Error::<T>::DeserializeErr)?;
+            let header_info = Self::headers(&block_hash).ok_or(Error::<T>::HeaderNotExisted)?;
+
+            let tx = BtcRelayedTx {
+                block_hash,
+                raw,
+                merkle_proof,
+            };
+            tx::validate_transaction::<T>(&tx, header_info.header.merkle_root_hash, None)?;
+
+            // only the tx in a confirmed main chain block could be regarded as settled
+            ensure!(Self::main_chain(&block_hash), Error::<T>::UnconfirmedTx);
+            let confirmed = Self::confirmed_index().ok_or(Error::<T>::UnconfirmedTx)?;
+            ensure!(
+                header_info.height <= confirmed.height,
+                Error::<T>::UnconfirmedTx
+            );
+
+            let best_height = Self::best_index().height;
+            let outputs = tx
+                .raw
+                .outputs
+                .iter()
+                .map(|output| BtcTxOutput {
+                    value: output.value,
+                    script_pubkey: output.script_pubkey.to_vec(),
+                })
+                .collect();
+
+            Ok(BtcSpvProof {
+                txid: tx.raw.hash(),
+                block_hash,
+                height: header_info.height,
+                confirmations: best_height.saturating_sub(header_info.height) + 1,
+                outputs,
+            })
+        }
     }
 }
 
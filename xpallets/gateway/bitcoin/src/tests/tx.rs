@@ -356,6 +356,52 @@ fn test_push_tx_call() {
     });
 }
 
+#[test]
+fn test_verify_spv_proof() {
+    set_default_ss58_version(Ss58AddressFormatRegistry::ChainxAccount.into());
+    let normal_deposit: Transaction = "02000000000101aeee49e0bbf7a36f78ea4321b5c8bae0b8c72bdf2c024d2484b137fa7d0f8e1f01000000000000000003a0860100000000002251209a9ea267884f5549c206b2aec2bd56d98730f90532ea7f7154d4d4f923b7e3bb0000000000000000326a3035516a706f3772516e7751657479736167477a6334526a376f737758534c6d4d7141754332416255364c464646476a38801a060000000000225120c9929543dfa1e0bb84891acd47bfa6546b05e26b7a04af8eb6765fcc969d565f01409e325889515ed47099fdd7098e6fafdc880b21456d3f368457de923f4229286e34cef68816348a0581ae5885ede248a35ac4b09da61a7b9b90f34c200872d2e300000000".parse().unwrap();
+    let tx = serialization::serialize(&normal_deposit);
+    let headers = generate_blocks_63290_63310();
+    let block_hash = headers[&63299].hash();
+
+    let raw_proof = hex::decode("0a000000050a59b195a68a29037580798ca0414941eb46eaf7607db2d0da1ff89e9570ce455fea22ec1a3e3e7e1167fa220cc8376225f07bd20aa194e7f3c4ac68c7375d8e0a35e47541de7d0aa7312dabcf3bc9f06603e832427b8e4fe9a97a309f8cd7141687d11a3fd8f21e2105a52a3c36a17ea870e326ecddb23221d4cc0398b6c44bdcce3f191919a31f4cfaca5a786cc8315db76683ad6b8008f2ed9b348df76a0d022f00").unwrap();
+
+    ExtBuilder::default().build_and_execute(|| {
+        let confirmed = XGatewayBitcoin::confirmation_number();
+        // the block header is not relayed yet
+        assert_noop!(
+            XGatewayBitcoin::verify_spv_proof(&tx, &raw_proof, block_hash),
+            XGatewayBitcoinErr::HeaderNotExisted,
+        );
+
+        // the block is relayed but not confirmed
+        for i in 63291..=63299 {
+            assert_ok!(XGatewayBitcoin::apply_push_header(headers[&i]));
+        }
+        assert_noop!(
+            XGatewayBitcoin::verify_spv_proof(&tx, &raw_proof, block_hash),
+            XGatewayBitcoinErr::UnconfirmedTx,
+        );
+
+        for i in 63300..=63299 + confirmed {
+            assert_ok!(XGatewayBitcoin::apply_push_header(headers[&i]));
+        }
+        // a tx not in the proof
+        let other = serialization::serialize(&*deposit_taproot1);
+        assert_noop!(
+            XGatewayBitcoin::verify_spv_proof(&other, &raw_proof, block_hash),
+            XGatewayBitcoinErr::BadMerkleProof,
+        );
+
+        let proof = XGatewayBitcoin::verify_spv_proof(&tx, &raw_proof, block_hash).unwrap();
+        assert_eq!(proof.txid, normal_deposit.hash());
+        assert_eq!(proof.height, 63299);
+        assert_eq!(proof.confirmations, confirmed + 1);
+        assert_eq!(proof.outputs.len(), normal_deposit.outputs.len());
+        assert_eq!(proof.outputs[0].value, 100000);
+    });
+}
+
 /*
 这段代码是 ChainX 项目中的一系列测试用例,用于验证比特币网关模块(`XGatewayBitcoin`)的交易类型检测和处理逻辑.
 测试用例涵盖了多种交易类型,包括存款,取款以及热钱包和冷钱包之间的转换.以下是对这些测试用例的解释:
@@ -46,6 +46,26 @@ impl BtcRelayedTxInfo {
     }
 }
 
+/// A decoded output of a transaction verified through a SPV proof.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct BtcTxOutput {
+    pub value: u64,
+    pub script_pubkey: Vec<u8>,
+}
+
+/// The result of a SPV verification against the relayed header chain.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct BtcSpvProof {
+    pub txid: H256,
+    pub block_hash: H256,
+    pub height: u32,
+    /// Number of blocks on top of (and including) the tx block in the main chain.
+    pub confirmations: u32,
+    pub outputs: Vec<BtcTxOutput>,
+}
+
 #[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct BtcHeaderInfo {
@@ -21,7 +21,7 @@ use frame_support::{
     transactional,
 };
 use frame_system::ensure_root;
-use sp_runtime::traits::StaticLookup;
+use sp_runtime::traits::{Saturating, StaticLookup};
 
 use chainx_primitives::{AddrStr, AssetId};
 use xp_runtime::Memo;
@@ -179,6 +179,18 @@ pub mod pallet {
     #[pallet::getter(fn state_of)]
     pub(crate) type WithdrawalStateOf<T: Config> =
         StorageMap<_, Twox64Concat, WithdrawalRecordId, WithdrawalState>;
+
+    /// The cumulative deposited balance of an asset.
+    #[pallet::storage]
+    #[pallet::getter(fn total_deposited)]
+    pub(crate) type TotalDeposited<T: Config> =
+        StorageMap<_, Twox64Concat, AssetId, BalanceOf<T>, ValueQuery>;
+
+    /// The cumulative balance of the finished withdrawals of an asset.
+    #[pallet::storage]
+    #[pallet::getter(fn total_withdrawn)]
+    pub(crate) type TotalWithdrawn<T: Config> =
+        StorageMap<_, Twox64Concat, AssetId, BalanceOf<T>, ValueQuery>;
 }
 
 impl<T: Config> Pallet<T> {
@@ -224,10 +236,16 @@ impl<T: Config> Pallet<T> {
         );
 
         xpallet_assets::Pallet::<T>::issue(&asset_id, who, balance, true)?;
+        Self::note_deposit(asset_id, balance);
         Self::deposit_event(Event::<T>::Deposited(who.clone(), asset_id, balance));
         Ok(())
     }
 
+    /// Record a deposit which is not issued through `deposit`, e.g. deposited into evm directly.
+    pub fn note_deposit(asset_id: AssetId, balance: BalanceOf<T>) {
+        TotalDeposited::<T>::mutate(asset_id, |total| *total = total.saturating_add(balance));
+    }
+
     /// Withdrawal asset (lock asset token firstly, follow-up operations are required).
     ///
     /// WithdrawalRecord State: `Applying`
@@ -418,6 +436,9 @@ impl<T: Config> Pallet<T> {
 
         // Destroy locked asset
         Self::destroy(record.applicant(), record.asset_id(), record.balance())?;
+        TotalWithdrawn::<T>::mutate(record.asset_id(), |total| {
+            *total = total.saturating_add(record.balance())
+        });
 
         // Remove storage
         PendingWithdrawals::<T>::remove(id);
@@ -39,6 +39,29 @@ fn test_normal() {
     })
 }
 
+#[test]
+fn test_deposit_and_withdrawn_totals() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XGatewayRecords::deposit(&ALICE, X_BTC, 100));
+        XGatewayRecords::note_deposit(X_BTC, 20);
+        assert_eq!(XGatewayRecords::total_deposited(X_BTC), 120);
+
+        assert_ok!(XGatewayRecords::withdraw(
+            &ALICE,
+            X_BTC,
+            50,
+            b"addr".to_vec(),
+            b"ext".to_vec().into()
+        ));
+        // the applying withdrawal is not counted
+        assert_eq!(XGatewayRecords::total_withdrawn(X_BTC), 0);
+
+        assert_ok!(XGatewayRecords::process_withdrawals(&[0], Chain::Bitcoin));
+        assert_ok!(XGatewayRecords::finish_withdrawal(0, None));
+        assert_eq!(XGatewayRecords::total_withdrawn(X_BTC), 50);
+    })
+}
+
 #[test]
 fn test_normal2() {
     ExtBuilder::default().build_and_execute(|| {
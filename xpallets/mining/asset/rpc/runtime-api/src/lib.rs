@@ -11,7 +11,8 @@ use codec::Codec;
 
 pub use chainx_primitives::AssetId;
 pub use xpallet_mining_asset::{
-    AssetLedger, MinerLedger, MiningAssetInfo, MiningDividendInfo, MiningWeight,
+    AssetLedger, FixedAssetPower, MinerLedger, MiningAssetInfo, MiningDividendInfo, MiningWeight,
+    PendingDividends, ReferralRewardInfo,
 };
 
 sp_api::decl_runtime_apis! {
@@ -31,6 +32,18 @@ sp_api::decl_runtime_apis! {
 
         /// Get the mining ledger details given the asset miner AccountId.
         fn miner_ledger(who: AccountId) -> BTreeMap<AssetId, MinerLedger<MiningWeight, BlockNumber>>;
+
+        /// Get all the pending staking and asset mining dividends given the AccountId.
+        fn pending_dividends(who: AccountId) -> PendingDividends<AccountId, Balance>;
+
+        /// Get the mining power of asset at the given block number.
+        fn asset_power_at(asset_id: AssetId, at: BlockNumber) -> FixedAssetPower;
+
+        /// Get the referral rewards given the referral AccountId.
+        fn referral_rewards(who: AccountId) -> BTreeMap<AssetId, ReferralRewardInfo<Balance>>;
+
+        /// Get the referrals credited by the latest claims given the asset miner AccountId.
+        fn miner_referrals(who: AccountId) -> BTreeMap<AssetId, AccountId>;
     }
 }
 
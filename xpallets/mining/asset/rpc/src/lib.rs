@@ -17,14 +17,15 @@ use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use xp_rpc::{runtime_error_into_rpc_err, Result, RpcBalance, RpcMiningWeight};
 
 use xpallet_mining_asset_rpc_runtime_api::{
-    AssetId, AssetLedger, MinerLedger, MiningAssetInfo, MiningDividendInfo,
-    XMiningAssetApi as XMiningAssetRuntimeApi,
+    AssetId, AssetLedger, FixedAssetPower, MinerLedger, MiningAssetInfo, MiningDividendInfo,
+    PendingDividends, ReferralRewardInfo, XMiningAssetApi as XMiningAssetRuntimeApi,
 };
 
 /// XMiningAsset RPC methods.
 #[rpc]
 pub trait XMiningAssetApi<BlockHash, AccountId, Balance, MiningWeight, BlockNumber>
 where
+    AccountId: Ord,
     Balance: Display + FromStr,
     MiningWeight: Display + FromStr,
 {
@@ -59,6 +60,39 @@ where
         who: AccountId,
         at: Option<BlockHash>,
     ) -> Result<BTreeMap<AssetId, MinerLedger<RpcMiningWeight<MiningWeight>, BlockNumber>>>;
+
+    /// Get all the pending staking and asset mining dividends given the AccountId.
+    #[rpc(name = "xminingasset_getPendingDividends")]
+    fn pending_dividends(
+        &self,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<PendingDividends<AccountId, RpcBalance<Balance>>>;
+
+    /// Get the mining power of asset at the given block number.
+    #[rpc(name = "xminingasset_getAssetPowerAt")]
+    fn asset_power_at(
+        &self,
+        asset_id: AssetId,
+        block_number: BlockNumber,
+        at: Option<BlockHash>,
+    ) -> Result<FixedAssetPower>;
+
+    /// Get the referral rewards given the referral AccountId.
+    #[rpc(name = "xminingasset_getReferralRewards")]
+    fn referral_rewards(
+        &self,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<BTreeMap<AssetId, ReferralRewardInfo<RpcBalance<Balance>>>>;
+
+    /// Get the referrals credited by the latest claims given the asset miner AccountId.
+    #[rpc(name = "xminingasset_getMinerReferrals")]
+    fn miner_referrals(
+        &self,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<BTreeMap<AssetId, AccountId>>;
 }
 
 /// A struct that implements the [`XMiningAssetApi`].
@@ -84,7 +118,7 @@ where
     Block: BlockT,
     C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
     C::Api: XMiningAssetRuntimeApi<Block, AccountId, Balance, MiningWeight, BlockNumber>,
-    AccountId: Codec,
+    AccountId: Codec + Ord,
     Balance: Codec + Display + FromStr,
     MiningWeight: Codec + Display + FromStr,
     BlockNumber: Codec,
@@ -179,6 +213,87 @@ where
             })
             .map_err(runtime_error_into_rpc_err)
     }
+
+    fn pending_dividends(
+        &self,
+        who: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<PendingDividends<AccountId, RpcBalance<Balance>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.pending_dividends(&at, who)
+            .map(|pending| PendingDividends {
+                staking: pending
+                    .staking
+                    .into_iter()
+                    .map(|(validator, dividend)| (validator, dividend.into()))
+                    .collect(),
+                mining: pending
+                    .mining
+                    .into_iter()
+                    .map(|(id, info)| {
+                        (
+                            id,
+                            MiningDividendInfo {
+                                own: info.own.into(),
+                                other: info.other.into(),
+                                insufficient_stake: info.insufficient_stake.into(),
+                            },
+                        )
+                    })
+                    .collect(),
+            })
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn asset_power_at(
+        &self,
+        asset_id: AssetId,
+        block_number: BlockNumber,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<FixedAssetPower> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.asset_power_at(&at, asset_id, block_number)
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn referral_rewards(
+        &self,
+        who: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<BTreeMap<AssetId, ReferralRewardInfo<RpcBalance<Balance>>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.referral_rewards(&at, who)
+            .map(|referral_rewards| {
+                referral_rewards
+                    .into_iter()
+                    .map(|(id, info)| {
+                        (
+                            id,
+                            ReferralRewardInfo {
+                                referral_share: info.referral_share,
+                                referred: info.referred,
+                                total_reward: info.total_reward.into(),
+                            },
+                        )
+                    })
+                    .collect()
+            })
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn miner_referrals(
+        &self,
+        who: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<BTreeMap<AssetId, AccountId>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.miner_referrals(&at, who)
+            .map_err(runtime_error_into_rpc_err)
+    }
 }
 
 /*
@@ -64,6 +64,29 @@ benchmarks! {
     verify {
         assert_eq!(FixedAssetPowerOf::<T>::get(X_BTC), c);
     }
+
+    set_asset_power_ratio {
+        let c = FixedU128::saturating_from_rational(1, 1_000);
+    }: _(RawOrigin::Root, X_BTC, Some(c))
+    verify {
+        assert_eq!(AssetPowerRatioOf::<T>::get(X_BTC), Some(c));
+    }
+
+    set_asset_power_schedule {
+        let s in 1 .. 100;
+        let steps = (1..=s).map(|i| (i.into(), i * 100)).collect::<Vec<_>>();
+        let schedule = PowerSchedule::Step(steps);
+    }: _(RawOrigin::Root, X_BTC, Some(schedule.clone()))
+    verify {
+        assert_eq!(AssetPowerScheduleOf::<T>::get(X_BTC), Some(schedule));
+    }
+
+    set_referral_share {
+        let c = Permill::from_percent(20);
+    }: _(RawOrigin::Root, X_BTC, c)
+    verify {
+        assert_eq!(ReferralShareOf::<T>::get(X_BTC), c);
+    }
 }
 
 #[cfg(test)]
@@ -80,6 +103,9 @@ mod tests {
             assert_ok!(Pallet::<Test>::test_benchmark_set_claim_staking_requirement());
             assert_ok!(Pallet::<Test>::test_benchmark_set_claim_frequency_limit());
             assert_ok!(Pallet::<Test>::test_benchmark_set_asset_power());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_asset_power_ratio());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_asset_power_schedule());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_referral_share());
         });
     }
 }
@@ -1,8 +1,10 @@
 // Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
 
 use codec::Encode;
+use frame_support::storage::with_transaction;
 use sp_core::crypto::UncheckedFrom;
 use sp_runtime::traits::{Hash, Saturating};
+use sp_runtime::{PerThing, TransactionOutcome};
 
 use xp_mining_common::{
     generic_weight_factors, BaseMiningWeight, Claim, ComputeMiningWeight, WeightFactors, WeightType,
@@ -163,38 +165,120 @@ impl<T: Config> Pallet<T> {
             .map(|(dividend, _, _, _)| dividend)
     }
 
+    /// Claims the dividends of `claimer` from at most `max_targets` mining assets.
+    ///
+    /// Returns the number of miner ledgers visited.
+    pub(crate) fn claim_all_dividends(claimer: &T::AccountId, max_targets: u32) -> u32 {
+        let current_block = <frame_system::Pallet<T>>::block_number();
+        let previleged_assets = Self::mining_previleged_assets();
+        let targets = match Self::claim_all_cursor_of(claimer) {
+            Some(last) => {
+                // Keys are iterated in the order of their hashed storage key, so resume
+                // right after the cursor even if the cursor itself has been removed.
+                let last_key = MinerLedgers::<T>::hashed_key_for(claimer, last);
+                MinerLedgers::<T>::iter_key_prefix(claimer)
+                    .skip_while(|target| {
+                        MinerLedgers::<T>::hashed_key_for(claimer, target) <= last_key
+                    })
+                    .take(max_targets as usize)
+                    .collect::<Vec<_>>()
+            }
+            None => MinerLedgers::<T>::iter_key_prefix(claimer)
+                .take(max_targets as usize)
+                .collect::<Vec<_>>(),
+        };
+        let visited = targets.len() as u32;
+        // Start over from the first mining asset once all the assets are visited.
+        match targets.last() {
+            Some(last) if visited == max_targets => ClaimAllCursorOf::<T>::insert(claimer, last),
+            _ => ClaimAllCursorOf::<T>::remove(claimer),
+        }
+        for asset_id in targets {
+            let has_dividend = previleged_assets.contains(&asset_id)
+                && Self::compute_dividend_at(claimer, &asset_id, current_block)
+                    .map_or(false, |dividend| !dividend.is_zero());
+            if !has_dividend {
+                continue;
+            }
+            let res = with_transaction(|| {
+                match <Self as Claim<T::AccountId>>::claim(claimer, &asset_id) {
+                    Ok(()) => TransactionOutcome::Commit(Ok(())),
+                    Err(e) => TransactionOutcome::Rollback(Err(e)),
+                }
+            });
+            if let Err(e) = res {
+                warn!(
+                    target: "runtime::mining::asset",
+                    "[claim_all] failed to claim {:?} from asset {:?}: {:?}",
+                    claimer, asset_id, e
+                );
+            }
+        }
+        visited
+    }
+
     /// Allocates the dividend to claimer and referral(treasury) accordingly.
     ///
-    /// Each asset miner can have a referral, which splits the 10% of
-    /// of total asset mining dividend. The 10% split will be transferred
-    /// to the treasury account if the claimer does not have a referral.
+    /// Each asset miner can have a referral, which splits the referral share
+    /// (10% by default) of total asset mining dividend. The split will be
+    /// transferred to the treasury account if the claimer does not have a referral.
     ///
     /// total_asset_miner_dividend
-    ///   ├──> referral(treasury) 10%
-    ///   └──> claimer            90%
+    ///   ├──> referral(treasury) referral share
+    ///   └──> claimer            the rest
     fn allocate_dividend(
         claimee_reward_pot: &T::AccountId,
         claimer: &T::AccountId,
         claimee: &AssetId,
         dividend: BalanceOf<T>,
     ) -> Result<(), Error<T>> {
-        let to_referral_or_treasury = dividend / 10u32.saturated_into();
-        let reward_splitter =
-            T::GatewayInterface::referral_of(claimer, *claimee).unwrap_or_else(|| {
-                <T as Config>::TreasuryAccount::treasury_account()
-                    .expect("TreasuryAccount is some; qed")
-            });
-        Self::transfer(
-            claimee_reward_pot,
-            &reward_splitter,
-            to_referral_or_treasury,
-        )?;
+        let to_referral_or_treasury = Self::referral_share_of(claimee).mul_floor(dividend);
+        match T::GatewayInterface::referral_of(claimer, *claimee) {
+            Some(referral) => {
+                Self::transfer(claimee_reward_pot, &referral, to_referral_or_treasury)?;
+                Self::record_referral_reward(&referral, claimer, claimee, to_referral_or_treasury);
+            }
+            None => {
+                let treasury = <T as Config>::TreasuryAccount::treasury_account()
+                    .expect("TreasuryAccount is some; qed");
+                Self::transfer(claimee_reward_pot, &treasury, to_referral_or_treasury)?;
+            }
+        }
 
         let to_claimer = dividend - to_referral_or_treasury;
         Self::transfer(claimee_reward_pot, claimer, to_claimer)?;
 
         Ok(())
     }
+
+    /// Accumulates the referral reward and moves the referred miner over if
+    /// the referral of miner has changed since the last claim.
+    fn record_referral_reward(
+        referral: &T::AccountId,
+        miner: &T::AccountId,
+        asset_id: &AssetId,
+        reward: BalanceOf<T>,
+    ) {
+        let newly_referred = match MinerReferralOf::<T>::get(miner, asset_id) {
+            Some(previous) if previous == *referral => false,
+            Some(previous) => {
+                ReferralRecords::<T>::mutate(&previous, asset_id, |record| {
+                    record.referred = record.referred.saturating_sub(1);
+                });
+                true
+            }
+            None => true,
+        };
+        if newly_referred {
+            MinerReferralOf::<T>::insert(miner, asset_id, referral);
+        }
+        ReferralRecords::<T>::mutate(referral, asset_id, |record| {
+            if newly_referred {
+                record.referred = record.referred.saturating_add(1);
+            }
+            record.total_reward = record.total_reward.saturating_add(reward);
+        });
+    }
 }
 
 impl<T: Config> Claim<T::AccountId> for Pallet<T> {
@@ -288,7 +372,14 @@ where
 
 impl<T: Config> xp_mining_staking::AssetMining<BalanceOf<T>> for Pallet<T> {
     /// Collects the mining power of all mining assets.
+    ///
+    /// The power schedules are evaluated beforehand, so that the scheduled power
+    /// takes effect in the session reward being distributed.
     fn asset_mining_power() -> Vec<(AssetId, MiningPower)> {
+        for (asset_id, schedule) in AssetPowerScheduleOf::<T>::iter() {
+            Self::update_asset_power_from_schedule(asset_id, &schedule);
+        }
+
         // Currently only X-BTC asset.
         FixedAssetPowerOf::<T>::iter()
             .map(|(asset_id, fixed_power)| {
@@ -27,6 +27,7 @@ use frame_support::{
 };
 use frame_system::{ensure_root, ensure_signed};
 use sp_runtime::traits::{SaturatedConversion, Zero};
+use sp_runtime::{FixedPointNumber, FixedU128, Permill};
 
 use chainx_primitives::AssetId;
 use xp_mining_common::{
@@ -34,7 +35,7 @@ use xp_mining_common::{
     ZeroMiningWeightError,
 };
 use xpallet_assets::{AssetType, BalanceOf};
-use xpallet_support::traits::TreasuryAccount;
+use xpallet_support::traits::{PriceOracle, TreasuryAccount};
 
 pub use self::impls::SimpleAssetRewardPotAccountDeterminer;
 pub use self::rpc::*;
@@ -43,6 +44,9 @@ pub use self::weights::WeightInfo;
 
 pub use pallet::*;
 
+/// The maximum share of asset mining dividend that goes to the referral, 50%.
+const MAX_REFERRAL_SHARE: Permill = Permill::from_parts(500_000);
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -66,6 +70,14 @@ pub mod pallet {
         /// Generate the reward pot account for mining asset.
         type DetermineRewardPotAccount: RewardPotAccountFor<Self::AccountId, AssetId>;
 
+        /// Get the price of an asset measured by another asset.
+        type PriceOracle: PriceOracle<(AssetId, AssetId), FixedU128>;
+
+        /// The interval of blocks at which the mining power of assets is updated
+        /// from the price oracle.
+        #[pallet::constant]
+        type AssetPowerUpdateInterval: Get<Self::BlockNumber>;
+
         type WeightInfo: WeightInfo;
     }
 
@@ -74,6 +86,18 @@ pub mod pallet {
     #[pallet::without_storage_info]
     pub struct Pallet<T>(PhantomData<T>);
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            let interval = T::AssetPowerUpdateInterval::get();
+            if !interval.is_zero() && (n % interval).is_zero() {
+                Self::update_asset_power_from_oracle()
+            } else {
+                0
+            }
+        }
+    }
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Claims the staking reward given the `target` validator.
@@ -91,6 +115,21 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Claims the mining rewards of all the mining assets held by the origin account.
+        ///
+        /// At most `max_targets` assets are visited, the assets that have no dividend
+        /// or fail to be claimed are skipped. The next call continues from where this
+        /// one stops, so that all the mining assets are visited in turn.
+        #[pallet::weight(<T as Config>::WeightInfo::claim().saturating_mul(*max_targets as Weight))]
+        pub fn claim_all(
+            origin: OriginFor<T>,
+            #[pallet::compact] max_targets: u32,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            let visited = Self::claim_all_dividends(&sender, max_targets);
+            Ok(Some(<T as Config>::WeightInfo::claim().saturating_mul(visited as Weight)).into())
+        }
+
         #[pallet::weight(<T as Config>::WeightInfo::set_claim_staking_requirement())]
         pub fn set_claim_staking_requirement(
             origin: OriginFor<T>,
@@ -127,6 +166,72 @@ pub mod pallet {
             FixedAssetPowerOf::<T>::insert(asset_id, new);
             Ok(())
         }
+
+        /// Set the ratio of the mining power to the price of asset measured by PCX.
+        ///
+        /// The mining power of asset will be updated from the price oracle
+        /// periodically, `None` to stop it. An asset with the power schedule
+        /// can not follow the price oracle at the same time.
+        #[pallet::weight(<T as Config>::WeightInfo::set_asset_power_ratio())]
+        pub fn set_asset_power_ratio(
+            origin: OriginFor<T>,
+            #[pallet::compact] asset_id: AssetId,
+            ratio: Option<FixedU128>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            if ratio.is_some() {
+                ensure!(
+                    !AssetPowerScheduleOf::<T>::contains_key(asset_id),
+                    Error::<T>::ConflictingPowerSource
+                );
+            }
+            AssetPowerRatioOf::<T>::set(asset_id, ratio);
+            Ok(())
+        }
+
+        /// Set the mining power schedule of asset, `None` to remove it.
+        ///
+        /// The scheduled power is applied whenever the mining power of assets is collected
+        /// for distributing the session reward.
+        /// An asset following the price oracle can not have the power schedule at the
+        /// same time.
+        #[pallet::weight(<T as Config>::WeightInfo::set_asset_power_schedule(
+            schedule.as_ref().map_or(0, |s| s.steps())
+        ))]
+        pub fn set_asset_power_schedule(
+            origin: OriginFor<T>,
+            #[pallet::compact] asset_id: AssetId,
+            schedule: Option<PowerSchedule<T::BlockNumber>>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            if let Some(ref schedule) = schedule {
+                ensure!(schedule.is_valid(), Error::<T>::InvalidPowerSchedule);
+                ensure!(
+                    !AssetPowerRatioOf::<T>::contains_key(asset_id),
+                    Error::<T>::ConflictingPowerSource
+                );
+            }
+            AssetPowerScheduleOf::<T>::set(asset_id, schedule);
+            Ok(())
+        }
+
+        /// Set the share of asset mining dividend that goes to the referral of claimer.
+        ///
+        /// The share can not exceed `MAX_REFERRAL_SHARE`.
+        #[pallet::weight(<T as Config>::WeightInfo::set_referral_share())]
+        pub fn set_referral_share(
+            origin: OriginFor<T>,
+            #[pallet::compact] asset_id: AssetId,
+            share: Permill,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(
+                share <= MAX_REFERRAL_SHARE,
+                Error::<T>::InvalidReferralShare
+            );
+            ReferralShareOf::<T>::insert(asset_id, share);
+            Ok(())
+        }
     }
 
     #[pallet::event]
@@ -136,6 +241,9 @@ pub mod pallet {
         Claimed(T::AccountId, AssetId, BalanceOf<T>),
         /// Issue new balance to the reward pot. [reward_pot_account, amount]
         Minted(T::AccountId, BalanceOf<T>),
+        /// The mining power of asset has been updated from the price oracle or the
+        /// power schedule. [asset_id, power]
+        AssetPowerUpdated(AssetId, FixedAssetPower),
     }
 
     /// Old name generated by `decl_event`.
@@ -153,6 +261,12 @@ pub mod pallet {
         UnexpiredFrequencyLimit,
         /// Zero mining weight.
         ZeroMiningWeight,
+        /// The power schedule is malformed.
+        InvalidPowerSchedule,
+        /// The mining power of asset can not follow both the price oracle and the power schedule.
+        ConflictingPowerSource,
+        /// The referral share exceeds `MAX_REFERRAL_SHARE`.
+        InvalidReferralShare,
         /// Balances error.
         DispatchError,
     }
@@ -198,12 +312,61 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// The map from miner to the last mining asset visited by `claim_all`,
+    /// the next `claim_all` continues from the asset after it.
+    #[pallet::storage]
+    #[pallet::getter(fn claim_all_cursor_of)]
+    pub type ClaimAllCursorOf<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, AssetId>;
+
     /// Mining power map of X-type assets.
     #[pallet::storage]
     #[pallet::getter(fn fixed_asset_power_of)]
     pub type FixedAssetPowerOf<T: Config> =
         StorageMap<_, Twox64Concat, AssetId, FixedAssetPower, ValueQuery>;
 
+    /// The ratio of the mining power to the price of asset measured by PCX.
+    ///
+    /// The mining power of these assets is updated from the price oracle.
+    #[pallet::storage]
+    #[pallet::getter(fn asset_power_ratio_of)]
+    pub type AssetPowerRatioOf<T: Config> = StorageMap<_, Twox64Concat, AssetId, FixedU128>;
+
+    /// The mining power schedule of assets.
+    #[pallet::storage]
+    #[pallet::getter(fn asset_power_schedule_of)]
+    pub type AssetPowerScheduleOf<T: Config> =
+        StorageMap<_, Twox64Concat, AssetId, PowerSchedule<T::BlockNumber>>;
+
+    #[pallet::type_value]
+    pub fn DefaultForReferralShare() -> Permill {
+        Permill::from_percent(10)
+    }
+
+    /// The share of asset mining dividend that goes to the referral(treasury) of claimer.
+    #[pallet::storage]
+    #[pallet::getter(fn referral_share_of)]
+    pub type ReferralShareOf<T: Config> =
+        StorageMap<_, Twox64Concat, AssetId, Permill, ValueQuery, DefaultForReferralShare>;
+
+    /// The referral rewards of referrals given the mining asset.
+    #[pallet::storage]
+    #[pallet::getter(fn referral_records)]
+    pub type ReferralRecords<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        AssetId,
+        ReferralRecord<BalanceOf<T>>,
+        ValueQuery,
+    >;
+
+    /// The referral credited by the latest claim of asset miner.
+    #[pallet::storage]
+    #[pallet::getter(fn miner_referral_of)]
+    pub type MinerReferralOf<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, AssetId, T::AccountId>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub claim_restrictions: Vec<(AssetId, (StakingRequirement, T::BlockNumber))>,
@@ -313,6 +476,47 @@ impl<T: Config> Pallet<T> {
         T::DetermineRewardPotAccount::reward_pot_account_for(asset_id)
     }
 
+    /// Updates the mining power of assets given the price measured by PCX.
+    ///
+    /// The mining power of asset stays unchanged if there is no price or the power is zero.
+    fn update_asset_power_from_oracle() -> Weight {
+        let mut reads: Weight = 0;
+        let mut writes: Weight = 0;
+        for (asset_id, ratio) in AssetPowerRatioOf::<T>::iter() {
+            reads += 1;
+            if let Some(price) = T::PriceOracle::twap((asset_id, xp_protocol::PCX)) {
+                let power: FixedAssetPower = price
+                    .saturating_mul(ratio)
+                    .saturating_mul_int(1u128)
+                    .saturated_into();
+                if power.is_zero() {
+                    continue;
+                }
+                FixedAssetPowerOf::<T>::insert(asset_id, power);
+                Self::deposit_event(Event::<T>::AssetPowerUpdated(asset_id, power));
+                writes += 1;
+            }
+        }
+        // The price oracle reads a few storage items for each asset.
+        T::DbWeight::get().reads_writes(reads.saturating_mul(4).saturating_add(1), writes)
+    }
+
+    /// Updates the mining power of asset given its power schedule at the current block.
+    ///
+    /// The mining power of asset stays unchanged if the schedule has not started yet.
+    fn update_asset_power_from_schedule(
+        asset_id: AssetId,
+        schedule: &PowerSchedule<T::BlockNumber>,
+    ) {
+        let current_block = <frame_system::Pallet<T>>::block_number();
+        if let Some(power) = schedule.power_at(current_block) {
+            if power != FixedAssetPowerOf::<T>::get(asset_id) {
+                FixedAssetPowerOf::<T>::insert(asset_id, power);
+                Self::deposit_event(Event::<T>::AssetPowerUpdated(asset_id, power));
+            }
+        }
+    }
+
     /// This rule doesn't take effect if the interval is zero.
     fn passed_enough_interval(
         who: &T::AccountId,
@@ -228,6 +228,7 @@ parameter_types! {
     pub const SessionDuration: BlockNumber = 50;
     pub const MinimumReferralId: u32 = 2;
     pub const MaximumReferralId: u32 = 12;
+    pub const StakingUnsignedPriority: u64 = u64::MAX / 2;
 }
 
 pub struct DummyStakingRewardPotAccountDeterminer;
@@ -258,9 +259,20 @@ impl xpallet_mining_staking::Config for Test {
     type TreasuryAccount = DummyTreasuryAccount;
     type DetermineRewardPotAccount = DummyStakingRewardPotAccountDeterminer;
     type ValidatorRegistration = Registration;
+    type ValidatorElection = ();
+    type SlashCancelOrigin = frame_system::EnsureRoot<AccountId>;
+    type UnsignedPriority = StakingUnsignedPriority;
     type WeightInfo = ();
 }
 
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
+where
+    Call: From<C>,
+{
+    type OverarchingCall = Call;
+    type Extrinsic = UncheckedExtrinsic;
+}
+
 pub struct DummyAssetRewardPotAccountDeterminer;
 
 impl xp_mining_common::RewardPotAccountFor<AccountId, AssetId>
@@ -279,12 +291,30 @@ impl GatewayInterface<AccountId> for DummyGatewayReferralGetter {
     }
 }
 
+pub struct DummyPriceOracle;
+
+impl xpallet_support::traits::PriceOracle<(AssetId, AssetId), FixedU128> for DummyPriceOracle {
+    fn twap(pair: (AssetId, AssetId)) -> Option<FixedU128> {
+        if pair == (xp_protocol::X_BTC, xp_protocol::PCX) {
+            Some(FixedU128::saturating_from_integer(500_000))
+        } else {
+            None
+        }
+    }
+}
+
+parameter_types! {
+    pub const AssetPowerUpdateInterval: BlockNumber = 10;
+}
+
 impl Config for Test {
     type StakingInterface = Self;
     type GatewayInterface = DummyGatewayReferralGetter;
     type Event = Event;
     type TreasuryAccount = ();
     type DetermineRewardPotAccount = DummyAssetRewardPotAccountDeterminer;
+    type PriceOracle = DummyPriceOracle;
+    type AssetPowerUpdateInterval = AssetPowerUpdateInterval;
     type WeightInfo = ();
 }
 
@@ -6,14 +6,15 @@ use codec::{Decode, Encode};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
-use sp_runtime::{RuntimeDebug, SaturatedConversion};
+use sp_runtime::{PerThing, Permill, RuntimeDebug};
 
 use chainx_primitives::AssetId;
 use xp_mining_common::RewardPotAccountFor;
 
 use crate::{
-    types::*, AssetLedgers, BalanceOf, ClaimRestrictionOf, Config, FixedAssetPowerOf, MinerLedgers,
-    MiningPrevilegedAssets, Pallet,
+    types::*, AssetLedgers, AssetPowerScheduleOf, BalanceOf, ClaimRestrictionOf, Config,
+    FixedAssetPowerOf, MinerLedgers, MinerReferralOf, MiningPrevilegedAssets, Pallet,
+    ReferralRecords, ReferralShareOf,
 };
 
 /// Mining asset info.
@@ -36,12 +37,37 @@ pub struct MiningAssetInfo<AccountId, Balance, MiningWeight, BlockNumber> {
 pub struct MiningDividendInfo<Balance> {
     /// Actual dividend balance that belongs to the claimer.
     pub own: Balance,
-    /// Dividend cut(10% of total) for the referral of claimer or treasury.
+    /// Dividend cut(referral share of total) for the referral of claimer or treasury.
     pub other: Balance,
     /// Required more stake to claim the mining dividend.
     pub insufficient_stake: Balance,
 }
 
+/// Referral rewards of a referral given a mining asset.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ReferralRewardInfo<Balance> {
+    /// Current share of asset mining dividend that goes to the referral.
+    pub referral_share: Permill,
+    /// Number of asset miners whose latest claim was credited to the referral,
+    /// the miners that have never claimed are not counted.
+    pub referred: u32,
+    /// Cumulative referral rewards.
+    pub total_reward: Balance,
+}
+
+/// All the pending dividends of an account, from both Staking and Asset Mining.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PendingDividends<AccountId: Ord, Balance> {
+    /// Staking dividends given the nominated validators.
+    pub staking: BTreeMap<AccountId, Balance>,
+    /// Asset mining dividends given the mining assets.
+    pub mining: BTreeMap<AssetId, MiningDividendInfo<Balance>>,
+}
+
 impl<T: Config> Pallet<T> {
     /// Get overall information about all mining assets.
     pub fn mining_assets(
@@ -65,6 +91,16 @@ impl<T: Config> Pallet<T> {
             .collect()
     }
 
+    /// Get the mining power of asset at the given block number.
+    ///
+    /// Returns the scheduled power if the power schedule has started by then,
+    /// otherwise the current power.
+    pub fn asset_power_at(asset_id: AssetId, at: T::BlockNumber) -> FixedAssetPower {
+        AssetPowerScheduleOf::<T>::get(asset_id)
+            .and_then(|schedule| schedule.power_at(at))
+            .unwrap_or_else(|| FixedAssetPowerOf::<T>::get(asset_id))
+    }
+
     /// Get the asset mining dividends info given the miner AccountId.
     pub fn mining_dividend(
         who: T::AccountId,
@@ -81,7 +117,7 @@ impl<T: Config> Pallet<T> {
                         let insufficient_stake =
                             Self::need_more_stake(&who, dividend, staking_requirement)
                                 .unwrap_or_default();
-                        let other = dividend / 10u32.saturated_into::<BalanceOf<T>>();
+                        let other = ReferralShareOf::<T>::get(&asset_id).mul_floor(dividend);
                         let own = dividend - other;
                         Some((
                             asset_id,
@@ -104,6 +140,29 @@ impl<T: Config> Pallet<T> {
     ) -> BTreeMap<AssetId, MinerLedger<MiningWeight, T::BlockNumber>> {
         MinerLedgers::<T>::iter_prefix(&who).collect()
     }
+
+    /// Get the referral rewards given the referral AccountId.
+    pub fn referral_rewards(
+        who: T::AccountId,
+    ) -> BTreeMap<AssetId, ReferralRewardInfo<BalanceOf<T>>> {
+        ReferralRecords::<T>::iter_prefix(&who)
+            .map(|(asset_id, record)| {
+                (
+                    asset_id,
+                    ReferralRewardInfo {
+                        referral_share: ReferralShareOf::<T>::get(&asset_id),
+                        referred: record.referred,
+                        total_reward: record.total_reward,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Get the referrals credited by the latest claims given the miner AccountId.
+    pub fn miner_referrals(who: T::AccountId) -> BTreeMap<AssetId, T::AccountId> {
+        MinerReferralOf::<T>::iter_prefix(&who).collect()
+    }
 }
 
 /*
@@ -457,6 +457,216 @@ fn asset_mining_reward_should_work() {
     });
 }
 
+#[test]
+fn claim_all_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(t_register_xbtc());
+
+        let t_1 = 666_666;
+        assert_ok!(t_issue_xbtc(t_1, 1));
+        t_set_xbtc_asset_power(100);
+
+        t_start_session(1);
+
+        // The failed claim is skipped.
+        assert_ok!(XMiningAsset::claim_all(Origin::signed(t_1), 10));
+        assert_eq!(Balances::free_balance(&t_1), 0);
+
+        t_xbtc_set_claim_staking_requirement(0);
+        let dividend =
+            XMiningAsset::compute_dividend_at(&t_1, &X_BTC, System::block_number()).unwrap();
+        assert!(dividend > 0);
+        assert_eq!(
+            XMiningAsset::mining_dividend(t_1)[&X_BTC].own,
+            dividend - dividend / 10
+        );
+
+        assert_ok!(XMiningAsset::claim_all(Origin::signed(t_1), 10));
+        assert_eq!(Balances::free_balance(&t_1), dividend - dividend / 10);
+        assert_eq!(
+            XMiningAsset::compute_dividend_at(&t_1, &X_BTC, System::block_number())
+                .unwrap_or_default(),
+            0
+        );
+    });
+}
+
+#[test]
+fn referral_records_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(t_register_xbtc());
+
+        let t_1 = 666_666;
+        let referral = DummyGatewayReferralGetter::referral_of(&t_1, X_BTC).unwrap();
+        assert_ok!(t_issue_xbtc(t_1, 1));
+        t_set_xbtc_asset_power(100);
+        t_xbtc_set_claim_staking_requirement(0);
+        t_xbtc_set_claim_frequency_limit(0);
+
+        assert_err!(
+            XMiningAsset::set_referral_share(Origin::root(), X_BTC, Permill::from_percent(51)),
+            Error::<Test>::InvalidReferralShare
+        );
+        assert_ok!(XMiningAsset::set_referral_share(
+            Origin::root(),
+            X_BTC,
+            Permill::from_percent(20)
+        ));
+
+        t_start_session(1);
+
+        let dividend =
+            XMiningAsset::compute_dividend_at(&t_1, &X_BTC, System::block_number()).unwrap();
+        assert_eq!(
+            XMiningAsset::mining_dividend(t_1)[&X_BTC].other,
+            dividend / 5
+        );
+        assert_ok!(XMiningAsset::claim(Origin::signed(t_1), X_BTC));
+        assert_eq!(Balances::free_balance(&t_1), dividend - dividend / 5);
+        assert_eq!(Balances::free_balance(&referral), dividend / 5);
+
+        assert_eq!(
+            XMiningAsset::referral_records(referral, X_BTC),
+            ReferralRecord {
+                referred: 1,
+                total_reward: dividend / 5,
+            }
+        );
+        assert_eq!(XMiningAsset::miner_referral_of(t_1, X_BTC), Some(referral));
+
+        // The same miner is not counted twice.
+        t_start_session(2);
+        let dividend2 =
+            XMiningAsset::compute_dividend_at(&t_1, &X_BTC, System::block_number()).unwrap();
+        assert_ok!(XMiningAsset::claim(Origin::signed(t_1), X_BTC));
+
+        assert_eq!(
+            XMiningAsset::referral_rewards(referral)[&X_BTC],
+            ReferralRewardInfo {
+                referral_share: Permill::from_percent(20),
+                referred: 1,
+                total_reward: dividend / 5 + dividend2 / 5,
+            }
+        );
+        assert_eq!(XMiningAsset::miner_referrals(t_1)[&X_BTC], referral);
+    });
+}
+
+#[test]
+fn asset_power_should_follow_price_oracle() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(t_register_xbtc());
+        t_set_xbtc_asset_power(400);
+
+        // 1 X-BTC is worth 500_000 PCX in the dummy price oracle.
+        assert_ok!(XMiningAsset::set_asset_power_ratio(
+            Origin::root(),
+            X_BTC,
+            Some(FixedU128::saturating_from_rational(1, 1_000))
+        ));
+        assert_err!(
+            XMiningAsset::set_asset_power_schedule(
+                Origin::root(),
+                X_BTC,
+                Some(PowerSchedule::Step(vec![(10, 200)]))
+            ),
+            Error::<Test>::ConflictingPowerSource
+        );
+
+        XMiningAsset::on_initialize(9);
+        assert_eq!(XMiningAsset::fixed_asset_power_of(X_BTC), 400);
+
+        XMiningAsset::on_initialize(10);
+        assert_eq!(XMiningAsset::fixed_asset_power_of(X_BTC), 500);
+
+        // The zero power is skipped.
+        assert_ok!(XMiningAsset::set_asset_power_ratio(
+            Origin::root(),
+            X_BTC,
+            Some(FixedU128::saturating_from_rational(1, 1_000_000_000))
+        ));
+        XMiningAsset::on_initialize(20);
+        assert_eq!(XMiningAsset::fixed_asset_power_of(X_BTC), 500);
+
+        assert_ok!(XMiningAsset::set_asset_power_ratio(
+            Origin::root(),
+            X_BTC,
+            None
+        ));
+        t_set_xbtc_asset_power(400);
+        XMiningAsset::on_initialize(30);
+        assert_eq!(XMiningAsset::fixed_asset_power_of(X_BTC), 400);
+    });
+}
+
+#[test]
+fn asset_power_schedule_should_work() {
+    use xp_mining_staking::AssetMining;
+
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(t_register_xbtc());
+        t_set_xbtc_asset_power(400);
+
+        assert_err!(
+            XMiningAsset::set_asset_power_schedule(
+                Origin::root(),
+                X_BTC,
+                Some(PowerSchedule::Step(vec![(20, 100), (10, 200)]))
+            ),
+            Error::<Test>::InvalidPowerSchedule
+        );
+
+        assert_ok!(XMiningAsset::set_asset_power_schedule(
+            Origin::root(),
+            X_BTC,
+            Some(PowerSchedule::Linear {
+                from: 400,
+                to: 200,
+                start: 10,
+                end: 20,
+            })
+        ));
+        assert_eq!(XMiningAsset::asset_power_at(X_BTC, 5), 400);
+        assert_eq!(XMiningAsset::asset_power_at(X_BTC, 15), 300);
+        assert_eq!(XMiningAsset::asset_power_at(X_BTC, 30), 200);
+
+        // The schedule is evaluated before the mining power of assets is collected.
+        System::set_block_number(15);
+        assert_eq!(XMiningAsset::fixed_asset_power_of(X_BTC), 400);
+        <XMiningAsset as AssetMining<Balance>>::reward(X_BTC, 100);
+        assert_eq!(XMiningAsset::fixed_asset_power_of(X_BTC), 400);
+        <XMiningAsset as AssetMining<Balance>>::asset_mining_power();
+        assert_eq!(XMiningAsset::fixed_asset_power_of(X_BTC), 300);
+
+        assert_ok!(XMiningAsset::set_asset_power_schedule(
+            Origin::root(),
+            X_BTC,
+            Some(PowerSchedule::Decay {
+                initial: 1_000,
+                start: 20,
+                period: 10,
+                rate: Permill::from_percent(50),
+                floor: 200,
+            })
+        ));
+        assert_eq!(XMiningAsset::asset_power_at(X_BTC, 29), 1_000);
+        assert_eq!(XMiningAsset::asset_power_at(X_BTC, 30), 500);
+        assert_eq!(XMiningAsset::asset_power_at(X_BTC, 40), 250);
+        assert_eq!(XMiningAsset::asset_power_at(X_BTC, 50), 200);
+
+        System::set_block_number(40);
+        <XMiningAsset as AssetMining<Balance>>::asset_mining_power();
+        assert_eq!(XMiningAsset::fixed_asset_power_of(X_BTC), 250);
+
+        assert_ok!(XMiningAsset::set_asset_power_schedule(
+            Origin::root(),
+            X_BTC,
+            None
+        ));
+        assert_eq!(XMiningAsset::asset_power_at(X_BTC, 50), 250);
+    });
+}
+
 /*
 这段代码是ChainX区块链项目的测试模块,包含了一系列的测试函数和测试用例,用于验证挖矿资产和挖矿奖励的逻辑是否按预期工作.
 1. **测试辅助函数**:
@@ -4,7 +4,11 @@ use codec::{Decode, Encode};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{
+    traits::{AtLeast32BitUnsigned, SaturatedConversion, Saturating, Zero},
+    PerThing, Permill, RuntimeDebug,
+};
+use sp_std::prelude::*;
 
 use chainx_primitives::AssetId;
 use xp_mining_common::WeightType;
@@ -87,6 +91,113 @@ pub struct ClaimRestriction<BlockNumber> {
     pub frequency_limit: BlockNumber,
 }
 
+/// Aggregated referral rewards of a referral given a mining asset.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ReferralRecord<Balance> {
+    /// Number of asset miners whose latest claim was credited to this referral.
+    ///
+    /// A miner is only counted after its first claim.
+    pub referred: u32,
+    /// Cumulative referral rewards received from the claims of asset miners.
+    pub total_reward: Balance,
+}
+
+/// Mining power curve of an asset over the block number.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum PowerSchedule<BlockNumber> {
+    /// Ramps linearly from `from` at block `start` to `to` at block `end`.
+    Linear {
+        from: FixedAssetPower,
+        to: FixedAssetPower,
+        start: BlockNumber,
+        end: BlockNumber,
+    },
+    /// Changes to the given power at each block, in ascending order of block.
+    Step(Vec<(BlockNumber, FixedAssetPower)>),
+    /// Decays `initial` by `rate` every `period` blocks since `start`,
+    /// but never goes below `floor`.
+    Decay {
+        initial: FixedAssetPower,
+        start: BlockNumber,
+        period: BlockNumber,
+        rate: Permill,
+        floor: FixedAssetPower,
+    },
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> PowerSchedule<BlockNumber> {
+    /// Returns true if the schedule is well-formed.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Self::Linear { start, end, .. } => start < end,
+            Self::Step(steps) => {
+                !steps.is_empty() && steps.windows(2).all(|pair| pair[0].0 < pair[1].0)
+            }
+            Self::Decay { period, .. } => !period.is_zero(),
+        }
+    }
+
+    /// Returns the number of step changes of the schedule.
+    pub fn steps(&self) -> u32 {
+        match self {
+            Self::Step(steps) => steps.len() as u32,
+            _ => 1,
+        }
+    }
+
+    /// Returns the scheduled power at block `now`, `None` if the schedule
+    /// has not started yet.
+    pub fn power_at(&self, now: BlockNumber) -> Option<FixedAssetPower> {
+        match self {
+            Self::Linear {
+                from,
+                to,
+                start,
+                end,
+            } => {
+                if now < *start {
+                    return None;
+                }
+                if now >= *end {
+                    return Some(*to);
+                }
+                let elapsed = (now - *start).saturated_into::<u128>();
+                let duration = (*end - *start).saturated_into::<u128>();
+                let (from, to) = (*from as u128, *to as u128);
+                let power = if to >= from {
+                    from + (to - from) * elapsed / duration
+                } else {
+                    from - (from - to) * elapsed / duration
+                };
+                Some(power.saturated_into())
+            }
+            Self::Step(steps) => steps
+                .iter()
+                .take_while(|(at, _)| *at <= now)
+                .last()
+                .map(|(_, power)| *power),
+            Self::Decay {
+                initial,
+                start,
+                period,
+                rate,
+                floor,
+            } => {
+                if now < *start || period.is_zero() {
+                    return None;
+                }
+                let periods = ((now - *start) / *period).saturated_into::<usize>();
+                let remaining = rate.left_from_one().saturating_pow(periods);
+                Some((remaining * *initial).max(*floor))
+            }
+        }
+    }
+}
+
 /*
 这段代码定义了ChainX区块链项目中与挖矿权重相关的几个关键结构体和类型.这些结构体用于存储和处理挖矿资产和矿工的权重信息,
 以及认领挖矿奖励的限制条件.以下是代码的主要组成部分和它们的功能:
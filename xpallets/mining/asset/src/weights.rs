@@ -35,6 +35,9 @@ pub trait WeightInfo {
     fn set_claim_staking_requirement() -> Weight;
     fn set_claim_frequency_limit() -> Weight;
     fn set_asset_power() -> Weight;
+    fn set_asset_power_ratio() -> Weight;
+    fn set_asset_power_schedule(s: u32) -> Weight;
+    fn set_referral_share() -> Weight;
 }
 
 /// Weights for xpallet_mining_asset using the Substrate node and recommended hardware.
@@ -42,8 +45,8 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     fn claim() -> Weight {
         (184_016_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(12 as Weight))
-            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+            .saturating_add(T::DbWeight::get().reads(15 as Weight))
+            .saturating_add(T::DbWeight::get().writes(7 as Weight))
     }
     fn set_claim_staking_requirement() -> Weight {
         (7_224_000 as Weight)
@@ -58,14 +61,25 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     fn set_asset_power() -> Weight {
         (3_043_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn set_asset_power_ratio() -> Weight {
+        (3_217_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_asset_power_schedule(s: u32) -> Weight {
+        (3_562_000 as Weight)
+            .saturating_add((12_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_referral_share() -> Weight {
+        (3_108_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
     fn claim() -> Weight {
         (184_016_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(12 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+            .saturating_add(RocksDbWeight::get().reads(15 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(7 as Weight))
     }
     fn set_claim_staking_requirement() -> Weight {
         (7_224_000 as Weight)
@@ -80,4 +94,15 @@ impl WeightInfo for () {
     fn set_asset_power() -> Weight {
         (3_043_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn set_asset_power_ratio() -> Weight {
+        (3_217_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_asset_power_schedule(s: u32) -> Weight {
+        (3_562_000 as Weight)
+            .saturating_add((12_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_referral_share() -> Weight {
+        (3_108_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
 }
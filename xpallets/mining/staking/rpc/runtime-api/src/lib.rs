@@ -10,7 +10,8 @@ use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 use codec::Codec;
 
 pub use xpallet_mining_staking::{
-    NominatorInfo, NominatorLedger, Unbonded, ValidatorInfo, ValidatorLedger, VoteWeight,
+    EraIndex, EraRewardInfo, NominatorInfo, NominatorLedger, SlashRecord, Unbonded,
+    ValidatorEraRecord, ValidatorEraReward, ValidatorInfo, ValidatorLedger, VoteWeight,
 };
 
 sp_api::decl_runtime_apis! {
@@ -36,6 +37,12 @@ sp_api::decl_runtime_apis! {
 
         /// Get individual nominator information given the nominator AccountId.
         fn nominator_info_of(who: AccountId) -> NominatorInfo<BlockNumber>;
+
+        /// Get the reward distribution and slashes of the given era.
+        fn era_reward_history(era: EraIndex) -> EraRewardInfo<AccountId, Balance>;
+
+        /// Get the rewards and slashes of the given validator in the eras kept in history.
+        fn validator_era_history(who: AccountId) -> Vec<ValidatorEraRecord<Balance>>;
     }
 }
 
@@ -17,7 +17,8 @@ use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use xp_rpc::{runtime_error_into_rpc_err, Result, RpcBalance, RpcVoteWeight};
 
 use xpallet_mining_staking_rpc_runtime_api::{
-    NominatorInfo, NominatorLedger, Unbonded, ValidatorInfo, ValidatorLedger,
+    EraIndex, EraRewardInfo, NominatorInfo, NominatorLedger, SlashRecord, Unbonded,
+    ValidatorEraRecord, ValidatorEraReward, ValidatorInfo, ValidatorLedger,
     XStakingApi as XStakingRuntimeApi,
 };
 
@@ -74,6 +75,22 @@ where
         who: AccountId,
         at: Option<BlockHash>,
     ) -> Result<NominatorInfo<BlockNumber>>;
+
+    /// Get the reward distribution and slashes of the given era.
+    #[rpc(name = "xstaking_getEraRewardHistory")]
+    fn era_reward_history(
+        &self,
+        era: EraIndex,
+        at: Option<BlockHash>,
+    ) -> Result<EraRewardInfo<AccountId, RpcBalance<Balance>>>;
+
+    /// Get the rewards and slashes of the given validator in the eras kept in history.
+    #[rpc(name = "xstaking_getValidatorEraHistory")]
+    fn validator_era_history(
+        &self,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<ValidatorEraRecord<RpcBalance<Balance>>>>;
 }
 
 /// A struct that implements the [`XStakingApi`].
@@ -227,6 +244,70 @@ where
         api.nominator_info_of(&at, who)
             .map_err(runtime_error_into_rpc_err)
     }
+
+    fn era_reward_history(
+        &self,
+        era: EraIndex,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<EraRewardInfo<AccountId, RpcBalance<Balance>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.era_reward_history(&at, era)
+            .map(|info| EraRewardInfo {
+                total_reward: info.total_reward.into(),
+                validators: info
+                    .validators
+                    .into_iter()
+                    .map(|(validator, reward)| (validator, into_rpc_era_reward(reward)))
+                    .collect(),
+                slashes: info
+                    .slashes
+                    .into_iter()
+                    .map(|(offender, slash)| (offender, into_rpc_slash_record(slash)))
+                    .collect(),
+            })
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn validator_era_history(
+        &self,
+        who: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<ValidatorEraRecord<RpcBalance<Balance>>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.validator_era_history(&at, who)
+            .map(|records| {
+                records
+                    .into_iter()
+                    .map(|record| ValidatorEraRecord {
+                        era: record.era,
+                        reward: into_rpc_era_reward(record.reward),
+                        slash: into_rpc_slash_record(record.slash),
+                    })
+                    .collect()
+            })
+            .map_err(runtime_error_into_rpc_err)
+    }
+}
+
+fn into_rpc_era_reward<Balance: Display + FromStr>(
+    reward: ValidatorEraReward<Balance>,
+) -> ValidatorEraReward<RpcBalance<Balance>> {
+    ValidatorEraReward {
+        to_validator: reward.to_validator.into(),
+        to_reward_pot: reward.to_reward_pot.into(),
+    }
+}
+
+fn into_rpc_slash_record<Balance: Display + FromStr>(
+    slash: SlashRecord<Balance>,
+) -> SlashRecord<RpcBalance<Balance>> {
+    SlashRecord {
+        penalty: slash.penalty.into(),
+        slashed: slash.slashed.into(),
+        force_chilled: slash.force_chilled,
+    }
 }
 
 /*
@@ -85,6 +85,40 @@ benchmarks! {
         assert!(Pallet::<T>::staked_of(&validator)  == 80u32.into());
     }
 
+    unlock_all_due {
+        let validator: T::AccountId = create_validator::<T>("validator", 2, 100);
+        let validator_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(validator.clone());
+
+        Pallet::<T>::set_validator_bonding_duration(RawOrigin::Root.into(), 0u32.into())?;
+
+        Pallet::<T>::unbond(
+            RawOrigin::Signed(validator.clone()).into(),
+            validator_lookup,
+            20u32.into(),
+        )?;
+
+        let block_number: T::BlockNumber = frame_system::Pallet::<T>::block_number();
+        frame_system::Pallet::<T>::set_block_number(block_number + 1u32.into());
+
+    }: _(RawOrigin::Signed(validator.clone()), 1)
+    verify {
+        assert!(Pallet::<T>::unbonded_chunks_of(&validator, &validator).is_empty());
+        assert!(Pallet::<T>::staked_of(&validator)  == 80u32.into());
+    }
+
+    instant_unbond {
+        let nominator = create_funded_user::<T>("nominator", 0, 100);
+        let validator: T::AccountId = create_validator::<T>("validator", 2, 1000);
+        b_bond::<T>(nominator.clone(), validator.clone(), 50);
+        let validator_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(validator.clone());
+
+        Pallet::<T>::set_instant_unbond_penalty(RawOrigin::Root.into(), Some(Perbill::from_percent(10)))?;
+    }: _(RawOrigin::Signed(nominator.clone()), validator_lookup, 50u32.into())
+    verify {
+        assert!(Pallet::<T>::bonded_to(&nominator, &validator).is_zero());
+        assert!(T::Currency::free_balance(&nominator) == 95u32.into());
+    }
+
     rebond {
         let nominator = create_funded_user::<T>("nominator", 0, 100);
         let validator1: T::AccountId = create_validator::<T>("validator1", 2, 100);
@@ -140,6 +174,14 @@ benchmarks! {
         assert!(Pallet::<T>::is_active(&validator2));
     }
 
+    set_commission {
+        let validator: T::AccountId = create_validator::<T>("validator", 2, 100);
+        let commission = Perbill::from_percent(22);
+    }: _(RawOrigin::Signed(validator.clone()), commission)
+    verify {
+        assert_eq!(Validators::<T>::get(&validator).commission, commission);
+    }
+
     set_validator_count {
         let c = 1000;
     }: _(RawOrigin::Root, c)
@@ -181,6 +223,105 @@ benchmarks! {
     verify {
         assert_eq!(SessionsPerEra::<T>::get(), c);
     }
+
+    set_maximum_commission {
+        let commission = Perbill::from_percent(30);
+    }: _(RawOrigin::Root, commission)
+    verify {
+        assert_eq!(MaximumCommission::<T>::get(), commission);
+    }
+
+    set_maximum_commission_change {
+        let change = Perbill::from_percent(10);
+    }: _(RawOrigin::Root, change)
+    verify {
+        assert_eq!(MaximumCommissionChange::<T>::get(), change);
+    }
+
+    set_instant_unbond_penalty {
+        let penalty = Perbill::from_percent(10);
+    }: _(RawOrigin::Root, Some(penalty))
+    verify {
+        assert_eq!(InstantUnbondPenalty::<T>::get(), Some(penalty));
+    }
+
+    set_history_depth {
+        let e in 1 .. 100;
+        let validator = create_validator::<T>("validator", 0, 1000);
+        ActiveEra::<T>::put(ActiveEraInfo { index: e, start: None });
+        HistoryDepth::<T>::put(e + 1);
+        for era in 0..=e {
+            ErasTotalReward::<T>::insert(era, BalanceOf::<T>::from(1000u32));
+            ErasValidatorReward::<T>::insert(era, &validator, ValidatorEraReward::default());
+        }
+    }: _(RawOrigin::Root, 1, e)
+    verify {
+        assert_eq!(HistoryDepth::<T>::get(), 1);
+        assert!(!ErasTotalReward::<T>::contains_key(e - 1));
+        assert!(ErasTotalReward::<T>::contains_key(e));
+    }
+
+    set_slash_defer_duration {
+        let duration = 7u32;
+    }: _(RawOrigin::Root, duration)
+    verify {
+        assert_eq!(SlashDeferDuration::<T>::get(), duration);
+    }
+
+    set_grace_offences {
+        let grace = 2u32;
+    }: _(RawOrigin::Root, grace)
+    verify {
+        assert_eq!(GraceOffences::<T>::get(), grace);
+    }
+
+    cancel_deferred_slash {
+        let s in 1 .. 1000;
+        let era: EraIndex = 1;
+        let offender: T::AccountId = account("offender", 0, SEED);
+        let unapplied = (0..s)
+            .map(|_| UnappliedSlash {
+                offender: offender.clone(),
+                penalty: 10u32.into(),
+                reported_era: 0,
+            })
+            .collect::<Vec<_>>();
+        UnappliedSlashes::<T>::insert(era, unapplied);
+        let slash_indices = (0..s).collect::<Vec<_>>();
+    }: _(RawOrigin::Root, era, slash_indices)
+    verify {
+        assert!(UnappliedSlashes::<T>::get(era).is_empty());
+    }
+
+    set_auto_compound {
+        let nominator = create_funded_user::<T>("nominator", 2, 100);
+    }: _(RawOrigin::Signed(nominator.clone()), true)
+    verify {
+        assert!(AutoCompoundOf::<T>::get(&nominator));
+    }
+
+    submit_election_solution {
+        let c in 1 .. 100;
+        let n in 1 .. 1000;
+        let validators = (0..c)
+            .map(|i| create_validator::<T>("validator", i, 1000))
+            .collect::<Vec<_>>();
+        for i in 0..n {
+            let nominator = create_funded_user::<T>("nominator", i, 100);
+            b_bond::<T>(nominator, validators[(i % c) as usize].clone(), 10);
+        }
+        ValidatorCount::<T>::put(c);
+        MinimumValidatorCount::<T>::put(1);
+        let winners = Pallet::<T>::elect_next_validators().unwrap();
+        let solution = ElectionSolution {
+            era: Pallet::<T>::current_era().map(|e| e + 1).unwrap_or_default(),
+            score: Pallet::<T>::election_score_of(&winners),
+            winners,
+        };
+    }: _(RawOrigin::None, solution.clone())
+    verify {
+        assert_eq!(QueuedElected::<T>::get(), Some(solution));
+    }
 }
 
 #[cfg(test)]
@@ -196,14 +337,26 @@ mod tests {
             assert_ok!(Pallet::<Test>::test_benchmark_bond());
             assert_ok!(Pallet::<Test>::test_benchmark_unbond());
             assert_ok!(Pallet::<Test>::test_benchmark_unlock_unbonded_withdrawal());
+            assert_ok!(Pallet::<Test>::test_benchmark_unlock_all_due());
+            assert_ok!(Pallet::<Test>::test_benchmark_instant_unbond());
             assert_ok!(Pallet::<Test>::test_benchmark_rebond());
             assert_ok!(Pallet::<Test>::test_benchmark_claim());
             assert_ok!(Pallet::<Test>::test_benchmark_chill());
             assert_ok!(Pallet::<Test>::test_benchmark_validate());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_commission());
             assert_ok!(Pallet::<Test>::test_benchmark_set_validator_count());
             assert_ok!(Pallet::<Test>::test_benchmark_set_minimum_validator_count());
             assert_ok!(Pallet::<Test>::test_benchmark_set_bonding_duration());
             assert_ok!(Pallet::<Test>::test_benchmark_set_validator_bonding_duration());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_maximum_commission());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_maximum_commission_change());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_instant_unbond_penalty());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_history_depth());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_slash_defer_duration());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_grace_offences());
+            assert_ok!(Pallet::<Test>::test_benchmark_cancel_deferred_slash());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_auto_compound());
+            assert_ok!(Pallet::<Test>::test_benchmark_submit_election_solution());
         });
     }
 }
@@ -1,6 +1,7 @@
 // Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
 
 use frame_support::traits::LockIdentifier;
+use sp_runtime::Perbill;
 
 pub const STAKING_ID: LockIdentifier = *b"staking ";
 
@@ -37,6 +38,29 @@ pub const DEFAULT_BONDING_DURATION: u64 = DEFAULT_BLOCKS_PER_SESSION * 12 * 24 *
 /// The default bonding duration for validator is 3 * 10 days.
 pub const DEFAULT_VALIDATOR_BONDING_DURATION: u64 = DEFAULT_BONDING_DURATION * 10;
 
+/// The maximum number of nominations read by the sequential Phragmén election.
+///
+/// The election falls back to the top-most candidates if there are more nominations.
+pub const MAX_ELECTING_NOMINATIONS: u32 = 20_000;
+
+/// Key of the offchain storage recording the last election solution submission.
+pub const OFFCHAIN_ELECTION_LOCK: &[u8] = b"xstaking/offchain-election-lock";
+
+/// Minimum number of blocks between two election solution submissions for the same era.
+pub const OFFCHAIN_ELECTION_REPEAT: u32 = 5;
+
+/// The default number of eras to keep the reward and slash history.
+pub const DEFAULT_HISTORY_DEPTH: u32 = 84;
+
+/// The default commission of validator is 20%, the rest 80% goes to the reward pot.
+pub const DEFAULT_VALIDATOR_COMMISSION: Perbill = Perbill::from_parts(200_000_000);
+
+/// Validators can not set the commission higher than 50% by default.
+pub const DEFAULT_MAXIMUM_COMMISSION: Perbill = Perbill::from_parts(500_000_000);
+
+/// Validators can change the commission by no more than 5% per era by default.
+pub const DEFAULT_MAXIMUM_COMMISSION_CHANGE: Perbill = Perbill::from_parts(50_000_000);
+
 /*
 这段代码是使用Rust语言编写的,它定义了一些与ChainX项目相关的常量和配置.
 1. `STAKEING_ID`: 这是一个`LockIdentifier`类型的常量,用于标识Staking(质押)操作.
@@ -1,8 +1,144 @@
 // Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
 
 use super::*;
-use frame_support::log;
-use sp_std::vec::Vec;
+use frame_support::{
+    log,
+    weights::{DispatchClass, Weight},
+};
+use frame_system::offchain::SubmitTransaction;
+use sp_npos_elections::seq_phragmen;
+use sp_runtime::offchain::storage::{StorageRetrievalError, StorageValueRef};
+use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
+
+/// Means for electing the validators of the next era from the qualified candidates.
+pub trait ValidatorElection<T: Config> {
+    /// Returns at most `desired_count` validators elected from `candidates`.
+    ///
+    /// The candidates are sorted by the total nominations in descending order.
+    fn elect(
+        candidates: Vec<(BalanceOf<T>, T::AccountId)>,
+        desired_count: usize,
+    ) -> Vec<T::AccountId>;
+}
+
+/// Chooses the top-most `desired_count` candidates by the total nominations.
+impl<T: Config> ValidatorElection<T> for () {
+    fn elect(
+        candidates: Vec<(BalanceOf<T>, T::AccountId)>,
+        desired_count: usize,
+    ) -> Vec<T::AccountId> {
+        candidates
+            .into_iter()
+            .take(desired_count)
+            .map(|(_, v)| v)
+            .collect()
+    }
+}
+
+/// Elects the validators by running sequential Phragmén over the nomination graph.
+///
+/// Each nominator is regarded as a voter approving all the candidates it has nominated,
+/// with the sum of these nominations as its budget, which tends to spread the backing
+/// evenly instead of concentrating it on a few validators.
+///
+/// Note that pooling the nominations into one budget departs from the staking model,
+/// in which each nomination is bonded to and only backs its own validator. The election
+/// is free to redistribute the budget among the targets, whereas the actual backing,
+/// rewards and slashes of a winner still follow the nominations bonded to it.
+///
+/// Falls back to the top-most candidates if there are more than `MAX_ELECTING_NOMINATIONS`
+/// nominations, or if the election fails or elects fewer than the minimum validator count.
+pub struct SequentialPhragmen;
+
+impl SequentialPhragmen {
+    /// Returns the voters of the candidates with their budgets and targets,
+    /// or `None` if there are more than `MAX_ELECTING_NOMINATIONS` nominations.
+    fn voters<T: Config>(
+        candidate_set: &BTreeSet<T::AccountId>,
+    ) -> Option<Vec<(T::AccountId, u64, Vec<T::AccountId>)>> {
+        let mut nominations = Nominations::<T>::iter();
+
+        let mut voters = BTreeMap::<T::AccountId, (BalanceOf<T>, Vec<T::AccountId>)>::new();
+        let mut visited = 0u32;
+        for (nominator, validator, ledger) in
+            nominations.by_ref().take(MAX_ELECTING_NOMINATIONS as usize)
+        {
+            visited += 1;
+            if ledger.nomination.is_zero() || !candidate_set.contains(&validator) {
+                continue;
+            }
+            let (budget, targets) = voters.entry(nominator).or_default();
+            *budget = budget.saturating_add(ledger.nomination);
+            targets.push(validator);
+        }
+
+        let exceeded = nominations.next().is_some();
+        frame_system::Pallet::<T>::register_extra_weight_unchecked(
+            T::DbWeight::get().reads(visited as Weight + exceeded as Weight),
+            DispatchClass::Mandatory,
+        );
+        if exceeded {
+            return None;
+        }
+
+        Some(
+            voters
+                .into_iter()
+                .map(|(voter, (budget, targets))| (voter, budget.saturated_into::<u64>(), targets))
+                .collect(),
+        )
+    }
+}
+
+impl<T: Config> ValidatorElection<T> for SequentialPhragmen {
+    fn elect(
+        candidates: Vec<(BalanceOf<T>, T::AccountId)>,
+        desired_count: usize,
+    ) -> Vec<T::AccountId> {
+        let candidate_set = candidates
+            .iter()
+            .map(|(_, v)| v.clone())
+            .collect::<BTreeSet<_>>();
+
+        let winners = match Self::voters::<T>(&candidate_set) {
+            Some(voters) => match seq_phragmen::<_, Perbill>(
+                desired_count,
+                candidate_set.into_iter().collect(),
+                voters,
+                None,
+            ) {
+                Ok(result) => result.winners.into_iter().map(|(v, _)| v).collect(),
+                Err(e) => {
+                    log::warn!(
+                        target: "runtime::mining::staking",
+                        "[elect] sequential phragmen failed: {:?}", e
+                    );
+                    Vec::new()
+                }
+            },
+            None => {
+                log::warn!(
+                    target: "runtime::mining::staking",
+                    "[elect] more than {} nominations", MAX_ELECTING_NOMINATIONS
+                );
+                Vec::new()
+            }
+        };
+
+        let minimum_count =
+            (Pallet::<T>::reasonable_minimum_validator_count() as usize).min(desired_count);
+        if winners.len() < minimum_count {
+            log::warn!(
+                target: "runtime::mining::staking",
+                "[elect] {} winners elected by sequential phragmen, \
+                fallback to the top-most candidates", winners.len()
+            );
+            return <() as ValidatorElection<T>>::elect(candidates, desired_count);
+        }
+
+        winners
+    }
+}
 
 impl<T: Config> Pallet<T> {
     /// Returns a new validator set for the new era.
@@ -13,6 +149,7 @@ impl<T: Config> Pallet<T> {
             s.unwrap()
         });
         ErasStartSessionIndex::<T>::insert(&current_era, &start_session_index);
+        IsCurrentSessionFinal::<T>::put(false);
 
         // Set staking information for new era.
         let maybe_new_validators = Self::select_and_update_validators(current_era);
@@ -37,13 +174,23 @@ impl<T: Config> Pallet<T> {
             && T::ValidatorRegistration::is_registered(who)
     }
 
+    /// Same as `is_qualified_candidate`, but never forces the candidate to be chilled.
+    fn is_eligible_candidate(who: &T::AccountId) -> bool {
+        Self::is_active(who)
+            && Self::candidate_threshold_satisfied(who)
+            && T::ValidatorRegistration::is_registered(who)
+    }
+
+    fn candidate_threshold_satisfied(who: &T::AccountId) -> bool {
+        let BondRequirement { self_bonded, total } = Self::validator_candidate_requirement();
+        Self::validator_self_bonded(who) >= self_bonded && Self::total_votes_of(who) >= total
+    }
+
     /// Returns true if the candidate meets the minimum candidate threshold.
     ///
     /// Otherwise the candidate will be **forced to be chilled**.
     fn meet_candidate_threshold(who: &T::AccountId) -> bool {
-        let BondRequirement { self_bonded, total } = Self::validator_candidate_requirement();
-        let threshold_satisfied =
-            Self::validator_self_bonded(who) >= self_bonded && Self::total_votes_of(who) >= total;
+        let threshold_satisfied = Self::candidate_threshold_satisfied(who);
 
         if !threshold_satisfied && Self::try_force_chilled(who).is_ok() {
             log::info!(
@@ -56,9 +203,14 @@ impl<T: Config> Pallet<T> {
     }
 
     /// Filters out all the qualified validator candidates, sorted by the total nominations.
-    fn filter_out_candidates() -> Vec<(BalanceOf<T>, T::AccountId)> {
-        let mut candidates = Self::validator_set()
-            .filter(Self::is_qualified_candidate)
+    pub(crate) fn filter_out_candidates() -> Vec<(BalanceOf<T>, T::AccountId)> {
+        Self::sort_by_total_votes(Self::validator_set().filter(Self::is_qualified_candidate))
+    }
+
+    fn sort_by_total_votes(
+        candidates: impl Iterator<Item = T::AccountId>,
+    ) -> Vec<(BalanceOf<T>, T::AccountId)> {
+        let mut candidates = candidates
             .map(|v| (Self::total_votes_of(&v), v))
             .collect::<Vec<_>>();
         candidates.sort_by(|&(ref b1, _), &(ref b2, _)| b2.cmp(b1));
@@ -67,13 +219,15 @@ impl<T: Config> Pallet<T> {
 
     /// Selects the new validator set at the end of the era.
     ///
-    /// Order potential validators by their total nominations and
-    /// choose the top-most ValidatorCount::get() of them.
+    /// Elect at most ValidatorCount::get() validators from the potential validators
+    /// using `T::ValidatorElection`.
     ///
     /// This should only be called at the end of an era.
-    fn select_and_update_validators(_current_era: EraIndex) -> Option<Vec<T::AccountId>> {
-        // TODO: might move to offchain worker solution in the future.
-        // Currently there is no performance issue practically.
+    fn select_and_update_validators(current_era: EraIndex) -> Option<Vec<T::AccountId>> {
+        if let Some(validators) = Self::take_queued_elected(current_era) {
+            return Some(validators);
+        }
+
         let candidates = Self::filter_out_candidates();
         debug!(
             target: "runtime::mining::staking",
@@ -88,15 +242,149 @@ impl<T: Config> Pallet<T> {
 
         let desired_validator_count = ValidatorCount::<T>::get() as usize;
 
-        let validators = candidates
-            .into_iter()
-            .take(desired_validator_count)
-            .map(|(_, v)| v)
-            .collect::<Vec<_>>();
+        let validators = T::ValidatorElection::elect(candidates, desired_validator_count);
 
         // Always return Some(new_validators).
         Some(validators)
     }
+
+    /// Takes the validators submitted by the offchain worker for the given era.
+    ///
+    /// Returns `None` if there is no such solution or some winner is no longer eligible,
+    /// in which case the election will be computed on-chain.
+    fn take_queued_elected(era: EraIndex) -> Option<Vec<T::AccountId>> {
+        let solution = QueuedElected::<T>::take()?;
+        if solution.era == era && solution.winners.iter().all(Self::is_eligible_candidate) {
+            Some(solution.winners)
+        } else {
+            log::warn!(
+                target: "runtime::mining::staking",
+                "[take_queued_elected] Discard the queued election solution for era {}, \
+                fallback to the on-chain election", solution.era
+            );
+            None
+        }
+    }
+
+    /// Computes the validators of the next era from the currently eligible candidates
+    /// using `T::ValidatorElection`.
+    ///
+    /// Returns `None` if there are fewer candidates than the minimum validator count.
+    pub(crate) fn elect_next_validators() -> Option<Vec<T::AccountId>> {
+        let candidates =
+            Self::sort_by_total_votes(Self::validator_set().filter(Self::is_eligible_candidate));
+        if candidates.len() < Self::reasonable_minimum_validator_count() as usize {
+            return None;
+        }
+        Some(T::ValidatorElection::elect(
+            candidates,
+            ValidatorCount::<T>::get() as usize,
+        ))
+    }
+
+    /// Returns the score of the given winners by their current total nominations.
+    pub(crate) fn election_score_of(winners: &[T::AccountId]) -> ElectionScore<BalanceOf<T>> {
+        let backings = winners.iter().map(Self::total_votes_of).collect::<Vec<_>>();
+        ElectionScore {
+            minimal_backing: backings.iter().min().copied().unwrap_or_default(),
+            total_backing: backings
+                .into_iter()
+                .fold(Zero::zero(), |acc: BalanceOf<T>, x| acc.saturating_add(x)),
+        }
+    }
+
+    /// Checks the validity of an election solution for the next era.
+    ///
+    /// Instead of running the election again, only checks that the winners are distinct
+    /// eligible candidates, as many as the desired validator count allows, that the score
+    /// matches the winners, and that the solution scores higher than the queued one.
+    pub(crate) fn check_election_solution(
+        solution: &ElectionSolution<T::AccountId, BalanceOf<T>>,
+    ) -> Result<(), Error<T>> {
+        let next_era = Self::current_era().map(|e| e + 1).unwrap_or_default();
+        ensure!(solution.era == next_era, Error::<T>::StaleElectionSolution);
+
+        let winners = &solution.winners;
+        let eligible = Self::validator_set()
+            .filter(Self::is_eligible_candidate)
+            .collect::<BTreeSet<_>>();
+        ensure!(
+            eligible.len() >= Self::reasonable_minimum_validator_count() as usize
+                && winners.len() == eligible.len().min(ValidatorCount::<T>::get() as usize)
+                && winners.iter().collect::<BTreeSet<_>>().len() == winners.len()
+                && winners.iter().all(|w| eligible.contains(w)),
+            Error::<T>::InvalidElectionSolution
+        );
+        ensure!(
+            Self::election_score_of(winners) == solution.score,
+            Error::<T>::InvalidElectionSolution
+        );
+
+        if let Some(queued) = Self::queued_elected() {
+            ensure!(
+                queued.era != solution.era || solution.score > queued.score,
+                Error::<T>::WeakElectionSolution
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Computes the validator set of the next era and submits it as an unsigned transaction.
+    ///
+    /// Only runs on the validator nodes during the final session of an era, the solution
+    /// is submitted again if it scores higher than the queued one.
+    pub(crate) fn offchain_election(now: T::BlockNumber) {
+        if !sp_io::offchain::is_validator() || !Self::is_current_session_final() {
+            return;
+        }
+
+        let next_era = match Self::current_era() {
+            Some(current_era) => current_era + 1,
+            None => return,
+        };
+
+        // Avoid computing and submitting the solution in every block.
+        let lock = StorageValueRef::persistent(OFFCHAIN_ELECTION_LOCK);
+        let res = lock.mutate(
+            |last: Result<Option<(EraIndex, T::BlockNumber)>, StorageRetrievalError>| match last {
+                Ok(Some((era, at)))
+                    if era == next_era && now < at + OFFCHAIN_ELECTION_REPEAT.into() =>
+                {
+                    Err(())
+                }
+                _ => Ok((next_era, now)),
+            },
+        );
+        if res.is_err() {
+            return;
+        }
+
+        let winners = match Self::elect_next_validators() {
+            Some(winners) => winners,
+            None => return,
+        };
+        let score = Self::election_score_of(&winners);
+        if let Some(queued) = Self::queued_elected() {
+            if queued.era == next_era && score <= queued.score {
+                return;
+            }
+        }
+
+        let solution = ElectionSolution {
+            era: next_era,
+            winners,
+            score,
+        };
+        let call = Call::submit_election_solution { solution };
+
+        if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
+            log::error!(
+                target: "runtime::mining::staking",
+                "[offchain_election] Failed to submit the election solution for era {}", next_era
+            );
+        }
+    }
 }
 
 /*
@@ -4,10 +4,10 @@ use sp_std::cmp::Ordering;
 use sp_std::vec::Vec;
 
 use codec::Encode;
-use frame_support::weights::Weight;
+use frame_support::{log, storage::with_transaction, weights::Weight};
 use sp_arithmetic::traits::BaseArithmetic;
 use sp_core::crypto::UncheckedFrom;
-use sp_runtime::{traits::Hash, Perbill};
+use sp_runtime::{traits::Hash, Perbill, TransactionOutcome};
 use sp_staking::offence::{DisableStrategy, OffenceDetails, OnOffenceHandler};
 
 use xp_mining_common::{
@@ -139,6 +139,84 @@ impl<T: Config> Pallet<T> {
             .map(|(dividend, _, _, _)| dividend)
     }
 
+    /// Claims the dividends of `nominator` from at most `max_targets` nominated validators.
+    ///
+    /// Returns the number of nominations visited.
+    pub(crate) fn claim_all_dividends(nominator: &T::AccountId, max_targets: u32) -> u32 {
+        let current_block = <frame_system::Pallet<T>>::block_number();
+        let targets = match Self::claim_all_cursor_of(nominator) {
+            Some(last) => {
+                // Keys are iterated in the order of their hashed storage key, so resume
+                // right after the cursor even if the cursor itself has been removed.
+                let last_key = Nominations::<T>::hashed_key_for(nominator, &last);
+                Nominations::<T>::iter_key_prefix(nominator)
+                    .skip_while(|target| {
+                        Nominations::<T>::hashed_key_for(nominator, target) <= last_key
+                    })
+                    .take(max_targets as usize)
+                    .collect::<Vec<_>>()
+            }
+            None => Nominations::<T>::iter_key_prefix(nominator)
+                .take(max_targets as usize)
+                .collect::<Vec<_>>(),
+        };
+        let visited = targets.len() as u32;
+        // Start over from the first nomination once all the nominations are visited.
+        match targets.last() {
+            Some(last) if visited == max_targets => ClaimAllCursorOf::<T>::insert(nominator, last),
+            _ => ClaimAllCursorOf::<T>::remove(nominator),
+        }
+        for validator in targets {
+            let has_dividend = Self::is_validator(&validator)
+                && Self::compute_dividend_at(nominator, &validator, current_block)
+                    .map_or(false, |dividend| !dividend.is_zero());
+            if !has_dividend {
+                continue;
+            }
+            let res = with_transaction(|| {
+                match <Self as Claim<T::AccountId>>::claim(nominator, &validator) {
+                    Ok(()) => TransactionOutcome::Commit(Ok(())),
+                    Err(e) => TransactionOutcome::Rollback(Err(e)),
+                }
+            });
+            if let Err(e) = res {
+                log::warn!(
+                    target: "runtime::mining::staking",
+                    "[claim_all] failed to claim {:?} from {:?}: {:?}",
+                    nominator, validator, e
+                );
+            }
+        }
+        visited
+    }
+
+    /// Bonds the claimed `dividend` to the validator again.
+    ///
+    /// The dividend stays in the free balance if the validator can accept no more votes.
+    fn compound_dividend(
+        nominator: &T::AccountId,
+        validator: &T::AccountId,
+        dividend: BalanceOf<T>,
+    ) {
+        if dividend.is_zero()
+            || dividend + Self::total_locked_of(nominator) > Self::free_balance(nominator)
+        {
+            return;
+        }
+        if !Self::is_validator_bonding_itself(nominator, validator)
+            && Self::check_validator_acceptable_votes_limit(validator, dividend).is_err()
+        {
+            debug!(
+                target: "runtime::mining::staking",
+                "[compound_dividend] {:?} can accept no more votes, the dividend {:?} of {:?} remains free",
+                validator, dividend, nominator
+            );
+            return;
+        }
+        // `apply_bond` is infallible.
+        let _ = Self::apply_bond(nominator, validator, dividend);
+    }
+
     fn allocate_dividend(
         claimer: &T::AccountId,
         pot_account: &T::AccountId,
@@ -217,6 +295,10 @@ impl<T: Config> Claim<T::AccountId> for Pallet<T> {
         Self::update_claimer_vote_weight_on_claim(claimer, claimee, current_block);
         Self::update_claimee_vote_weight_on_claim(claimee, new_target_weight, current_block);
 
+        if Self::auto_compound_of(claimer) {
+            Self::compound_dividend(claimer, claimee, dividend);
+        }
+
         Ok(())
     }
 }
@@ -333,9 +415,10 @@ impl<T: Config> Pallet<T> {
 
     /// * Increment `active_era.index`,
     /// * reset `active_era.start`,
-    /// * update `BondedEras` and apply slashes.
-    fn start_era(_start_session: SessionIndex) {
-        let _active_era = ActiveEra::<T>::mutate(|active_era| {
+    /// * update `BondedEras` and apply slashes,
+    /// * prune the slashing spans of the last era.
+    fn start_era(start_session: SessionIndex) {
+        let active_era = ActiveEra::<T>::mutate(|active_era| {
             let new_index = active_era.as_ref().map(|info| info.index + 1).unwrap_or(0);
             *active_era = Some(ActiveEraInfo {
                 index: new_index,
@@ -344,6 +427,27 @@ impl<T: Config> Pallet<T> {
             });
             new_index
         });
+
+        if let Some(stale_era) = active_era.checked_sub(Self::history_depth()) {
+            Self::clear_era_history(stale_era);
+        }
+
+        // The slashing spans only cover the offences in the last era.
+        SlashingSpans::<T>::remove_all(None);
+
+        Self::apply_unapplied_slashes(active_era, start_session);
+    }
+
+    /// Returns the index of active era, which is 0 if the first era has not started.
+    pub(crate) fn active_era_index() -> EraIndex {
+        Self::active_era().map(|e| e.index).unwrap_or_default()
+    }
+
+    /// Removes the reward and slash records of the given era.
+    pub(crate) fn clear_era_history(era: EraIndex) {
+        ErasTotalReward::<T>::remove(era);
+        ErasValidatorReward::<T>::remove_prefix(era, None);
+        ErasSlash::<T>::remove_prefix(era, None);
     }
 
     /// Compute payout for era.
@@ -16,6 +16,7 @@
 mod constants;
 mod election;
 mod impls;
+mod migrations;
 mod reward;
 mod rpc;
 mod slashing;
@@ -38,8 +39,9 @@ use frame_support::{
         Currency, ExistenceRequirement, Get, LockableCurrency, ValidatorRegistration,
         WithdrawReasons,
     },
+    transactional,
 };
-use frame_system::{ensure_root, ensure_signed};
+use frame_system::{ensure_none, ensure_root, ensure_signed};
 use sp_runtime::{
     traits::{Convert, SaturatedConversion, Saturating, StaticLookup, Zero},
     DispatchResult, Perbill,
@@ -53,6 +55,7 @@ use xpallet_support::traits::TreasuryAccount;
 
 use crate::constants::*;
 
+pub use self::election::{SequentialPhragmen, ValidatorElection};
 pub use self::impls::{IdentificationTuple, SimpleValidatorRewardPotAccountDeterminer};
 pub use self::rpc::*;
 pub use self::types::*;
@@ -71,10 +74,10 @@ pub type EraIndex = u32;
 pub mod pallet {
     use super::*;
     use frame_support::{dispatch::DispatchResult, pallet_prelude::*};
-    use frame_system::pallet_prelude::*;
+    use frame_system::{offchain::SendTransactionTypes, pallet_prelude::*};
 
     #[pallet::config]
-    pub trait Config: frame_system::Config {
+    pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
         /// The overarching event type.
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
@@ -111,15 +114,46 @@ pub mod pallet {
         /// Provide information about whether or not some
         /// validator has been registered with them
         type ValidatorRegistration: ValidatorRegistration<Self::AccountId>;
+
+        /// Election algorithm of the validators for the next era, `()` for the top-most
+        /// candidates by the total nominations.
+        type ValidatorElection: self::ValidatorElection<Self>;
+
+        /// The origin which can cancel a deferred slash.
+        type SlashCancelOrigin: EnsureOrigin<Self::Origin>;
+
+        /// A configuration for base priority of the election solution submitted
+        /// by the offchain worker.
+        type UnsignedPriority: Get<TransactionPriority>;
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
 
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if StorageVersion::get::<Pallet<T>>() < 1 {
+                let weight = migrations::migrate_to_v1::<T>();
+                STORAGE_VERSION.put::<Pallet<T>>();
+                weight
+            } else {
+                0
+            }
+        }
+
+        fn offchain_worker(now: T::BlockNumber) {
+            Self::offchain_election(now);
+        }
+    }
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Nominate the `target` with `value` of the origin account's balance locked.
@@ -239,6 +273,63 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Unlock all the frozen unbonded balances that are due across the nominations.
+        ///
+        /// At most `max_targets` nominations are visited, the next call continues from
+        /// where this one stops, so that all the nominations are visited in turn.
+        #[pallet::weight(
+            T::WeightInfo::unlock_unbonded_withdrawal().saturating_mul(*max_targets as Weight)
+        )]
+        pub fn unlock_all_due(
+            origin: OriginFor<T>,
+            #[pallet::compact] max_targets: u32,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+
+            let (visited, value) = Self::unlock_all_due_chunks(&sender, max_targets);
+            ensure!(!value.is_zero(), Error::<T>::UnbondedWithdrawalNotYetDue);
+
+            Self::apply_unlock_unbonded_withdrawal(&sender, value);
+
+            Self::deposit_event(Event::<T>::Withdrawn(sender, value));
+            Ok(
+                Some(T::WeightInfo::unlock_unbonded_withdrawal().saturating_mul(visited as Weight))
+                    .into(),
+            )
+        }
+
+        /// Unbond the `value` and unlock it immediately, paying `InstantUnbondPenalty`
+        /// of it to the treasury.
+        ///
+        /// The validator self-bonded balance can not be unbonded instantly.
+        #[pallet::weight(T::WeightInfo::instant_unbond())]
+        #[transactional]
+        pub fn instant_unbond(
+            origin: OriginFor<T>,
+            target: <T::Lookup as StaticLookup>::Source,
+            #[pallet::compact] value: BalanceOf<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            let target = T::Lookup::lookup(target)?;
+
+            let penalty_ratio =
+                Self::instant_unbond_penalty().ok_or(Error::<T>::InstantUnbondDisabled)?;
+            ensure!(!value.is_zero(), Error::<T>::ZeroBalance);
+            ensure!(Self::is_validator(&target), Error::<T>::NotValidator);
+            ensure!(
+                !Self::is_validator_bonding_itself(&sender, &target),
+                Error::<T>::InstantUnbondSelfBondedNotAllowed
+            );
+            ensure!(
+                value <= Self::bonded_to(&sender, &target),
+                Error::<T>::InvalidUnbondBalance
+            );
+
+            let penalty = penalty_ratio * value;
+            Self::apply_instant_unbond(&sender, &target, value, penalty)?;
+            Ok(())
+        }
+
         /// Claim the staking reward given the `target` validator.
         #[pallet::weight(T::WeightInfo::claim())]
         pub fn claim(
@@ -254,6 +345,72 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Claim the staking rewards from all the validators nominated by the origin account.
+        ///
+        /// At most `max_targets` nominations are visited, the validators that have
+        /// no dividend or fail to be claimed are skipped. The next call continues from
+        /// where this one stops, so that all the nominations are visited in turn.
+        #[pallet::weight(T::WeightInfo::claim().saturating_mul(*max_targets as Weight))]
+        pub fn claim_all(
+            origin: OriginFor<T>,
+            #[pallet::compact] max_targets: u32,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            let visited = Self::claim_all_dividends(&sender, max_targets);
+            Ok(Some(T::WeightInfo::claim().saturating_mul(visited as Weight)).into())
+        }
+
+        /// Set whether the staking dividend of the origin account is bonded again
+        /// to the same validator automatically on claim.
+        #[pallet::weight(T::WeightInfo::set_auto_compound())]
+        pub fn set_auto_compound(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            if enabled {
+                AutoCompoundOf::<T>::insert(&sender, true);
+            } else {
+                AutoCompoundOf::<T>::remove(&sender);
+            }
+            Self::deposit_event(Event::<T>::AutoCompoundSet(sender, enabled));
+            Ok(())
+        }
+
+        /// Set the commission of the origin validator.
+        ///
+        /// The commission can be changed once per era, by no more than `MaximumCommissionChange`
+        /// and up to `MaximumCommission`.
+        #[pallet::weight(T::WeightInfo::set_commission())]
+        pub fn set_commission(origin: OriginFor<T>, new: Perbill) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(Self::is_validator(&sender), Error::<T>::NotValidator);
+            ensure!(
+                new <= Self::maximum_commission(),
+                Error::<T>::CommissionTooHigh
+            );
+
+            let current_era = Self::current_era().unwrap_or_default();
+            Validators::<T>::try_mutate(&sender, |validator| -> DispatchResult {
+                ensure!(
+                    validator.last_commission_change != Some(current_era),
+                    Error::<T>::NoMoreCommissionChange
+                );
+                let change = if new > validator.commission {
+                    new.saturating_sub(validator.commission)
+                } else {
+                    validator.commission.saturating_sub(new)
+                };
+                ensure!(
+                    change <= Self::maximum_commission_change(),
+                    Error::<T>::CommissionChangeTooLarge
+                );
+                validator.commission = new;
+                validator.last_commission_change = Some(current_era);
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::<T>::CommissionChanged(sender, new));
+            Ok(())
+        }
+
         /// Declare the desire to validate for the origin account.
         #[pallet::weight(T::WeightInfo::validate())]
         pub fn validate(origin: OriginFor<T>) -> DispatchResult {
@@ -365,6 +522,126 @@ pub mod pallet {
             Ok(())
         }
 
+        #[pallet::weight(T::WeightInfo::set_maximum_commission())]
+        pub fn set_maximum_commission(origin: OriginFor<T>, new: Perbill) -> DispatchResult {
+            ensure_root(origin)?;
+            MaximumCommission::<T>::put(new);
+            Ok(())
+        }
+
+        #[pallet::weight(T::WeightInfo::set_maximum_commission_change())]
+        pub fn set_maximum_commission_change(origin: OriginFor<T>, new: Perbill) -> DispatchResult {
+            ensure_root(origin)?;
+            MaximumCommissionChange::<T>::put(new);
+            Ok(())
+        }
+
+        /// Set the number of eras to keep the reward and slash history.
+        ///
+        /// The history of the eras dropped by a lower depth is cleared at once,
+        /// `max_cleared_eras` is the upper bound of the number of the cleared eras.
+        #[pallet::weight(T::WeightInfo::set_history_depth(*max_cleared_eras))]
+        pub fn set_history_depth(
+            origin: OriginFor<T>,
+            #[pallet::compact] new: u32,
+            #[pallet::compact] max_cleared_eras: u32,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(new > 0, Error::<T>::ZeroHistoryDepth);
+
+            if let Some(active_era) = Self::active_era() {
+                let first_kept_era =
+                    |depth: u32| active_era.index.checked_sub(depth).map_or(0, |era| era + 1);
+                let cleared_eras = first_kept_era(Self::history_depth())..first_kept_era(new);
+                ensure!(
+                    cleared_eras.len() as u32 <= max_cleared_eras,
+                    Error::<T>::TooManyClearedEras
+                );
+                cleared_eras.for_each(Self::clear_era_history);
+            }
+
+            HistoryDepth::<T>::put(new);
+            Ok(())
+        }
+
+        /// Set the number of eras that the slashes are deferred by, zero to apply immediately.
+        #[pallet::weight(T::WeightInfo::set_slash_defer_duration())]
+        pub fn set_slash_defer_duration(
+            origin: OriginFor<T>,
+            #[pallet::compact] new: EraIndex,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            SlashDeferDuration::<T>::put(new);
+            Ok(())
+        }
+
+        /// Set the number of offences in a slashing span tolerated without penalty.
+        #[pallet::weight(T::WeightInfo::set_grace_offences())]
+        pub fn set_grace_offences(
+            origin: OriginFor<T>,
+            #[pallet::compact] new: u32,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            GraceOffences::<T>::put(new);
+            Ok(())
+        }
+
+        /// Cancel the deferred slashes that will be applied in `era`.
+        ///
+        /// `slash_indices` are the indices in `UnappliedSlashes` of `era`,
+        /// which must be sorted and unique.
+        ///
+        /// The dispatch origin of this call must be _SlashCancelOrigin_.
+        #[pallet::weight(T::WeightInfo::cancel_deferred_slash(slash_indices.len() as u32))]
+        pub fn cancel_deferred_slash(
+            origin: OriginFor<T>,
+            era: EraIndex,
+            slash_indices: Vec<u32>,
+        ) -> DispatchResult {
+            T::SlashCancelOrigin::ensure_origin(origin)?;
+
+            ensure!(!slash_indices.is_empty(), Error::<T>::EmptySlashIndices);
+            ensure!(
+                slash_indices.windows(2).all(|pair| pair[0] < pair[1]),
+                Error::<T>::SlashIndicesNotSortedAndUnique
+            );
+
+            let mut unapplied = UnappliedSlashes::<T>::get(era);
+            let last_index = slash_indices[slash_indices.len() - 1] as usize;
+            ensure!(last_index < unapplied.len(), Error::<T>::InvalidSlashIndex);
+
+            for index in slash_indices.into_iter().rev() {
+                let slash = unapplied.remove(index as usize);
+                Self::deposit_event(Event::<T>::SlashCancelled(
+                    slash.offender,
+                    slash.penalty,
+                    era,
+                ));
+            }
+
+            if unapplied.is_empty() {
+                UnappliedSlashes::<T>::remove(era);
+            } else {
+                UnappliedSlashes::<T>::insert(era, unapplied);
+            }
+
+            Ok(())
+        }
+
+        /// Set the penalty ratio of `instant_unbond`, `None` disables it.
+        #[pallet::weight(T::WeightInfo::set_instant_unbond_penalty())]
+        pub fn set_instant_unbond_penalty(
+            origin: OriginFor<T>,
+            new: Option<Perbill>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            match new {
+                Some(penalty) => InstantUnbondPenalty::<T>::put(penalty),
+                None => InstantUnbondPenalty::<T>::kill(),
+            }
+            Ok(())
+        }
+
         #[pallet::weight(T::WeightInfo::set_sessions_per_era())]
         pub fn set_sessions_per_era(
             origin: OriginFor<T>,
@@ -432,6 +709,26 @@ pub mod pallet {
             }
             Ok(())
         }
+
+        /// Submit the validator set of the next era computed by the offchain worker.
+        ///
+        /// The solution must consist of the eligible candidates at present and score higher
+        /// than the queued one, and is used when the next era begins, if all the winners are
+        /// still eligible then, otherwise the election is computed on-chain again.
+        #[pallet::weight(T::WeightInfo::submit_election_solution(
+            Self::maximum_validator_count(),
+            MAX_ELECTING_NOMINATIONS
+        ))]
+        pub fn submit_election_solution(
+            origin: OriginFor<T>,
+            solution: ElectionSolution<T::AccountId, BalanceOf<T>>,
+        ) -> DispatchResult {
+            ensure_none(origin)?;
+            Self::check_election_solution(&solution)?;
+            Self::deposit_event(Event::<T>::ElectionSolutionStored(solution.era));
+            QueuedElected::<T>::put(solution);
+            Ok(())
+        }
     }
 
     #[pallet::event]
@@ -457,6 +754,20 @@ pub mod pallet {
         ForceChilled(SessionIndex, Vec<T::AccountId>),
         /// Unlock the unbonded withdrawal by force. [account]
         ForceAllWithdrawn(T::AccountId),
+        /// The auto-compound of staking dividend was switched. [nominator, enabled]
+        AutoCompoundSet(T::AccountId, bool),
+        /// A nominator unbonded and unlocked this amount immediately. [nominator, validator, amount, penalty]
+        InstantUnbonded(T::AccountId, T::AccountId, BalanceOf<T>, BalanceOf<T>),
+        /// A validator changed the commission. [validator, commission]
+        CommissionChanged(T::AccountId, Perbill),
+        /// A slash was deferred to be applied in a future era. [offender, penalty, apply_era]
+        ///
+        /// The `Slashed` event will be emitted once it's applied.
+        SlashDeferred(T::AccountId, BalanceOf<T>, EraIndex),
+        /// A deferred slash was cancelled. [offender, penalty, apply_era]
+        SlashCancelled(T::AccountId, BalanceOf<T>, EraIndex),
+        /// An election solution computed offchain was stored. [era]
+        ElectionSolutionStored(EraIndex),
     }
 
     /// Old name generated by `decl_event`.
@@ -506,6 +817,32 @@ pub mod pallet {
         XssCheckFailed,
         /// Failed to allocate the dividend.
         AllocateDividendFailed,
+        /// The instant unbond is disabled.
+        InstantUnbondDisabled,
+        /// Can not unbond the validator self-bonded votes instantly.
+        InstantUnbondSelfBondedNotAllowed,
+        /// The commission exceeds `MaximumCommission`.
+        CommissionTooHigh,
+        /// The commission change exceeds `MaximumCommissionChange`.
+        CommissionChangeTooLarge,
+        /// The commission can only be changed once per era.
+        NoMoreCommissionChange,
+        /// No slash index is given.
+        EmptySlashIndices,
+        /// The slash indices must be sorted and unique.
+        SlashIndicesNotSortedAndUnique,
+        /// Can not find the deferred slash given the index.
+        InvalidSlashIndex,
+        /// The election solution is not for the next era.
+        StaleElectionSolution,
+        /// The election solution violates the election rules.
+        InvalidElectionSolution,
+        /// The election solution does not score higher than the queued one.
+        WeakElectionSolution,
+        /// The history depth can not be zero.
+        ZeroHistoryDepth,
+        /// The history depth change clears more eras than `max_cleared_eras`.
+        TooManyClearedEras,
     }
 
     /// The ideal number of staking participants.
@@ -639,6 +976,24 @@ pub mod pallet {
     #[pallet::getter(fn last_rebond_of)]
     pub type LastRebondOf<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, T::BlockNumber>;
 
+    /// The nominators whose staking dividend is bonded again on claim.
+    #[pallet::storage]
+    #[pallet::getter(fn auto_compound_of)]
+    pub type AutoCompoundOf<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, bool, ValueQuery>;
+
+    /// The map from nominator to the last nominee visited by `claim_all`,
+    /// the next `claim_all` continues from the nominee after it.
+    #[pallet::storage]
+    #[pallet::getter(fn claim_all_cursor_of)]
+    pub type ClaimAllCursorOf<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, T::AccountId>;
+
+    /// The map from nominator to the last nominee visited by `unlock_all_due`,
+    /// the next `unlock_all_due` continues from the nominee after it.
+    #[pallet::storage]
+    #[pallet::getter(fn unlock_all_cursor_of)]
+    pub type UnlockAllCursorOf<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, T::AccountId>;
+
     /// All kinds of locked balances of an account in Staking.
     #[pallet::storage]
     #[pallet::getter(fn locks)]
@@ -688,6 +1043,12 @@ pub mod pallet {
     pub type IsCurrentSessionFinal<T: Config> =
         StorageValue<_, bool, ValueQuery, DefaultForIsCurrentSessionFinal>;
 
+    /// Validator set of the next era submitted by the offchain worker.
+    #[pallet::storage]
+    #[pallet::getter(fn queued_elected)]
+    pub type QueuedElected<T: Config> =
+        StorageValue<_, ElectionSolution<T::AccountId, BalanceOf<T>>>;
+
     /// Offenders reported in last session.
     #[pallet::storage]
     #[pallet::getter(fn session_offenders)]
@@ -698,6 +1059,107 @@ pub mod pallet {
     #[pallet::getter(fn minimum_penalty)]
     pub type MinimumPenalty<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
+    #[pallet::type_value]
+    pub fn DefaultForMaximumCommission() -> Perbill {
+        DEFAULT_MAXIMUM_COMMISSION
+    }
+
+    /// The maximum commission that a validator can set.
+    #[pallet::storage]
+    #[pallet::getter(fn maximum_commission)]
+    pub type MaximumCommission<T: Config> =
+        StorageValue<_, Perbill, ValueQuery, DefaultForMaximumCommission>;
+
+    #[pallet::type_value]
+    pub fn DefaultForMaximumCommissionChange() -> Perbill {
+        DEFAULT_MAXIMUM_COMMISSION_CHANGE
+    }
+
+    /// The maximum commission change of a validator per era.
+    #[pallet::storage]
+    #[pallet::getter(fn maximum_commission_change)]
+    pub type MaximumCommissionChange<T: Config> =
+        StorageValue<_, Perbill, ValueQuery, DefaultForMaximumCommissionChange>;
+
+    #[pallet::type_value]
+    pub fn DefaultForHistoryDepth() -> u32 {
+        DEFAULT_HISTORY_DEPTH
+    }
+
+    /// Number of eras to keep in history.
+    ///
+    /// The reward and slash records of the eras older than `active_era - HistoryDepth`
+    /// are removed once a new era starts.
+    #[pallet::storage]
+    #[pallet::getter(fn history_depth)]
+    pub type HistoryDepth<T: Config> = StorageValue<_, u32, ValueQuery, DefaultForHistoryDepth>;
+
+    /// Total session rewards minted in an era.
+    #[pallet::storage]
+    #[pallet::getter(fn eras_total_reward)]
+    pub type ErasTotalReward<T: Config> =
+        StorageMap<_, Twox64Concat, EraIndex, BalanceOf<T>, ValueQuery>;
+
+    /// Session rewards of the validators in an era.
+    #[pallet::storage]
+    #[pallet::getter(fn eras_validator_reward)]
+    pub type ErasValidatorReward<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        EraIndex,
+        Twox64Concat,
+        T::AccountId,
+        ValidatorEraReward<BalanceOf<T>>,
+        ValueQuery,
+    >;
+
+    /// Slashes of the validators in an era.
+    #[pallet::storage]
+    #[pallet::getter(fn eras_slash)]
+    pub type ErasSlash<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        EraIndex,
+        Twox64Concat,
+        T::AccountId,
+        SlashRecord<BalanceOf<T>>,
+        ValueQuery,
+    >;
+
+    /// Number of eras that the slashes are deferred by, during which they can be cancelled
+    /// by `SlashCancelOrigin`. The slashes are applied immediately if zero.
+    #[pallet::storage]
+    #[pallet::getter(fn slash_defer_duration)]
+    pub type SlashDeferDuration<T: Config> = StorageValue<_, EraIndex, ValueQuery>;
+
+    /// Number of offences in a slashing span tolerated without penalty, which gives
+    /// the validators a grace period to recover from the unresponsiveness.
+    #[pallet::storage]
+    #[pallet::getter(fn grace_offences)]
+    pub type GraceOffences<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// The slashing span of each offender in the current era.
+    #[pallet::storage]
+    #[pallet::getter(fn slashing_spans)]
+    pub type SlashingSpans<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, SlashingSpan<BalanceOf<T>>, ValueQuery>;
+
+    /// All the deferred slashes that will be applied in an era.
+    #[pallet::storage]
+    #[pallet::getter(fn unapplied_slashes)]
+    pub type UnappliedSlashes<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        EraIndex,
+        Vec<UnappliedSlash<T::AccountId, BalanceOf<T>>>,
+        ValueQuery,
+    >;
+
+    /// Penalty ratio of the instant unbond which goes to the treasury, disabled if none.
+    #[pallet::storage]
+    #[pallet::getter(fn instant_unbond_penalty)]
+    pub type InstantUnbondPenalty<T: Config> = StorageValue<_, Perbill>;
+
     /// Immortal validators will always be elected if any.
     ///
     /// Immortals will be intialized from the genesis validators.
@@ -705,6 +1167,38 @@ pub mod pallet {
     #[pallet::getter(fn immortals)]
     pub(super) type Immortals<T: Config> = StorageValue<_, Vec<T::AccountId>>;
 
+    #[pallet::validate_unsigned]
+    impl<T: Config> ValidateUnsigned for Pallet<T> {
+        type Call = Call<T>;
+
+        fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+            if let Call::submit_election_solution { solution } = call {
+                // Only accept the solution from the local offchain worker or the block author.
+                match source {
+                    TransactionSource::Local | TransactionSource::InBlock => {}
+                    _ => return InvalidTransaction::Call.into(),
+                }
+
+                match Self::check_election_solution(solution) {
+                    Ok(()) => {}
+                    Err(Error::<T>::StaleElectionSolution) => {
+                        return InvalidTransaction::Stale.into()
+                    }
+                    Err(_) => return InvalidTransaction::Call.into(),
+                }
+
+                ValidTransaction::with_tag_prefix("XStakingOffchainElection")
+                    .priority(T::UnsignedPriority::get())
+                    .and_provides((solution.era, &solution.winners))
+                    .longevity(T::SessionDuration::get().saturated_into::<u64>())
+                    .propagate(false)
+                    .build()
+            } else {
+                InvalidTransaction::Call.into()
+            }
+        }
+    }
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub validator_count: u32,
@@ -1094,14 +1588,88 @@ impl<T: Config> Pallet<T> {
             value <= Self::bonded_to(sender, target),
             Error::<T>::InvalidUnbondBalance
         );
+        let current_block = <frame_system::Pallet<T>>::block_number();
+        let locked_until = current_block + Self::bonding_duration_for(sender, target);
+        let mut unbonded_chunks = Self::unbonded_chunks_of(sender, target);
+        Self::merge_unbonded_chunks(&mut unbonded_chunks, current_block);
         ensure!(
-            Self::unbonded_chunks_of(sender, target).len()
-                < Self::maximum_unbonded_chunk_size() as usize,
+            unbonded_chunks.len() < Self::maximum_unbonded_chunk_size() as usize
+                || unbonded_chunks
+                    .iter()
+                    .any(|chunk| chunk.locked_until == locked_until),
             Error::<T>::NoMoreUnbondChunks
         );
         Ok(())
     }
 
+    /// Merges the unbonded chunks that are due into one, as well as the chunks
+    /// sharing the same `locked_until`.
+    fn merge_unbonded_chunks(
+        unbonded_chunks: &mut Vec<Unbonded<BalanceOf<T>, T::BlockNumber>>,
+        current_block: T::BlockNumber,
+    ) {
+        let mut merged: Vec<Unbonded<BalanceOf<T>, T::BlockNumber>> =
+            Vec::with_capacity(unbonded_chunks.len());
+        for chunk in unbonded_chunks.drain(..) {
+            let is_due = current_block > chunk.locked_until;
+            match merged.iter_mut().find(|x| {
+                x.locked_until == chunk.locked_until || (is_due && current_block > x.locked_until)
+            }) {
+                Some(x) => {
+                    x.value += chunk.value;
+                    x.locked_until = x.locked_until.max(chunk.locked_until);
+                }
+                None => merged.push(chunk),
+            }
+        }
+        *unbonded_chunks = merged;
+    }
+
+    /// Takes out all the due unbonded chunks from at most `max_targets` nominations,
+    /// starting after the nomination visited last time.
+    ///
+    /// Returns the number of nominations visited and the total due value.
+    fn unlock_all_due_chunks(who: &T::AccountId, max_targets: u32) -> (u32, BalanceOf<T>) {
+        let current_block = <frame_system::Pallet<T>>::block_number();
+        let nominations = match Self::unlock_all_cursor_of(who) {
+            Some(last) => {
+                let last_key = Nominations::<T>::hashed_key_for(who, &last);
+                Nominations::<T>::iter_prefix(who)
+                    .skip_while(|(target, _)| {
+                        Nominations::<T>::hashed_key_for(who, target) <= last_key
+                    })
+                    .take(max_targets as usize)
+                    .collect::<Vec<_>>()
+            }
+            None => Nominations::<T>::iter_prefix(who)
+                .take(max_targets as usize)
+                .collect::<Vec<_>>(),
+        };
+        let visited = nominations.len() as u32;
+        // Start over from the first nomination once all the nominations are visited.
+        match nominations.last() {
+            Some((last, _)) if visited == max_targets => UnlockAllCursorOf::<T>::insert(who, last),
+            _ => UnlockAllCursorOf::<T>::remove(who),
+        }
+
+        let mut total_due: BalanceOf<T> = Zero::zero();
+        for (target, mut nominator) in nominations {
+            let (due, pending): (Vec<_>, Vec<_>) = nominator
+                .unbonded_chunks
+                .drain(..)
+                .partition(|chunk| current_block > chunk.locked_until);
+            if due.is_empty() {
+                continue;
+            }
+            nominator.unbonded_chunks = pending;
+            total_due = due
+                .into_iter()
+                .fold(total_due, |acc, chunk| acc + chunk.value);
+            Nominations::<T>::insert(who, &target, nominator);
+        }
+        (visited, total_due)
+    }
+
     /// `unbond` only triggers the internal change of Staking locked type.
     fn unbond_reserve(who: &T::AccountId, value: BalanceOf<T>) -> Result<(), Error<T>> {
         Locks::<T>::mutate(who, |locks| {
@@ -1152,6 +1720,7 @@ impl<T: Config> Pallet<T> {
             ValidatorProfile {
                 registered_at: current_block,
                 referral_id,
+                commission: DEFAULT_VALIDATOR_COMMISSION,
                 ..Default::default()
             },
         );
@@ -1198,7 +1767,9 @@ impl<T: Config> Pallet<T> {
         value: BalanceOf<T>,
         locked_until: T::BlockNumber,
     ) {
+        let current_block = <frame_system::Pallet<T>>::block_number();
         Nominations::<T>::mutate(who, target, |nominator| {
+            Self::merge_unbonded_chunks(&mut nominator.unbonded_chunks, current_block);
             if let Some(idx) = nominator
                 .unbonded_chunks
                 .iter()
@@ -1245,6 +1816,39 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    fn apply_instant_unbond(
+        who: &T::AccountId,
+        target: &T::AccountId,
+        value: BalanceOf<T>,
+        penalty: BalanceOf<T>,
+    ) -> DispatchResult {
+        Locks::<T>::mutate(who, |locks| {
+            let bonded = locks.entry(LockedType::Bonded).or_default();
+            *bonded -= value;
+            // All the bonded funds have been unbonded.
+            if bonded.is_zero() {
+                locks.remove(&LockedType::Bonded);
+            }
+        });
+        Self::set_lock(who, Self::total_locked_of(who));
+
+        Self::update_vote_weight(who, target, Delta::Sub(value));
+
+        if !penalty.is_zero() {
+            let treasury_account =
+                T::TreasuryAccount::treasury_account().expect("TreasuryAccount is some; qed");
+            Self::transfer(who, &treasury_account, penalty)?;
+        }
+
+        Self::deposit_event(Event::<T>::InstantUnbonded(
+            who.clone(),
+            target.clone(),
+            value,
+            penalty,
+        ));
+        Ok(())
+    }
+
     fn apply_unlock_unbonded_withdrawal(who: &T::AccountId, value: BalanceOf<T>) {
         let new_bonded = Self::total_locked_of(who) - value;
         Self::set_lock(who, new_bonded);
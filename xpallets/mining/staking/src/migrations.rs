@@ -0,0 +1,39 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode};
+use frame_support::{log::info, traits::Get, weights::Weight};
+use sp_runtime::RuntimeDebug;
+
+use chainx_primitives::ReferralId;
+
+use crate::{constants::DEFAULT_VALIDATOR_COMMISSION, Config, ValidatorProfile, Validators};
+
+/// The validator profile before the commission was introduced.
+#[derive(Encode, Decode, RuntimeDebug)]
+struct OldValidatorProfile<BlockNumber> {
+    registered_at: BlockNumber,
+    is_chilled: bool,
+    last_chilled: Option<BlockNumber>,
+    referral_id: ReferralId,
+}
+
+/// Migrates the validator profiles to the one with the default commission.
+pub fn migrate_to_v1<T: Config>() -> Weight {
+    let mut translated = 0u64;
+    Validators::<T>::translate::<OldValidatorProfile<T::BlockNumber>, _>(|_, old| {
+        translated += 1;
+        Some(ValidatorProfile {
+            registered_at: old.registered_at,
+            is_chilled: old.is_chilled,
+            last_chilled: old.last_chilled,
+            referral_id: old.referral_id,
+            commission: DEFAULT_VALIDATOR_COMMISSION,
+            last_commission_change: None,
+        })
+    });
+    info!(
+        target: "runtime::mining::staking",
+        "[migrate_to_v1] {} validator profiles translated", translated
+    );
+    T::DbWeight::get().reads_writes(translated, translated)
+}
@@ -193,6 +193,7 @@ parameter_types! {
     pub const SessionDuration: BlockNumber = 50;
     pub const MinimumReferralId: u32 = 2;
     pub const MaximumReferralId: u32 = 12;
+    pub const StakingUnsignedPriority: u64 = u64::MAX / 2;
 }
 
 pub struct Registration;
@@ -213,9 +214,20 @@ impl Config for Test {
     type TreasuryAccount = DummyTreasuryAccount;
     type DetermineRewardPotAccount = DummyStakingRewardPotAccountDeterminer;
     type ValidatorRegistration = Registration;
+    type ValidatorElection = ();
+    type SlashCancelOrigin = frame_system::EnsureRoot<AccountId>;
+    type UnsignedPriority = StakingUnsignedPriority;
     type WeightInfo = ();
 }
 
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
+where
+    Call: From<C>,
+{
+    type OverarchingCall = Call;
+    type Extrinsic = UncheckedExtrinsic;
+}
+
 thread_local! {
     static SESSION: RefCell<(Vec<AccountId>, HashSet<AccountId>)> = RefCell::new(Default::default());
     static SESSION_PER_ERA: RefCell<SessionIndex> = RefCell::new(3);
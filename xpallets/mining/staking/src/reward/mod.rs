@@ -3,6 +3,7 @@
 use super::*;
 #[allow(unused_imports)]
 use micromath::F32Ext;
+use sp_runtime::PerThing;
 use sp_std::vec::Vec;
 
 mod proposal09;
@@ -54,15 +55,24 @@ impl<T: Config> Pallet<T> {
     ///
     /// Add the reward to their balance, and their reward pot, pro-rata.
     fn apply_reward_validator(who: &T::AccountId, reward: BalanceOf<T>) {
-        // Validator themselves can only directly gain 20%, the rest 80% is for the reward pot.
-        let off_the_table = reward.saturated_into::<BalanceOf<T>>() / 5u32.saturated_into();
-
-        // Issue the rest 80% to validator's reward pot.
+        // Validator themselves can only directly gain the commission, the rest is for the reward pot.
+        // The commission set before `MaximumCommission` was lowered is capped by the new maximum.
+        let commission = Validators::<T>::get(who)
+            .commission
+            .min(Self::maximum_commission());
+        let off_the_table = commission.mul_floor(reward);
+
+        // Issue the rest to validator's reward pot.
         let to_reward_pot = (reward - off_the_table).saturated_into();
         let reward_pot = T::DetermineRewardPotAccount::reward_pot_account_for(who);
 
         Self::mint_for_validator(who, off_the_table, &reward_pot, to_reward_pot);
 
+        ErasValidatorReward::<T>::mutate(Self::active_era_index(), who, |era_reward| {
+            era_reward.to_validator += off_the_table;
+            era_reward.to_reward_pot += to_reward_pot;
+        });
+
         frame_support::log::debug!(
             target: "runtime::mining::staking",
             "� Mint validator({:?}):{:?}, reward_pot({:?}):{:?}",
@@ -86,6 +96,10 @@ impl<T: Config> Pallet<T> {
     pub(crate) fn distribute_session_reward() -> Vec<(T::AccountId, BalanceOf<T>)> {
         let session_reward = Self::this_session_reward();
 
+        ErasTotalReward::<T>::mutate(Self::active_era_index(), |total| {
+            *total += session_reward;
+        });
+
         Self::distribute_session_reward_impl_09(session_reward)
     }
 }
@@ -11,8 +11,8 @@ use sp_runtime::RuntimeDebug;
 use xp_mining_common::RewardPotAccountFor;
 
 use crate::{
-    types::*, BalanceOf, Config, LastRebondOf, Nominations, Pallet, SessionInterface,
-    ValidatorLedgers, Validators,
+    types::*, BalanceOf, Config, EraIndex, ErasSlash, ErasTotalReward, ErasValidatorReward,
+    LastRebondOf, Nominations, Pallet, SessionInterface, ValidatorLedgers, Validators,
 };
 
 /// Total information about a validator.
@@ -45,6 +45,29 @@ pub struct NominatorInfo<BlockNumber> {
     pub last_rebond: Option<BlockNumber>,
 }
 
+/// Reward distribution and slashes of an era.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct EraRewardInfo<AccountId: Ord, Balance> {
+    /// Total session rewards minted in this era.
+    pub total_reward: Balance,
+    /// Session rewards of each validator.
+    pub validators: BTreeMap<AccountId, ValidatorEraReward<Balance>>,
+    /// Slashes of each offender.
+    pub slashes: BTreeMap<AccountId, SlashRecord<Balance>>,
+}
+
+/// Reward and slash of a validator in an era.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ValidatorEraRecord<Balance> {
+    pub era: EraIndex,
+    pub reward: ValidatorEraReward<Balance>,
+    pub slash: SlashRecord<Balance>,
+}
+
 impl<T: Config> Pallet<T> {
     pub fn validators_info(
     ) -> Vec<ValidatorInfo<T::AccountId, BalanceOf<T>, VoteWeight, T::BlockNumber>> {
@@ -96,6 +119,31 @@ impl<T: Config> Pallet<T> {
         let last_rebond = LastRebondOf::<T>::get(&who);
         NominatorInfo { last_rebond }
     }
+
+    pub fn era_reward_history(era: EraIndex) -> EraRewardInfo<T::AccountId, BalanceOf<T>> {
+        EraRewardInfo {
+            total_reward: ErasTotalReward::<T>::get(era),
+            validators: ErasValidatorReward::<T>::iter_prefix(era).collect(),
+            slashes: ErasSlash::<T>::iter_prefix(era).collect(),
+        }
+    }
+
+    /// Returns the records of the given validator in the eras still kept in history.
+    pub fn validator_era_history(who: T::AccountId) -> Vec<ValidatorEraRecord<BalanceOf<T>>> {
+        let active_era = Self::active_era_index();
+        let first_era = active_era.saturating_sub(Self::history_depth());
+        (first_era..=active_era)
+            .filter(|era| {
+                ErasValidatorReward::<T>::contains_key(era, &who)
+                    || ErasSlash::<T>::contains_key(era, &who)
+            })
+            .map(|era| ValidatorEraRecord {
+                era,
+                reward: ErasValidatorReward::<T>::get(era, &who),
+                slash: ErasSlash::<T>::get(era, &who),
+            })
+            .collect()
+    }
 }
 
 /*
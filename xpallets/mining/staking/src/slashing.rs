@@ -8,17 +8,14 @@ use super::*;
 impl<T: Config> Pallet<T> {
     /// Returns the force chilled offenders if any after applying the slashings.
     ///
-    /// The slashed balances will be moved to the treasury.
+    /// The slashes are deferred to `active_era + SlashDeferDuration` if the duration is not
+    /// zero, otherwise the slashed balances will be moved to the treasury immediately.
     pub(crate) fn slash_offenders_in_session(
         offenders: BTreeMap<T::AccountId, Perbill>,
         validator_rewards: Vec<(T::AccountId, BalanceOf<T>)>,
     ) -> Vec<T::AccountId> {
         let validator_rewards = validator_rewards.into_iter().collect::<BTreeMap<_, _>>();
 
-        let treasury_account =
-            T::TreasuryAccount::treasury_account().expect("TreasuryAccount is some; qed");
-        let slasher = Slasher::<T>::new(treasury_account);
-
         let minimum_penalty = Self::minimum_penalty();
         let calc_base_slash = |offender: &T::AccountId, slash_fraction: Perbill| {
             // https://github.com/paritytech/substrate/blob/c60f00840034017d4b7e6d20bd4fcf9a3f5b529a/frame/im-online/src/lib.rs#L773
@@ -31,6 +28,124 @@ impl<T: Config> Pallet<T> {
             }
         };
 
+        let active_era = Self::active_era_index();
+        let defer_duration = Self::slash_defer_duration();
+
+        let mut slashes = Vec::new();
+        for (offender, slash_fraction) in offenders {
+            let base_slash = calc_base_slash(&offender, slash_fraction);
+            let penalty = validator_rewards
+                .get(&offender)
+                .copied()
+                .map(|reward| reward + base_slash)
+                .unwrap_or(base_slash)
+                .max(minimum_penalty);
+
+            let penalty = Self::uncounted_penalty_in_span(&offender, active_era, penalty);
+            if penalty.is_zero() {
+                debug!(
+                    target: "runtime::mining::staking",
+                    "The offence of {:?} has been counted in the current slashing span", offender
+                );
+                continue;
+            }
+
+            if defer_duration.is_zero() {
+                slashes.push((offender, penalty, active_era));
+            } else {
+                let apply_era = active_era.saturating_add(defer_duration);
+                UnappliedSlashes::<T>::append(
+                    apply_era,
+                    UnappliedSlash {
+                        offender: offender.clone(),
+                        penalty,
+                        reported_era: active_era,
+                    },
+                );
+                Self::deposit_event(Event::<T>::SlashDeferred(offender, penalty, apply_era));
+            }
+        }
+
+        Self::apply_slashes(slashes)
+    }
+
+    /// Returns the part of `penalty` that has not been counted in the current slashing span
+    /// of the offender.
+    ///
+    /// A slashing span covers the offences of a validator in an era, only the largest
+    /// penalty is applied in a span and the first `GraceOffences` offences are tolerated.
+    fn uncounted_penalty_in_span(
+        offender: &T::AccountId,
+        active_era: EraIndex,
+        penalty: BalanceOf<T>,
+    ) -> BalanceOf<T> {
+        let grace_offences = Self::grace_offences();
+        SlashingSpans::<T>::mutate(offender, |span| {
+            if span.offences == 0 || span.start != active_era {
+                *span = SlashingSpan {
+                    start: active_era,
+                    offences: 0,
+                    penalty: Zero::zero(),
+                };
+            }
+
+            span.offences += 1;
+            if span.offences <= grace_offences {
+                return Zero::zero();
+            }
+
+            let uncounted = penalty.saturating_sub(span.penalty);
+            span.penalty = span.penalty.max(penalty);
+            uncounted
+        })
+    }
+
+    /// Applies the slashes deferred to the given era.
+    pub(crate) fn apply_unapplied_slashes(era: EraIndex, start_session: SessionIndex) {
+        let slashes = UnappliedSlashes::<T>::take(era)
+            .into_iter()
+            .map(|slash| (slash.offender, slash.penalty, slash.reported_era))
+            .collect::<Vec<_>>();
+
+        if slashes.is_empty() {
+            return;
+        }
+
+        let force_chilled = Self::apply_slashes(slashes);
+        if !force_chilled.is_empty() {
+            Self::deposit_event(Event::<T>::ForceChilled(start_session, force_chilled));
+            Self::ensure_new_era();
+        }
+    }
+
+    /// Returns the force chilled offenders if any after slashing their reward pots.
+    ///
+    /// Each slash is given with the era in which the offence was reported, where the slash
+    /// is recorded unless the era is no longer kept in history.
+    ///
+    /// The slashing span of an offender ends once it's force chilled.
+    fn apply_slashes(slashes: Vec<(T::AccountId, BalanceOf<T>, EraIndex)>) -> Vec<T::AccountId> {
+        let treasury_account =
+            T::TreasuryAccount::treasury_account().expect("TreasuryAccount is some; qed");
+        let slasher = Slasher::<T>::new(treasury_account);
+
+        let active_era = Self::active_era_index();
+        let history_depth = Self::history_depth();
+        let record_slash = |offender: &T::AccountId,
+                            reported_era: EraIndex,
+                            penalty: BalanceOf<T>,
+                            slashed: BalanceOf<T>,
+                            force_chilled: bool| {
+            if active_era.saturating_sub(reported_era) >= history_depth {
+                return;
+            }
+            ErasSlash::<T>::mutate(reported_era, offender, |record| {
+                record.penalty += penalty;
+                record.slashed += slashed;
+                record.force_chilled |= force_chilled;
+            });
+        };
+
         let minimum_validator_count = Self::reasonable_minimum_validator_count() as usize;
         let mut active_count = Self::active_validator_set().count();
         let mut chill_offender_safe = |offender: T::AccountId| {
@@ -38,6 +153,7 @@ impl<T: Config> Pallet<T> {
             // but we must avoid the over-slashing, ensure have the minimum active validators.
             if active_count > minimum_validator_count {
                 Self::apply_force_chilled(&offender);
+                SlashingSpans::<T>::remove(&offender);
                 active_count -= 1;
                 Some(offender)
             } else {
@@ -45,23 +161,17 @@ impl<T: Config> Pallet<T> {
             }
         };
 
-        offenders
+        slashes
             .into_iter()
-            .flat_map(|(offender, slash_fraction)| {
-                let base_slash = calc_base_slash(&offender, slash_fraction);
-                let penalty = validator_rewards
-                    .get(&offender)
-                    .copied()
-                    .map(|reward| reward + base_slash)
-                    .unwrap_or(base_slash)
-                    .max(minimum_penalty);
+            .flat_map(|(offender, penalty, reported_era)| {
                 match slasher.try_slash(&offender, penalty) {
-                    SlashOutcome::Slashed(_) => {
+                    SlashOutcome::Slashed(slashed) => {
                         debug!(
                             target: "runtime::mining::staking",
-                            "Slash the offender:{:?} for penalty {:?} by the given slash_fraction:{:?} successfully",
-                            offender, penalty, slash_fraction
+                            "Slash the offender:{:?} for penalty {:?} successfully",
+                            offender, penalty
                         );
+                        record_slash(&offender, reported_era, penalty, slashed, false);
                         None
                     }
                     SlashOutcome::InsufficientSlash(actual_slashed) => {
@@ -70,7 +180,15 @@ impl<T: Config> Pallet<T> {
                             "Insufficient reward pot balance of {:?}, actual slashed:{:?}",
                             offender, actual_slashed
                         );
-                        chill_offender_safe(offender)
+                        let force_chilled = chill_offender_safe(offender.clone());
+                        record_slash(
+                            &offender,
+                            reported_era,
+                            penalty,
+                            actual_slashed,
+                            force_chilled.is_some(),
+                        );
+                        force_chilled
                     }
                     SlashOutcome::SlashFailed(e) => {
                         debug!(
@@ -82,7 +200,15 @@ impl<T: Config> Pallet<T> {
                         //
                         // TODO: Reconsider this once https://github.com/paritytech/substrate/pull/7127
                         // is merged.
-                        chill_offender_safe(offender)
+                        let force_chilled = chill_offender_safe(offender.clone());
+                        record_slash(
+                            &offender,
+                            reported_era,
+                            penalty,
+                            Zero::zero(),
+                            force_chilled.is_some(),
+                        );
+                        force_chilled
                     }
                 }
             })
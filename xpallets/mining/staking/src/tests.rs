@@ -410,6 +410,152 @@ fn withdraw_unbond_should_work() {
     });
 }
 
+#[test]
+fn unlock_all_due_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XStaking::set_bonding_duration(Origin::root(), 2));
+
+        // Block 2
+        t_system_block_number_inc(1);
+        assert_ok!(t_bond(1, 2, 10));
+        assert_ok!(t_bond(1, 3, 10));
+
+        // Block 3
+        t_system_block_number_inc(1);
+        assert_ok!(t_unbond(1, 2, 5));
+        assert_ok!(t_unbond(1, 3, 5));
+
+        // Block 4
+        t_system_block_number_inc(1);
+        assert_ok!(t_unbond(1, 3, 5));
+        assert_bonded_withdrawal_locks(1, 15);
+
+        // Block 5
+        t_system_block_number_inc(1);
+        assert_err!(
+            XStaking::unlock_all_due(Origin::signed(1), 10),
+            Error::<Test>::UnbondedWithdrawalNotYetDue
+        );
+
+        // Block 6
+        t_system_block_number_inc(1);
+        let before_unlock = Balances::usable_balance(&1);
+        assert_ok!(XStaking::unlock_all_due(Origin::signed(1), 10));
+        assert_eq!(Balances::usable_balance(&1), before_unlock + 10);
+        assert_bonded_withdrawal_locks(1, 5);
+        assert_eq!(XStaking::unlock_all_cursor_of(1), None);
+        assert!(<Nominations<Test>>::get(1, 2).unbonded_chunks.is_empty());
+        assert_eq!(
+            <Nominations<Test>>::get(1, 3).unbonded_chunks,
+            vec![Unbonded {
+                value: 5,
+                locked_until: 6
+            }]
+        );
+    });
+}
+
+#[test]
+fn unbonded_chunks_should_be_merged() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XStaking::set_bonding_duration(Origin::root(), 20));
+
+        t_system_block_number_inc(1);
+        assert_ok!(t_bond(1, 2, 20));
+
+        // Block 3 ~ Block 12, locked until block 23 ~ 32.
+        for _ in 0..DEFAULT_MAXIMUM_UNBONDED_CHUNK_SIZE {
+            t_system_block_number_inc(1);
+            assert_ok!(t_unbond(1, 2, 1));
+        }
+        // Merged into the chunk locked until block 32.
+        assert_ok!(t_unbond(1, 2, 1));
+
+        t_system_block_number_inc(1);
+        assert_err!(t_unbond(1, 2, 1), Error::<Test>::NoMoreUnbondChunks);
+
+        // The chunks locked until block 23 and 24 are due and merged into one.
+        System::set_block_number(25);
+        assert_ok!(t_unbond(1, 2, 1));
+
+        let unbonded_chunks = <Nominations<Test>>::get(1, 2).unbonded_chunks;
+        assert_eq!(
+            unbonded_chunks.len(),
+            DEFAULT_MAXIMUM_UNBONDED_CHUNK_SIZE as usize
+        );
+        assert_eq!(
+            unbonded_chunks[0],
+            Unbonded {
+                value: 2,
+                locked_until: 24
+            }
+        );
+        assert_eq!(
+            unbonded_chunks[8],
+            Unbonded {
+                value: 2,
+                locked_until: 32
+            }
+        );
+        assert_eq!(
+            unbonded_chunks[9],
+            Unbonded {
+                value: 1,
+                locked_until: 45
+            }
+        );
+        assert_bonded_withdrawal_locks(1, 12);
+    });
+}
+
+#[test]
+fn instant_unbond_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        t_system_block_number_inc(1);
+        assert_ok!(t_bond(1, 2, 50));
+
+        assert_err!(
+            XStaking::instant_unbond(Origin::signed(1), 2, 50),
+            Error::<Test>::InstantUnbondDisabled
+        );
+
+        assert_ok!(XStaking::set_instant_unbond_penalty(
+            Origin::root(),
+            Some(Perbill::from_percent(10))
+        ));
+
+        assert_err!(
+            XStaking::instant_unbond(Origin::signed(1), 1, 5),
+            Error::<Test>::InstantUnbondSelfBondedNotAllowed
+        );
+        assert_err!(
+            XStaking::instant_unbond(Origin::signed(1), 2, 51),
+            Error::<Test>::InvalidUnbondBalance
+        );
+
+        t_system_block_number_inc(1);
+        let before_unbond = Balances::usable_balance(&1);
+        let treasury_balance = Balances::free_balance(&TREASURY_ACCOUNT);
+        assert_ok!(XStaking::instant_unbond(Origin::signed(1), 2, 50));
+
+        assert_eq!(Balances::usable_balance(&1), before_unbond + 50 - 5);
+        assert_eq!(
+            Balances::free_balance(&TREASURY_ACCOUNT),
+            treasury_balance + 5
+        );
+        assert_bonded_locks(1, 10);
+        assert_bonded_withdrawal_locks(1, 0);
+        assert_eq!(<Nominations<Test>>::get(1, 2).nomination, 0);
+        assert_eq!(<ValidatorLedgers<Test>>::get(2).total_nomination, 20);
+
+        // The bonded lock is removed once all the bonded balance is unbonded.
+        t_issue_pcx(666, 100);
+        assert_ok!(t_bond(666, 2, 20));
+        assert_ok!(XStaking::instant_unbond(Origin::signed(666), 2, 20));
+        assert!(!XStaking::locks(666).contains_key(&LockedType::Bonded));
+    });
+}
+
 // todo! fix
 #[ignore]
 #[test]
@@ -832,6 +978,465 @@ fn balances_reserve_should_work() {
     });
 }
 
+#[test]
+fn claim_all_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 1111;
+        t_issue_pcx(t_1, 100);
+
+        XStaking::mint(&888, (FIXED_TOTAL / 2) as u128);
+
+        assert_ok!(t_bond(t_1, 1, 10));
+        assert_ok!(t_bond(t_1, 2, 10));
+
+        t_start_session(2);
+
+        let now = System::block_number();
+        let dividend_1 = XStaking::compute_dividend_at(&t_1, &1, now).unwrap();
+        let dividend_2 = XStaking::compute_dividend_at(&t_1, &2, now).unwrap();
+        assert!(dividend_1 > 0 && dividend_2 > 0);
+
+        // Only one of the nominations is visited.
+        assert_ok!(XStaking::claim_all(Origin::signed(t_1), 1));
+        let free = XStaking::free_balance(&t_1);
+        assert!(free == 100 + dividend_1 || free == 100 + dividend_2);
+        assert!(XStaking::claim_all_cursor_of(t_1).is_some());
+
+        // The next call continues from the other nomination.
+        assert_ok!(XStaking::claim_all(Origin::signed(t_1), 1));
+        assert_eq!(XStaking::free_balance(&t_1), 100 + dividend_1 + dividend_2);
+
+        // All the nominations are visited, the cursor is reset.
+        assert_ok!(XStaking::claim_all(Origin::signed(t_1), 10));
+        assert_eq!(XStaking::claim_all_cursor_of(t_1), None);
+        assert_eq!(XStaking::free_balance(&t_1), 100 + dividend_1 + dividend_2);
+        assert_eq!(
+            XStaking::compute_dividend_at(&t_1, &1, now).unwrap_or_default(),
+            0
+        );
+        assert_eq!(
+            XStaking::compute_dividend_at(&t_1, &2, now).unwrap_or_default(),
+            0
+        );
+    });
+}
+
+#[test]
+fn auto_compound_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 1111;
+        let t_2 = 2222;
+        t_issue_pcx(t_1, 100);
+        t_issue_pcx(t_2, 1000);
+
+        XStaking::mint(&888, (FIXED_TOTAL / 2) as u128);
+
+        assert_ok!(t_bond(t_1, 1, 10));
+        // Validator 2 has self-bonded 20, it can accept no more votes then.
+        assert_ok!(t_bond(t_2, 2, 180));
+
+        assert_ok!(XStaking::set_auto_compound(Origin::signed(t_1), true));
+        assert_ok!(XStaking::set_auto_compound(Origin::signed(t_2), true));
+        assert!(XStaking::auto_compound_of(t_1));
+
+        t_start_session(2);
+
+        let now = System::block_number();
+        let dividend_1 = XStaking::compute_dividend_at(&t_1, &1, now).unwrap();
+        let dividend_2 = XStaking::compute_dividend_at(&t_2, &2, now).unwrap();
+
+        assert_ok!(XStaking::claim(Origin::signed(t_1), 1));
+        assert_eq!(XStaking::free_balance(&t_1), 100 + dividend_1);
+        assert_eq!(<Nominations<Test>>::get(t_1, 1).nomination, 10 + dividend_1);
+        assert_bonded_locks(t_1, 10 + dividend_1);
+
+        // Fall back to the free balance.
+        assert_ok!(XStaking::claim(Origin::signed(t_2), 2));
+        assert_eq!(XStaking::free_balance(&t_2), 1000 + dividend_2);
+        assert_eq!(<Nominations<Test>>::get(t_2, 2).nomination, 180);
+        assert_bonded_locks(t_2, 180);
+
+        assert_ok!(XStaking::set_auto_compound(Origin::signed(t_1), false));
+        assert!(!XStaking::auto_compound_of(t_1));
+    });
+}
+
+#[test]
+fn validator_commission_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        XStaking::mint(&888, (FIXED_TOTAL / 2) as u128);
+
+        assert_eq!(
+            XStaking::validator_info_of(1).profile.commission,
+            Perbill::from_percent(20)
+        );
+
+        assert_err!(
+            XStaking::set_commission(Origin::signed(888), Perbill::from_percent(25)),
+            Error::<Test>::NotValidator
+        );
+        assert_err!(
+            XStaking::set_commission(Origin::signed(1), Perbill::from_percent(60)),
+            Error::<Test>::CommissionTooHigh
+        );
+        assert_err!(
+            XStaking::set_commission(Origin::signed(1), Perbill::from_percent(30)),
+            Error::<Test>::CommissionChangeTooLarge
+        );
+
+        assert_ok!(XStaking::set_commission(
+            Origin::signed(1),
+            Perbill::from_percent(25)
+        ));
+        assert_eq!(
+            XStaking::validator_info_of(1).profile.commission,
+            Perbill::from_percent(25)
+        );
+        assert_err!(
+            XStaking::set_commission(Origin::signed(1), Perbill::from_percent(24)),
+            Error::<Test>::NoMoreCommissionChange
+        );
+
+        // Block 1
+        // total_staked = val(10) + val2(20) + val(30) + val(40) = 100
+        // reward pot:
+        // 1: 1_980_000_000 * 10/100 * 75% = 148_500_000
+        // 2: 1_980_000_000 * 20/100 * 80% = 316_800_000
+        t_start_session(1);
+        assert_eq!(t_reward_pot_balance(1), 148_500_000);
+        assert_eq!(t_reward_pot_balance(2), 316_800_000);
+
+        let next_era = XStaking::current_era().unwrap_or_default() + 1;
+        CurrentEra::<Test>::put(next_era);
+        assert_ok!(XStaking::set_commission(
+            Origin::signed(1),
+            Perbill::from_percent(24)
+        ));
+
+        // The commission is capped by the lowered `MaximumCommission`.
+        // 1: 1_980_000_000 * 10/100 * 90% = 178_200_000
+        assert_ok!(XStaking::set_maximum_commission(
+            Origin::root(),
+            Perbill::from_percent(10)
+        ));
+        let reward_pot_before = t_reward_pot_balance(1);
+        t_start_session(2);
+        assert_eq!(t_reward_pot_balance(1) - reward_pot_before, 178_200_000);
+    });
+}
+
+#[test]
+fn era_reward_history_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        XStaking::mint(&888, (FIXED_TOTAL / 2) as u128);
+
+        // Block 1
+        // 1: 1_980_000_000 * 10/100 = 198_000_000
+        //   20% -> validator: 39_600_000
+        //   80% -> reward pot: 158_400_000
+        t_start_session(1);
+        let era = XStaking::active_era_index();
+        let expected_reward = ValidatorEraReward {
+            to_validator: 39_600_000,
+            to_reward_pot: 158_400_000,
+        };
+
+        let history = XStaking::era_reward_history(era);
+        assert_eq!(history.total_reward, 2_500_000_000);
+        assert_eq!(history.validators.get(&1), Some(&expected_reward));
+        assert!(history.slashes.is_empty());
+
+        // Slash 10% of the reward pot of validator 1.
+        let offenders = vec![(1, Perbill::from_percent(10))].into_iter().collect();
+        assert!(XStaking::slash_offenders_in_session(offenders, vec![]).is_empty());
+        let expected_slash = SlashRecord {
+            penalty: 15_840_000,
+            slashed: 15_840_000,
+            force_chilled: false,
+        };
+        assert_eq!(
+            XStaking::era_reward_history(era).slashes.get(&1),
+            Some(&expected_slash)
+        );
+        assert_eq!(
+            XStaking::validator_era_history(1),
+            vec![ValidatorEraRecord {
+                era,
+                reward: expected_reward,
+                slash: expected_slash,
+            }]
+        );
+
+        assert_err!(
+            XStaking::set_history_depth(Origin::signed(1), 2, 0),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_err!(
+            XStaking::set_history_depth(Origin::root(), 0, 0),
+            Error::<Test>::ZeroHistoryDepth
+        );
+        assert_ok!(XStaking::set_history_depth(Origin::root(), 2, 10));
+
+        // The records of `era` are kept when `era + 1` starts.
+        let mut session = 1;
+        while XStaking::active_era_index() == era {
+            session += 1;
+            t_start_session(session);
+        }
+        assert_eq!(XStaking::active_era_index(), era + 1);
+        assert_ne!(XStaking::era_reward_history(era), EraRewardInfo::default());
+
+        // The records of `era` are removed once the history depth is lowered to 1.
+        assert_err!(
+            XStaking::set_history_depth(Origin::root(), 1, 0),
+            Error::<Test>::TooManyClearedEras
+        );
+        assert_ok!(XStaking::set_history_depth(Origin::root(), 1, 1));
+        assert_eq!(XStaking::era_reward_history(era), EraRewardInfo::default());
+        assert!(XStaking::validator_era_history(1)
+            .iter()
+            .all(|record| record.era > era));
+    });
+}
+
+#[test]
+fn sequential_phragmen_election_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 67;
+        let t_2 = 68;
+        t_issue_pcx(t_1, 200);
+        t_issue_pcx(t_2, 108);
+
+        // t_1 backs both 3 and 4, while t_2 backs 2 only.
+        assert_ok!(t_bond(t_1, 3, 100));
+        assert_ok!(t_bond(t_1, 4, 100));
+        assert_ok!(t_bond(t_2, 2, 108));
+
+        // 1: 10, 2: 20 + 108 = 128, 3: 30 + 100 = 130, 4: 40 + 100 = 140
+        let candidates = XStaking::filter_out_candidates();
+        assert_eq!(candidates, vec![(140, 4), (130, 3), (128, 2), (10, 1)]);
+
+        assert_eq!(
+            <() as ValidatorElection<Test>>::elect(candidates.clone(), 2),
+            vec![4, 3]
+        );
+
+        // Approval stakes: 2: 128, 3: 230, 4: 240
+        // Round 1: 4 is elected with the score 1/240.
+        // Round 2: score of 2 is 1/128 < score of 3 (1 + 200/240)/230.
+        let mut elected = <SequentialPhragmen as ValidatorElection<Test>>::elect(candidates, 2);
+        elected.sort_unstable();
+        assert_eq!(elected, vec![2, 4]);
+    });
+}
+
+#[test]
+fn offchain_election_solution_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let next_era = XStaking::current_era().map(|e| e + 1).unwrap_or_default();
+        let solution = |era, winners: Vec<AccountId>| ElectionSolution {
+            era,
+            score: XStaking::election_score_of(&winners),
+            winners,
+        };
+
+        assert_err!(
+            XStaking::submit_election_solution(
+                Origin::signed(1),
+                solution(next_era, vec![4, 3, 2, 1])
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_err!(
+            XStaking::submit_election_solution(
+                Origin::none(),
+                solution(next_era + 1, vec![4, 3, 2, 1])
+            ),
+            Error::<Test>::StaleElectionSolution
+        );
+        // Duplicate winners.
+        assert_err!(
+            XStaking::submit_election_solution(
+                Origin::none(),
+                solution(next_era, vec![4, 3, 2, 1, 1])
+            ),
+            Error::<Test>::InvalidElectionSolution
+        );
+        // Fewer winners than the eligible candidates and the validator count.
+        assert_err!(
+            XStaking::submit_election_solution(Origin::none(), solution(next_era, vec![4, 3])),
+            Error::<Test>::InvalidElectionSolution
+        );
+        // Not an eligible candidate.
+        assert_err!(
+            XStaking::submit_election_solution(
+                Origin::none(),
+                solution(next_era, vec![4, 3, 2, 5])
+            ),
+            Error::<Test>::InvalidElectionSolution
+        );
+        // The score does not match the winners.
+        assert_err!(
+            XStaking::submit_election_solution(
+                Origin::none(),
+                ElectionSolution {
+                    era: next_era,
+                    winners: vec![4, 3, 2, 1],
+                    score: Default::default(),
+                }
+            ),
+            Error::<Test>::InvalidElectionSolution
+        );
+
+        assert_ok!(XStaking::submit_election_solution(
+            Origin::none(),
+            solution(next_era, vec![1, 2, 3, 4])
+        ));
+        assert_err!(
+            XStaking::submit_election_solution(
+                Origin::none(),
+                solution(next_era, vec![4, 3, 2, 1])
+            ),
+            Error::<Test>::WeakElectionSolution
+        );
+
+        // The queued solution is replaced only by a solution scoring higher.
+        assert_ok!(XStaking::set_validator_count(Origin::root(), 3));
+        assert_err!(
+            XStaking::submit_election_solution(Origin::none(), solution(next_era, vec![3, 2, 1])),
+            Error::<Test>::WeakElectionSolution
+        );
+        assert_ok!(XStaking::submit_election_solution(
+            Origin::none(),
+            solution(next_era, vec![4, 3, 2])
+        ));
+        assert_ok!(XStaking::set_validator_count(Origin::root(), 6));
+
+        // The queued solution is used instead of the on-chain election.
+        let mut session = 0;
+        while XStaking::current_era() != Some(next_era) {
+            session += 1;
+            t_start_session(session);
+        }
+        assert_eq!(XStaking::queued_elected(), None);
+
+        t_start_session(session + 1);
+        let mut validators = Session::validators();
+        validators.sort_unstable();
+        assert_eq!(validators, vec![2, 3, 4]);
+    });
+}
+
+#[test]
+fn slashing_span_and_grace_offences_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        XStaking::mint(&888, (FIXED_TOTAL / 2) as u128);
+        t_start_session(1);
+
+        let slash = |offender: AccountId, percent: u32| {
+            let offenders = vec![(offender, Perbill::from_percent(percent))]
+                .into_iter()
+                .collect();
+            XStaking::slash_offenders_in_session(offenders, vec![])
+        };
+
+        // 1: 158_400_000 * 10% = 15_840_000
+        assert_eq!(t_reward_pot_balance(1), 158_400_000);
+        slash(1, 10);
+        assert_eq!(t_reward_pot_balance(1), 142_560_000);
+        // 142_560_000 * 10% = 14_256_000 has been counted in this span.
+        slash(1, 10);
+        assert_eq!(t_reward_pot_balance(1), 142_560_000);
+        // 142_560_000 * 20% - 15_840_000 = 12_672_000
+        slash(1, 20);
+        assert_eq!(t_reward_pot_balance(1), 129_888_000);
+        assert_eq!(XStaking::slashing_spans(1).offences, 3);
+        assert_eq!(XStaking::slashing_spans(1).penalty, 28_512_000);
+
+        // The first offence of 2 is tolerated.
+        assert_ok!(XStaking::set_grace_offences(Origin::root(), 1));
+        slash(2, 10);
+        assert_eq!(t_reward_pot_balance(2), 316_800_000);
+        slash(2, 10);
+        assert_eq!(t_reward_pot_balance(2), 285_120_000);
+    });
+}
+
+#[test]
+fn deferred_slash_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        XStaking::mint(&888, (FIXED_TOTAL / 2) as u128);
+        t_start_session(1);
+
+        let era = XStaking::active_era_index();
+        assert_ok!(XStaking::set_slash_defer_duration(Origin::root(), 1));
+
+        // 3: 475_200_000 * 10% = 47_520_000
+        // 4: 633_600_000 * 10% = 63_360_000
+        let offenders = vec![
+            (3, Perbill::from_percent(10)),
+            (4, Perbill::from_percent(10)),
+        ]
+        .into_iter()
+        .collect();
+        assert!(XStaking::slash_offenders_in_session(offenders, vec![]).is_empty());
+        assert_eq!(t_reward_pot_balance(3), 475_200_000);
+        assert_eq!(t_reward_pot_balance(4), 633_600_000);
+        assert_eq!(
+            XStaking::unapplied_slashes(era + 1),
+            vec![
+                UnappliedSlash {
+                    offender: 3,
+                    penalty: 47_520_000,
+                    reported_era: era,
+                },
+                UnappliedSlash {
+                    offender: 4,
+                    penalty: 63_360_000,
+                    reported_era: era,
+                },
+            ]
+        );
+
+        assert_err!(
+            XStaking::cancel_deferred_slash(Origin::signed(1), era + 1, vec![1]),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_err!(
+            XStaking::cancel_deferred_slash(Origin::root(), era + 1, vec![]),
+            Error::<Test>::EmptySlashIndices
+        );
+        assert_err!(
+            XStaking::cancel_deferred_slash(Origin::root(), era + 1, vec![1, 0]),
+            Error::<Test>::SlashIndicesNotSortedAndUnique
+        );
+        assert_err!(
+            XStaking::cancel_deferred_slash(Origin::root(), era + 1, vec![2]),
+            Error::<Test>::InvalidSlashIndex
+        );
+        assert_ok!(XStaking::cancel_deferred_slash(
+            Origin::root(),
+            era + 1,
+            vec![1]
+        ));
+        assert_eq!(XStaking::unapplied_slashes(era + 1).len(), 1);
+
+        // The remaining slash is applied once the next era starts.
+        let mut session = 1;
+        while XStaking::active_era_index() == era {
+            session += 1;
+            t_start_session(session);
+        }
+        assert!(XStaking::unapplied_slashes(era + 1).is_empty());
+        // The slash is recorded in the era in which the offence was reported.
+        assert_eq!(XStaking::eras_slash(era, 3).slashed, 47_520_000);
+        assert_eq!(XStaking::eras_slash(era + 1, 3), SlashRecord::default());
+        assert_eq!(XStaking::eras_slash(era, 4), SlashRecord::default());
+        // The slashing spans of the last era are pruned.
+        assert!(!SlashingSpans::<Test>::contains_key(3));
+    });
+}
+
 #[test]
 fn referral_id_should_work() {
     ExtBuilder::default().build_and_execute(|| {
@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 
 use sp_runtime::{
     traits::{SaturatedConversion, Saturating},
-    DispatchError, DispatchResult, RuntimeDebug,
+    DispatchError, DispatchResult, Perbill, RuntimeDebug,
 };
 
 use chainx_primitives::{AssetId, ReferralId};
@@ -109,6 +109,78 @@ pub struct ValidatorProfile<BlockNumber> {
     /// Referral identity that belongs to the validator.
     #[cfg_attr(feature = "std", serde(with = "xp_rpc::serde_text"))]
     pub referral_id: ReferralId,
+    /// Cut of the session reward taken by the validator, the rest goes to the reward pot.
+    pub commission: Perbill,
+    /// Era index of last performed `set_commission` operation.
+    pub last_commission_change: Option<EraIndex>,
+}
+
+/// Session rewards of a validator accumulated in an era.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ValidatorEraReward<Balance> {
+    /// Commission minted to the validator.
+    pub to_validator: Balance,
+    /// The rest minted to the reward pot of validator.
+    pub to_reward_pot: Balance,
+}
+
+/// Slashes of a validator accumulated in an era.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct SlashRecord<Balance> {
+    /// Total penalty that the validator should pay.
+    pub penalty: Balance,
+    /// Total balance actually slashed from the reward pot.
+    pub slashed: Balance,
+    /// Validator has been forced to be chilled due to the insufficient reward pot.
+    pub force_chilled: bool,
+}
+
+/// Offences of a validator counted in an era.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct SlashingSpan<Balance> {
+    /// Era in which this span starts.
+    pub start: EraIndex,
+    /// Number of offences in this span.
+    pub offences: u32,
+    /// The largest penalty counted in this span.
+    pub penalty: Balance,
+}
+
+/// A slash deferred to be applied in a future era.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct UnappliedSlash<AccountId, Balance> {
+    /// The offender to be slashed.
+    pub offender: AccountId,
+    /// Penalty to slash from the reward pot of offender.
+    pub penalty: Balance,
+    /// Era in which the offence was reported.
+    pub reported_era: EraIndex,
+}
+
+/// Score of an election solution, compared by the minimal backing first.
+#[derive(
+    PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default, Encode, Decode, RuntimeDebug, TypeInfo,
+)]
+pub struct ElectionScore<Balance> {
+    /// Minimal total nominations of the winners.
+    pub minimal_backing: Balance,
+    /// Sum of the total nominations of the winners.
+    pub total_backing: Balance,
+}
+
+/// Validator set of the next era computed by the offchain worker.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ElectionSolution<AccountId, Balance> {
+    /// Era in which the winners will be the validators.
+    pub era: EraIndex,
+    /// Elected validators.
+    pub winners: Vec<AccountId>,
+    /// Score of the winners, a solution replaces the queued one only if it scores higher.
+    pub score: ElectionScore<Balance>,
 }
 
 /// Information regarding the active era (era in used in session).
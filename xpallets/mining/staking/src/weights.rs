@@ -35,16 +35,27 @@ pub trait WeightInfo {
     fn bond() -> Weight;
     fn unbond() -> Weight;
     fn unlock_unbonded_withdrawal() -> Weight;
+    fn instant_unbond() -> Weight;
     fn rebond() -> Weight;
     fn claim() -> Weight;
     fn chill() -> Weight;
     fn validate() -> Weight;
+    fn set_commission() -> Weight;
     fn set_validator_count() -> Weight;
     fn set_minimum_validator_count() -> Weight;
     fn set_bonding_duration() -> Weight;
     fn set_validator_bonding_duration() -> Weight;
     fn set_minimum_penalty() -> Weight;
+    fn set_maximum_commission() -> Weight;
+    fn set_maximum_commission_change() -> Weight;
     fn set_sessions_per_era() -> Weight;
+    fn set_instant_unbond_penalty() -> Weight;
+    fn set_history_depth(e: u32) -> Weight;
+    fn set_slash_defer_duration() -> Weight;
+    fn set_grace_offences() -> Weight;
+    fn cancel_deferred_slash(s: u32) -> Weight;
+    fn set_auto_compound() -> Weight;
+    fn submit_election_solution(c: u32, n: u32) -> Weight;
 }
 
 /// Weights for xpallet_mining_staking using the Substrate node and recommended hardware.
@@ -70,15 +81,20 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(4 as Weight))
             .saturating_add(T::DbWeight::get().writes(4 as Weight))
     }
+    fn instant_unbond() -> Weight {
+        (124_637_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(10 as Weight))
+            .saturating_add(T::DbWeight::get().writes(7 as Weight))
+    }
     fn rebond() -> Weight {
         (111_922_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(10 as Weight))
             .saturating_add(T::DbWeight::get().writes(5 as Weight))
     }
     fn claim() -> Weight {
-        (96_268_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(5 as Weight))
-            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+        (181_576_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(11 as Weight))
+            .saturating_add(T::DbWeight::get().writes(7 as Weight))
     }
     fn chill() -> Weight {
         (1_141_804_000 as Weight)
@@ -90,6 +106,11 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(1 as Weight))
             .saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn set_commission() -> Weight {
+        (32_947_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
     fn set_validator_count() -> Weight {
         (2_276_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
@@ -105,9 +126,47 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     fn set_minimum_penalty() -> Weight {
         (2_469_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn set_maximum_commission() -> Weight {
+        (2_391_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_maximum_commission_change() -> Weight {
+        (2_384_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
     fn set_sessions_per_era() -> Weight {
         (2_275_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn set_instant_unbond_penalty() -> Weight {
+        (2_412_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_history_depth(e: u32) -> Weight {
+        (11_840_000 as Weight)
+            .saturating_add((6_294_000 as Weight).saturating_mul(e as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(e as Weight)))
+    }
+    fn set_slash_defer_duration() -> Weight {
+        (2_318_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_grace_offences() -> Weight {
+        (2_287_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn cancel_deferred_slash(s: u32) -> Weight {
+        (38_471_000 as Weight)
+            .saturating_add((1_953_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_auto_compound() -> Weight {
+        (12_418_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn submit_election_solution(c: u32, _n: u32) -> Weight {
+        (21_583_000 as Weight)
+            .saturating_add((6_314_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(c as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -132,15 +191,20 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(4 as Weight))
             .saturating_add(RocksDbWeight::get().writes(4 as Weight))
     }
+    fn instant_unbond() -> Weight {
+        (124_637_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(10 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(7 as Weight))
+    }
     fn rebond() -> Weight {
         (111_922_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(10 as Weight))
             .saturating_add(RocksDbWeight::get().writes(5 as Weight))
     }
     fn claim() -> Weight {
-        (96_268_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+        (181_576_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(11 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(7 as Weight))
     }
     fn chill() -> Weight {
         (1_141_804_000 as Weight)
@@ -152,6 +216,11 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(1 as Weight))
             .saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn set_commission() -> Weight {
+        (32_947_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
     fn set_validator_count() -> Weight {
         (2_276_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
@@ -167,7 +236,45 @@ impl WeightInfo for () {
     fn set_minimum_penalty() -> Weight {
         (2_469_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn set_maximum_commission() -> Weight {
+        (2_391_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_maximum_commission_change() -> Weight {
+        (2_384_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
     fn set_sessions_per_era() -> Weight {
         (2_275_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn set_instant_unbond_penalty() -> Weight {
+        (2_412_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_history_depth(e: u32) -> Weight {
+        (11_840_000 as Weight)
+            .saturating_add((6_294_000 as Weight).saturating_mul(e as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(e as Weight)))
+    }
+    fn set_slash_defer_duration() -> Weight {
+        (2_318_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_grace_offences() -> Weight {
+        (2_287_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn cancel_deferred_slash(s: u32) -> Weight {
+        (38_471_000 as Weight)
+            .saturating_add((1_953_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_auto_compound() -> Weight {
+        (12_418_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn submit_election_solution(c: u32, _n: u32) -> Weight {
+        (21_583_000 as Weight)
+            .saturating_add((6_314_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(c as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
 }
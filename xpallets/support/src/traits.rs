@@ -41,6 +41,19 @@ impl<AccountId> TreasuryAccount<AccountId> for () {
     }
 }
 
+/// This trait provides the manipulation-resistant price derived from the trades.
+pub trait PriceOracle<PairId, Price> {
+    /// Returns the time-weighted average price of the trading pair over the
+    /// configured window, `None` if there is no trade so far.
+    fn twap(pair_id: PairId) -> Option<Price>;
+}
+
+impl<PairId, Price> PriceOracle<PairId, Price> for () {
+    fn twap(_: PairId) -> Option<Price> {
+        None
+    }
+}
+
 /*
 这段代码定义了几个用于区块链系统中的多签名地址计算,多签名验证和国库账户检索的trait(特质),以及它们的默认实现.
 这些trait和实现通常用于区块链项目的模块开发中,以便在不依赖特定运行时环境的情况下提供通用的功能.